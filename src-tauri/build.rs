@@ -1,3 +1,14 @@
 fn main() {
+    // Expose the resolved whisper-rs version to the app's build info
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    let lock = std::fs::read_to_string("Cargo.lock").unwrap_or_default();
+    let version = lock
+        .split("[[package]]")
+        .find(|package| package.lines().any(|line| line.trim() == "name = \"whisper-rs\""))
+        .and_then(|package| package.lines().find_map(|line| line.trim().strip_prefix("version = \"")))
+        .and_then(|version| version.strip_suffix('"'))
+        .unwrap_or("unknown");
+    println!("cargo:rustc-env=WHISPER_RS_VERSION={}", version);
+
     tauri_build::build()
 }
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicIsize, AtomicU64, Ordering},
     Arc, Mutex,
 };
 
@@ -10,15 +10,28 @@ use cpal::Sample;
 use futures_util::StreamExt;
 use rdev::{listen, simulate, Event, EventType, Key};
 use rubato::{Resampler, SincFixedIn, SincInterpolationParameters, SincInterpolationType, WindowFunction};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tauri::{
     menu::{Menu, MenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    AppHandle, Emitter, Manager, PhysicalPosition, WindowEvent,
+    AppHandle, DragDropEvent, Emitter, Manager, PhysicalPosition, WindowEvent,
 };
 use tauri_plugin_autostart::MacosLauncher;
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
 
+/// Display monitor info returned to frontend
+#[derive(Serialize)]
+pub struct MonitorInfo {
+    pub id: String,
+    pub name: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+    pub is_primary: bool,
+}
+
 /// Preset model definition
 #[derive(Clone, Serialize)]
 pub struct PresetModel {
@@ -29,6 +42,36 @@ pub struct PresetModel {
     pub url: String,
 }
 
+/// Custom model file imported into the models directory
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CustomModel {
+    pub id: String,
+    pub name: String,
+    pub filename: String,
+    pub size: String,
+}
+
+/// Named bundle of dictation settings that can be applied at once
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub settings: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Portable bundle of tuned settings that can be shared with other users.
+/// Holds no paths, devices, history or other personal data.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ShareablePreset {
+    pub format_version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Preset model id the settings were tuned for
+    #[serde(default)]
+    pub recommended_model: Option<String>,
+    pub settings: serde_json::Map<String, serde_json::Value>,
+}
+
 /// Model info returned to frontend
 #[derive(Serialize)]
 pub struct ModelInfo {
@@ -38,9 +81,675 @@ pub struct ModelInfo {
     pub size: String,
     pub downloaded: bool,
     pub active: bool,
+    pub english_only: bool,
+}
+
+/// A downloaded model with when it was last loaded or used, for `list_models_by_usage`
+#[derive(Serialize)]
+pub struct ModelUsage {
+    pub id: String,
+    pub name: String,
+    pub filename: String,
+    pub size_bytes: u64,
+    pub active: bool,
+    /// Unix time of the last load or transcription (None = never used)
+    pub last_used: Option<u64>,
+}
+
+/// Find/replace rule applied to transcripts before output
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ReplacementRule {
+    pub find: String,
+    pub replace: String,
+    #[serde(default)]
+    pub regex: bool,
+}
+
+/// A replacement rule that could not be applied
+#[derive(Serialize)]
+pub struct ReplacementRuleError {
+    pub index: usize,
+    pub find: String,
+    pub error: String,
+}
+
+/// Result of previewing replacement rules against sample text
+#[derive(Serialize)]
+pub struct ReplacementPreview {
+    pub output: String,
+    pub errors: Vec<ReplacementRuleError>,
+}
+
+/// Number formatting settings ("off", "digits" or "words")
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NumberFormatting {
+    pub mode: String,
+    #[serde(default = "default_number_locale")]
+    pub locale: String,
+}
+
+fn default_number_locale() -> String {
+    "en".to_string()
+}
+
+impl Default for NumberFormatting {
+    fn default() -> Self {
+        Self {
+            mode: "off".to_string(),
+            locale: default_number_locale(),
+        }
+    }
+}
+
+/// GPU backend preference ("auto", "cpu", "cuda", "vulkan" or "metal")
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GpuSettings {
+    pub backend: String,
+    #[serde(default)]
+    pub device: i32,
+}
+
+impl Default for GpuSettings {
+    fn default() -> Self {
+        Self {
+            backend: "auto".to_string(),
+            device: 0,
+        }
+    }
+}
+
+/// GPU backend status returned to frontend
+#[derive(Serialize)]
+pub struct GpuBackendInfo {
+    pub preferred: String,
+    pub selected: Option<String>,
+    pub available: Vec<String>,
+}
+
+/// Whisper decoding settings, read from config before each transcription
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DecodingOptions {
+    /// ISO 639-1 code to transcribe in, or "auto" to detect it
+    pub language: String,
+    /// Translate speech into English instead of transcribing it
+    pub translate: bool,
+    /// Break segments on word boundaries rather than tokens
+    pub split_on_word: bool,
+    /// Maximum segment length in characters (0 = no limit)
+    pub max_len: u32,
+    /// How much CPU transcription may use
+    pub performance_mode: PerformanceMode,
+    /// Explicit inference thread count, overriding the performance mode (None = use the mode)
+    pub n_threads: Option<u32>,
+    /// Decoder sampling: greedy (fast, the default) or beam search (slower, more accurate)
+    pub sampling: SamplingSettings,
+    /// Text fed to Whisper as preceding context to bias it toward names and terms
+    /// (empty = none)
+    pub initial_prompt: String,
+    /// Initial decoding temperature; only raised internally for the blank-result retry
+    #[serde(skip)]
+    pub temperature: f32,
+    /// Experimental: auto-detect instead of forcing a language, and detect each
+    /// segment's language for audio that switches languages
+    pub detect_segment_languages: bool,
+    /// What goes between segments in the transcript text
+    pub segment_separator: SegmentSeparator,
+}
+
+impl Default for DecodingOptions {
+    fn default() -> Self {
+        Self {
+            language: "en".to_string(),
+            translate: false,
+            split_on_word: false,
+            max_len: 0,
+            performance_mode: PerformanceMode::default(),
+            n_threads: None,
+            sampling: SamplingSettings::default(),
+            initial_prompt: String::new(),
+            temperature: 0.0,
+            detect_segment_languages: false,
+            segment_separator: SegmentSeparator::default(),
+        }
+    }
+}
+
+/// Decoder search strategy
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SamplingKind {
+    /// Pick the most likely token at each step
+    #[default]
+    Greedy,
+    /// Keep several candidate transcripts and pick the best; better on technical
+    /// terms and names, but several times slower
+    Beam,
+}
+
+/// Sampling parameters. Greedy with best_of 1 is Whisper's fastest setting; beam
+/// search with 5 beams matches the reference implementation.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SamplingSettings {
+    pub sampling_strategy: SamplingKind,
+    /// Candidates sampled when decoding falls back to a higher temperature (greedy only)
+    pub best_of: u32,
+    /// Beams kept during beam search
+    pub beam_size: u32,
+}
+
+impl Default for SamplingSettings {
+    fn default() -> Self {
+        Self {
+            sampling_strategy: SamplingKind::Greedy,
+            best_of: 1,
+            beam_size: 5,
+        }
+    }
+}
+
+impl SamplingSettings {
+    fn strategy(&self) -> SamplingStrategy {
+        match self.sampling_strategy {
+            SamplingKind::Greedy => SamplingStrategy::Greedy { best_of: self.best_of.max(1) as i32 },
+            // Negative patience uses whisper.cpp's default
+            SamplingKind::Beam => SamplingStrategy::BeamSearch { beam_size: self.beam_size.max(1) as i32, patience: -1.0 },
+        }
+    }
+}
+
+/// How segment texts are joined into the transcript
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentSeparator {
+    /// A single space, added only where a segment doesn't already start or end with one
+    #[default]
+    Space,
+    /// One segment per line
+    Newline,
+}
+
+impl SegmentSeparator {
+    /// Joins raw segment texts so words at segment boundaries aren't glued together
+    fn join(self, segments: &[String]) -> String {
+        if self == SegmentSeparator::Newline {
+            return segments.iter()
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+        let mut joined = String::new();
+        for segment in segments.iter().filter(|s| !s.trim().is_empty()) {
+            let needs_space = !joined.is_empty()
+                && !joined.ends_with(char::is_whitespace)
+                && !segment.starts_with(char::is_whitespace);
+            if needs_space {
+                joined.push(' ');
+            }
+            joined.push_str(segment);
+        }
+        joined.trim().to_string()
+    }
+}
+
+/// Trades transcription latency for CPU load and heat
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PerformanceMode {
+    /// One or two threads: slower, but keeps the machine cool and responsive
+    Eco,
+    /// Up to four threads
+    #[default]
+    Balanced,
+    /// Every available core
+    Max,
+}
+
+impl DecodingOptions {
+    /// Inference thread count: the explicit setting, else the performance mode's
+    fn thread_count(&self) -> i32 {
+        self.n_threads.map_or_else(|| self.performance_mode.n_threads(), |n| n.max(1) as i32)
+    }
+}
+
+impl PerformanceMode {
+    /// Inference thread count for this mode on the current machine
+    fn n_threads(self) -> i32 {
+        let cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4);
+        let threads = match self {
+            PerformanceMode::Eco => (cores / 4).clamp(1, 2),
+            PerformanceMode::Balanced => cores.min(4),
+            PerformanceMode::Max => cores,
+        };
+        threads as i32
+    }
+}
+
+/// Voice activity filter settings for removing non-speech before transcription
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VadSettings {
+    /// Remove internal silence/non-speech gaps before transcription
+    pub enabled: bool,
+    /// 0 (keeps the most audio) to 3 (removes the most)
+    pub aggressiveness: u8,
+    /// Fixed RMS speech threshold; None adapts to each recording's noise floor
+    pub threshold: Option<f32>,
+}
+
+impl Default for VadSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            aggressiveness: 1,
+            threshold: None,
+        }
+    }
+}
+
+/// Live noise gate: mutes capture below a level so background noise never reaches Whisper
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NoiseGateSettings {
+    pub enabled: bool,
+    /// Peak level (0-1) that opens the gate
+    pub threshold: f32,
+    /// Fade-in time once the gate opens
+    pub attack_ms: u32,
+    /// Time the gate stays open after the level drops, so word endings aren't cut
+    pub hold_ms: u32,
+    /// Fade-out time once the hold expires
+    pub release_ms: u32,
+}
+
+impl Default for NoiseGateSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: 0.01,
+            attack_ms: 2,
+            hold_ms: 250,
+            release_ms: 150,
+        }
+    }
+}
+
+/// Automatic gain control: evens out level changes within a recording, e.g. when
+/// the speaker moves toward or away from the mic
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgcSettings {
+    pub enabled: bool,
+    /// RMS level (0-1) speech is brought toward
+    pub target_level: f32,
+    /// How quickly gain drops when the level rises
+    pub attack_ms: u32,
+    /// How quickly gain recovers when the level falls
+    pub release_ms: u32,
+    /// Gain ceiling, so quiet passages and noise aren't amplified without bound
+    pub max_gain: f32,
+}
+
+impl Default for AgcSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_level: 0.1,
+            attack_ms: 50,
+            release_ms: 500,
+            max_gain: 10.0,
+        }
+    }
+}
+
+/// Defer under load: waits for system CPU usage to drop before starting inference,
+/// so dictating while gaming or rendering doesn't make the foreground app stutter
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoadDeferSettings {
+    pub enabled: bool,
+    /// System CPU usage (percent) above which transcription waits
+    pub cpu_threshold_percent: u32,
+    /// Longest wait before transcribing anyway
+    pub max_defer_seconds: u64,
+}
+
+impl Default for LoadDeferSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cpu_threshold_percent: 80,
+            max_defer_seconds: 10,
+        }
+    }
+}
+
+/// Webhook output: each transcript is POSTed as JSON (the `transcription_result` payload)
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookSettings {
+    /// Endpoint to POST to (empty = disabled)
+    pub url: String,
+    /// Send to the webhook instead of pasting; paste is still used if the webhook fails
+    pub replace_paste: bool,
+    pub timeout_ms: u64,
+}
+
+impl Default for WebhookSettings {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            replace_paste: false,
+            timeout_ms: 2000,
+        }
+    }
+}
+
+impl WebhookSettings {
+    fn is_enabled(&self) -> bool {
+        !self.url.is_empty()
+    }
+}
+
+/// Journal output: each transcript is appended to a plain-text file the user picked
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JournalSettings {
+    /// File to append to (empty = disabled)
+    pub path: String,
+    /// Prefix each entry with the UTC date and time
+    pub timestamps: bool,
+}
+
+impl Default for JournalSettings {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            timestamps: true,
+        }
+    }
+}
+
+impl JournalSettings {
+    fn is_enabled(&self) -> bool {
+        !self.path.is_empty()
+    }
+}
+
+/// A timed piece of a transcript, in milliseconds from the start of the audio
+#[derive(Clone, Serialize)]
+pub struct TranscriptSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+    /// Detected language of this segment, when per-segment detection is on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// Action a spoken voice command performs
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VoiceAction {
+    NewLine,
+    NewParagraph,
+    DeleteThat,  // Drops what came before in this dictation, or the previous dictation
+    AllCaps,     // Uppercases the following words up to the next punctuation
+}
+
+/// Voice command layer: spoken phrases mapped to actions, applied after transcription
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VoiceCommandSettings {
+    pub enabled: bool,
+    pub phrases: std::collections::BTreeMap<String, VoiceAction>,
+}
+
+impl Default for VoiceCommandSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            phrases: [
+                ("new line", VoiceAction::NewLine),
+                ("new paragraph", VoiceAction::NewParagraph),
+                ("delete that", VoiceAction::DeleteThat),
+                ("all caps", VoiceAction::AllCaps),
+            ]
+            .into_iter()
+            .map(|(phrase, action)| (phrase.to_string(), action))
+            .collect(),
+        }
+    }
+}
+
+/// Phrases stripped when Whisper appends them to the end of a transcript on silence
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrailingPhraseSettings {
+    pub enabled: bool,
+    pub phrases: Vec<String>,
+    /// Extra phrases only stripped for the given model ID, for hallucinations
+    /// particular to one model
+    pub model_phrases: std::collections::HashMap<String, Vec<String>>,
+}
+
+impl Default for TrailingPhraseSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            phrases: ["Thank you.", "Thanks for watching!", "Bye."]
+                .into_iter()
+                .map(|p| p.to_string())
+                .collect(),
+            model_phrases: std::collections::HashMap::new(),
+        }
+    }
+}
+
+impl TrailingPhraseSettings {
+    /// The shared phrases plus those configured for `model_id`
+    fn phrases_for(&self, model_id: Option<&str>) -> Vec<String> {
+        let extra = model_id.and_then(|id| self.model_phrases.get(id)).into_iter().flatten();
+        self.phrases.iter().chain(extra).cloned().collect()
+    }
+}
+
+/// Pastes each sentence of a transcript separately with Enter between, so one
+/// dictation becomes several chat messages
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SentenceSplitSettings {
+    pub enabled: bool,
+    pub delay_ms: u64,  // Wait around each Enter so the app can send the message
+}
+
+impl Default for SentenceSplitSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            delay_ms: 150,
+        }
+    }
+}
+
+/// Transcript after voice commands were applied
+pub struct VoiceCommandResult {
+    pub text: String,
+    pub delete_previous: bool,  // "delete that" with nothing before it: remove the previous dictation
+}
+
+/// One model's row in the full benchmark
+#[derive(Clone, Serialize)]
+pub struct ModelBenchmark {
+    pub model_id: String,
+    pub name: String,
+    pub load_ms: u64,
+    pub resample_ms: u64,
+    pub inference_ms: u64,
+    /// Processing time over clip duration (below 1.0 is faster than realtime)
+    pub realtime_factor: f32,
+    pub error: Option<String>,
+}
+
+/// Result of `run_full_benchmark`: every downloaded model on the same clip
+#[derive(Clone, Serialize)]
+pub struct FullBenchmark {
+    pub clip: String,
+    pub clip_seconds: f32,
+    pub sample_rate: u32,
+    pub models: Vec<ModelBenchmark>,
+}
+
+/// Cancels one Whisper run: checked before inference starts and by the abort
+/// callback during it, so aborting one run never touches another
+#[derive(Clone, Default)]
+pub struct AbortToken(Arc<AtomicBool>);
+
+impl AbortToken {
+    fn abort(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+    
+    fn is_aborted(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Error returned by a Whisper run that was aborted
+const TRANSCRIPTION_ABORTED: &str = "Transcription aborted";
+
+/// Output of a single Whisper run
+#[derive(Clone)]
+pub struct Transcription {
+    pub text: String,
+    pub segments: Vec<TranscriptSegment>,
+    pub language: Option<String>,
+    pub confidence: Option<f32>,
+    pub resample_ms: u64,
+    pub inference_ms: u64,
+}
+
+/// Payload of the `transcription_result` event (versioned; `transcription_done`
+/// still carries just the text for existing listeners)
+#[derive(Clone, Serialize)]
+pub struct TranscriptionResult {
+    pub version: u32,
+    pub text: String,
+    pub model_id: Option<String>,
+    pub language: Option<String>,
+    pub duration_seconds: f32,
+    pub inference_ms: u64,
+    pub confidence: Option<f32>,
+    pub word_count: usize,
+    /// Distinct languages across segments, when per-segment detection is on
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub segment_languages: Vec<String>,
+}
+
+/// Bounds on the retained last recording
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionSettings {
+    /// Seconds of the most recent audio to keep (0 = don't retain)
+    pub max_seconds: u64,
+    /// Seconds after which the retained audio is dropped (0 = keep until replaced)
+    pub timeout_seconds: u64,
+}
+
+impl Default for RetentionSettings {
+    fn default() -> Self {
+        Self {
+            max_seconds: 120,
+            timeout_seconds: 600,
+        }
+    }
+}
+
+/// Time spent in each phase between stopping a recording and pasting the text
+#[derive(Clone, Serialize)]
+pub struct LatencyBreakdown {
+    pub stop_ms: u64,
+    pub resample_ms: u64,
+    pub inference_ms: u64,
+    pub paste_ms: u64,
+    pub total_ms: u64,
+}
+
+/// Summary of a bounded microphone test for onboarding
+#[derive(Serialize)]
+pub struct MicTestResult {
+    pub device: String,
+    pub duration_seconds: f32,
+    pub peak: f32,
+    pub average_rms: f32,
+    pub clipping_ratio: f32,
+    pub verdict: String,  // "pass", "no_signal", "too_quiet" or "clipping"
+}
+
+/// Result of measuring the ambient noise floor of a microphone
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NoiseCalibration {
+    pub device: String,
+    pub noise_floor: f32,  // Median frame RMS of the ambient recording
+    pub threshold: f32,    // Speech threshold derived from it, used by VAD and endpointing
+}
+
+/// Diagnostics from running a short recording through the resampler
+#[derive(Serialize)]
+pub struct ResamplePreview {
+    pub source_rate: u32,
+    pub input_samples: usize,
+    pub output_samples: usize,
+    pub expected_output_samples: usize,
+    pub input_peak: f32,
+    pub output_peak: f32,
+    pub input_clipped: usize,   // Samples at or beyond full scale before resampling
+    pub output_clipped: usize,  // ...and after; more here means the resampler introduced clipping
+    pub input_rolloff_hz: f32,  // Frequency below which 85% of the spectral energy lies
+    pub output_rolloff_hz: f32,
+    pub resample_ms: u64,
 }
 
-/// Audio input device info
+/// Build and runtime capabilities, for the UI and bug reports
+#[derive(Serialize)]
+pub struct BuildInfo {
+    pub app_version: String,
+    pub whisper_rs_version: String,
+    pub gpu_backends: Vec<String>,
+    pub audio_hosts: Vec<String>,
+    pub default_audio_host: String,
+    pub os: String,
+    pub arch: String,
+    pub debug_build: bool,
+    pub whisper_system_info: String,
+    /// Whether models can be memory-mapped instead of read in full at load time
+    pub mmap_model_loading: bool,
+}
+
+/// Outcome of pasting a known string into the app's scratch window
+#[derive(Serialize)]
+pub struct PasteTestResult {
+    pub success: bool,
+    pub expected: String,
+    pub received: Option<String>,
+    pub latency_ms: u64,
+}
+
+/// Summary of repeated paste round-trips into the scratch window
+#[derive(Serialize)]
+pub struct PasteReliabilityResult {
+    pub attempts: usize,
+    pub successes: usize,
+    pub success_rate: f32,
+    pub average_latency_ms: u64,
+    /// Set when reliability is poor enough that auto-paste shouldn't be relied on
+    pub suggestion: Option<String>,
+    pub results: Vec<PasteTestResult>,
+}
+
+/// Audio input or output device info
 #[derive(Clone, Serialize)]
 pub struct AudioDeviceInfo {
     pub id: String,
@@ -290,73 +999,538 @@ fn get_preset_models() -> Vec<PresetModel> {
     ]
 }
 
+/// Get list of preset VAD models, stored in the models dir alongside the Whisper models
+fn get_vad_presets() -> Vec<PresetModel> {
+    vec![
+        PresetModel {
+            id: "silero-v5.1.2".to_string(),
+            name: "Silero VAD v5.1.2".to_string(),
+            filename: "ggml-silero-v5.1.2.bin".to_string(),
+            size: "885 KB".to_string(),
+            url: "https://huggingface.co/ggml-org/whisper-vad/resolve/main/ggml-silero-v5.1.2.bin".to_string(),
+        },
+    ]
+}
+
+/// Preset models plus any imported custom models
+fn get_all_models(app: &AppHandle) -> Vec<PresetModel> {
+    let mut models = get_preset_models();
+    models.extend(load_custom_models(app).into_iter().map(|m| PresetModel {
+        id: m.id,
+        name: m.name,
+        filename: m.filename,
+        size: m.size,
+        url: String::new(),
+    }));
+    models
+}
+
 /// Shared state for tracking recording status
 pub struct RecordingState {
     pub is_recording: AtomicBool,
     pub is_processing: AtomicBool,  // True while transcription is in progress
+    pub session_id: AtomicU64,      // Incremented on every stop-recording cycle
+    pub pasted_session: AtomicU64,  // Last session whose transcript was pasted
+    pub continuous: AtomicBool,     // Restart recording after each transcription
+    pub is_paused: AtomicBool,      // Recording is paused: stream stopped, buffer kept
+    pub start_queued: AtomicBool,   // Hotkey pressed during processing; start when done
+    pub start_window: AtomicIsize,  // Window focused when recording started (0 = unknown)
+    pub processing_abort: Mutex<AbortToken>,  // Aborts the transcription of the current session
+}
+
+impl RecordingState {
+    /// Claims the paste for a session. Returns false if that session already pasted,
+    /// so the paste side-effect stays idempotent per recording.
+    fn claim_paste(&self, session: u64) -> bool {
+        self.pasted_session.swap(session, Ordering::SeqCst) != session
+    }
 }
 
+/// How long stopping waits for the recording thread to drop its stream
+const STREAM_STOP_TIMEOUT_MS: u64 = 1000;
+
 /// Audio context holding captured samples (stream is kept local to recording thread)
 pub struct AudioContext {
     pub buffer: Vec<f32>,
     pub sample_rate: u32,
     pub stop_signal: Arc<AtomicBool>,
+    pub stream_stopped: Arc<AtomicBool>,  // Set by the recording thread once its stream is dropped
+    pub disk: Option<DiskRecording>,      // Long-recording mode: audio spilled to a WAV file
 }
 
-pub type SharedAudio = Arc<Mutex<AudioContext>>;
+/// Seconds of audio buffered in memory before it is spilled to disk
+const DISK_FLUSH_SECONDS: usize = 10;
 
-/// Whisper context state for transcription
-pub struct WhisperState {
-    pub ctx: Option<WhisperContext>,
-    pub model_path: Option<PathBuf>,
-}
+/// Seconds kept in memory after a spill, for the level meter and endpointing
+const DISK_KEEP_SECONDS: usize = 1;
 
-pub type SharedWhisper = Arc<Mutex<WhisperState>>;
+/// Length of each piece a spilled recording is transcribed in
+const DISK_CHUNK_SECONDS: usize = 120;
 
-/// Computes the RMS (root mean square) of the last N samples for waveform visualization
-fn compute_rms(samples: &[f32], window_size: usize) -> f32 {
-    if samples.is_empty() {
-        return 0.0;
-    }
-    let start = if samples.len() > window_size {
-        samples.len() - window_size
-    } else {
-        0
-    };
-    let window = &samples[start..];
-    let sum_sq: f32 = window.iter().map(|s| s * s).sum();
-    (sum_sq / window.len() as f32).sqrt()
+/// Audio from the end of the previous chunk decoded again at the start of the next,
+/// so words cut at a chunk boundary are heard whole at least once
+const DISK_CHUNK_OVERLAP_SECONDS: usize = 3;
+
+/// A recording being written to a temp WAV file instead of held in RAM, so memory
+/// stays bounded however long the session runs
+pub struct DiskRecording {
+    path: PathBuf,
+    writer: hound::WavWriter<std::io::BufWriter<std::fs::File>>,
+    samples_written: usize,
 }
 
-/// Resamples audio from source_rate to 16kHz (required by Whisper)
-fn resample_to_16khz(samples: &[f32], source_rate: u32) -> Result<Vec<f32>, String> {
-    const TARGET_RATE: u32 = 16000;
-    
-    if source_rate == TARGET_RATE {
-        return Ok(samples.to_vec());
+impl DiskRecording {
+    /// Starts a float mono WAV in the temp directory
+    fn create(sample_rate: u32) -> Result<Self, String> {
+        let path = std::env::temp_dir().join(format!("winsper-recording-{}.wav", unix_now()));
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+        let writer = hound::WavWriter::create(&path, spec)
+            .map_err(|e| format!("Failed to create recording file: {:?}", e))?;
+        println!("[Audio] Recording to disk: {}", path.display());
+        Ok(Self { path, writer, samples_written: 0 })
     }
     
-    let params = SincInterpolationParameters {
-        sinc_len: 256,
-        f_cutoff: 0.95,
-        interpolation: SincInterpolationType::Linear,
-        oversampling_factor: 256,
-        window: WindowFunction::BlackmanHarris2,
-    };
-    
-    let mut resampler = SincFixedIn::<f32>::new(
-        TARGET_RATE as f64 / source_rate as f64,
-        2.0, // max relative ratio (not used for fixed ratio)
-        params,
-        samples.len(),
-        1, // mono
-    ).map_err(|e| format!("Failed to create resampler: {:?}", e))?;
+    fn write(&mut self, samples: &[f32]) -> Result<(), String> {
+        for &sample in samples {
+            self.writer.write_sample(sample)
+                .map_err(|e| format!("Failed to write recording file: {:?}", e))?;
+        }
+        self.samples_written += samples.len();
+        Ok(())
+    }
     
-    let waves_in = vec![samples.to_vec()];
-    let waves_out = resampler.process(&waves_in, None)
-        .map_err(|e| format!("Resampling failed: {:?}", e))?;
+    /// Closes and deletes the file, for recordings that won't be transcribed
+    fn discard(self) {
+        let path = self.path.clone();
+        drop(self);
+        let _ = std::fs::remove_file(path);
+    }
     
-    Ok(waves_out.into_iter().next().unwrap_or_default())
+    /// Writes the remaining samples and closes the file. Returns its path and length.
+    fn finish(mut self, remaining: &[f32]) -> Result<(PathBuf, usize), String> {
+        self.write(remaining)?;
+        self.writer.finalize()
+            .map_err(|e| format!("Failed to finish recording file: {:?}", e))?;
+        Ok((self.path, self.samples_written))
+    }
+}
+
+/// Moves all but the last `DISK_KEEP_SECONDS` of the buffer into the disk recording,
+/// once more than `DISK_FLUSH_SECONDS` has built up. The file is written with the
+/// audio lock released so the capture callback never waits on disk I/O.
+fn spill_to_disk(audio_ctx: &SharedAudio) {
+    let (mut disk, spilled) = {
+        let mut ctx = audio_ctx.lock().unwrap();
+        let sample_rate = ctx.sample_rate as usize;
+        if ctx.disk.is_none() || ctx.buffer.len() < DISK_FLUSH_SECONDS * sample_rate {
+            return;
+        }
+        let split = ctx.buffer.len() - DISK_KEEP_SECONDS * sample_rate;
+        let spilled: Vec<f32> = ctx.buffer.drain(..split).collect();
+        (ctx.disk.take().unwrap(), spilled)
+    };
+    let result = disk.write(&spilled);
+    
+    let mut ctx = audio_ctx.lock().unwrap();
+    if let Err(e) = result {
+        // Put the audio back in front of what arrived meanwhile; the next spill retries it
+        eprintln!("[Audio] {}", e);
+        ctx.buffer.splice(0..0, spilled);
+    }
+    ctx.disk = Some(disk);
+}
+
+/// Marks the recording thread's stream as stopped when dropped, covering every exit path
+struct StreamStoppedGuard(Arc<AtomicBool>);
+
+impl Drop for StreamStoppedGuard {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Waits for the recording thread to acknowledge the stop, up to `STREAM_STOP_TIMEOUT_MS`
+fn wait_for_stream_stopped(audio_ctx: &SharedAudio) -> bool {
+    let stopped = audio_ctx.lock().unwrap().stream_stopped.clone();
+    let deadline = std::time::Instant::now() + std::time::Duration::from_millis(STREAM_STOP_TIMEOUT_MS);
+    while !stopped.load(Ordering::SeqCst) {
+        if std::time::Instant::now() >= deadline {
+            eprintln!("[Audio] Stream did not acknowledge stop within {}ms", STREAM_STOP_TIMEOUT_MS);
+            return false;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    true
+}
+
+pub type SharedAudio = Arc<Mutex<AudioContext>>;
+
+/// Whisper context state for transcription
+pub struct WhisperState {
+    pub ctx: Option<WhisperContext>,
+    pub model_path: Option<PathBuf>,
+    pub backend: Option<String>,  // Acceleration backend the loaded model is using
+}
+
+pub type SharedWhisper = Arc<Mutex<WhisperState>>;
+
+/// VAD model slot, kept separate from the Whisper model so either can change independently
+pub struct VadModelState {
+    pub model_id: Option<String>,
+    pub model_path: Option<PathBuf>,
+}
+
+pub type SharedVadModel = Arc<Mutex<VadModelState>>;
+
+/// The voice activity detector a VAD feature ran with, for its logs and events
+#[derive(Clone, Serialize)]
+pub struct VadDetector {
+    /// Detection method. Always "energy" for now: whisper-rs 0.13 has no binding for
+    /// whisper.cpp's Silero VAD, so a model in the VAD slot waits for one.
+    pub kind: &'static str,
+    /// Model in the VAD slot, if one is loaded
+    pub model_id: Option<String>,
+}
+
+/// The detector the VAD features currently run with
+fn vad_detector(app: &AppHandle) -> VadDetector {
+    let model_id = app.state::<SharedVadModel>().lock().ok().and_then(|slot| slot.model_id.clone());
+    VadDetector { kind: "energy", model_id }
+}
+
+/// Default longest recording before it is stopped and transcribed automatically
+const DEFAULT_MAX_RECORDING_SECONDS: u64 = 120;
+
+/// Default ratio over the running level above which a level window counts as a transient
+const DEFAULT_TRANSIENT_REJECTION_RATIO: f32 = 4.0;
+
+/// Consecutive spike windows suppressed before a rise counts as sustained. Level
+/// windows overlap by half, so one short bang lands in two of them.
+const MAX_TRANSIENT_WINDOWS: u32 = 2;
+
+/// Smooths the `audio_level` meter by holding back short spikes (door slams, coughs)
+/// far above the running level, so it tracks sustained speech. Only the meter is
+/// affected; recorded audio is untouched.
+struct LevelMeter {
+    /// Spike threshold as a multiple of the running level (0 = no rejection)
+    ratio: f32,
+    average: Option<f32>,
+    spike_windows: u32,
+}
+
+impl LevelMeter {
+    fn new(ratio: f32) -> Self {
+        Self { ratio, average: None, spike_windows: 0 }
+    }
+    
+    /// Feeds one window's RMS, returning the RMS to display
+    fn level(&mut self, rms: f32) -> f32 {
+        let Some(average) = self.average else {
+            self.average = Some(rms);
+            return rms;
+        };
+        if self.ratio > 0.0 && rms > average * self.ratio && self.spike_windows < MAX_TRANSIENT_WINDOWS {
+            self.spike_windows += 1;
+            return average;
+        }
+        self.spike_windows = 0;
+        self.average = Some(average * 0.8 + rms * 0.2);
+        rms
+    }
+}
+
+/// Computes the RMS (root mean square) of the last N samples for waveform visualization
+fn compute_rms(samples: &[f32], window_size: usize) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let start = if samples.len() > window_size {
+        samples.len() - window_size
+    } else {
+        0
+    };
+    let window = &samples[start..];
+    let sum_sq: f32 = window.iter().map(|s| s * s).sum();
+    (sum_sq / window.len() as f32).sqrt()
+}
+
+/// Sample rate expected by Whisper
+const WHISPER_SAMPLE_RATE: u32 = 16000;
+
+/// Resamples audio from source_rate to 16kHz (required by Whisper)
+fn resample_to_16khz(samples: &[f32], source_rate: u32) -> Result<std::borrow::Cow<'_, [f32]>, String> {
+    resample(samples, source_rate, WHISPER_SAMPLE_RATE)
+}
+
+/// Resamples mono audio from source_rate to target_rate
+fn resample(samples: &[f32], source_rate: u32, target_rate: u32) -> Result<std::borrow::Cow<'_, [f32]>, String> {
+    // Audio already at the target rate is returned as-is, without a copy
+    if source_rate == target_rate {
+        return Ok(std::borrow::Cow::Borrowed(samples));
+    }
+    
+    let params = SincInterpolationParameters {
+        sinc_len: 256,
+        f_cutoff: 0.95,
+        interpolation: SincInterpolationType::Linear,
+        oversampling_factor: 256,
+        window: WindowFunction::BlackmanHarris2,
+    };
+    
+    let mut resampler = SincFixedIn::<f32>::new(
+        target_rate as f64 / source_rate as f64,
+        2.0, // max relative ratio (not used for fixed ratio)
+        params,
+        samples.len(),
+        1, // mono
+    ).map_err(|e| format!("Failed to create resampler: {:?}", e))?;
+    
+    let waves_out = resampler.process(&[samples], None)
+        .map_err(|e| format!("Resampling failed: {:?}", e))?;
+    
+    Ok(std::borrow::Cow::Owned(waves_out.into_iter().next().unwrap_or_default()))
+}
+
+/// Frame size for voice activity detection
+const VAD_FRAME_MS: usize = 30;
+
+/// Pause inserted where speech regions are joined, so words at the seams stay separate
+const VAD_JOIN_SILENCE_MS: usize = 100;
+
+/// Floor for the adaptive speech threshold, so digital silence doesn't make every frame speech
+const VAD_MIN_THRESHOLD: f32 = 0.002;
+
+/// VAD tuning per aggressiveness level:
+/// (noise floor multiplier, padding kept around speech in ms, shortest gap removed in ms)
+fn vad_tuning(aggressiveness: u8) -> (f32, usize, usize) {
+    match aggressiveness {
+        0 => (1.5, 300, 800),
+        1 => (2.0, 200, 500),
+        2 => (3.0, 150, 400),
+        _ => (4.0, 100, 300),
+    }
+}
+
+/// Finds speech regions (as sample ranges) from frame-level energy.
+/// Regions are padded on both sides so word onsets and trailing consonants aren't clipped.
+fn detect_speech_regions(samples: &[f32], sample_rate: u32, settings: &VadSettings) -> Vec<std::ops::Range<usize>> {
+    let frame_len = (sample_rate as usize * VAD_FRAME_MS / 1000).max(1);
+    let levels: Vec<f32> = samples.chunks(frame_len).map(|f| compute_rms(f, f.len())).collect();
+    if levels.is_empty() {
+        return Vec::new();
+    }
+    
+    let (multiplier, pad_ms, min_gap_ms) = vad_tuning(settings.aggressiveness);
+    let threshold = settings.threshold.unwrap_or_else(|| {
+        // Treat the quietest 10% of frames as the noise floor
+        let mut sorted = levels.clone();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        (sorted[sorted.len() / 10] * multiplier).max(VAD_MIN_THRESHOLD)
+    });
+    let pad = pad_ms / VAD_FRAME_MS;
+    let min_gap = min_gap_ms / VAD_FRAME_MS;
+    
+    // Build padded regions in frames, merging any separated by less than the minimum gap
+    let mut regions: Vec<std::ops::Range<usize>> = Vec::new();
+    for (i, &level) in levels.iter().enumerate() {
+        if level < threshold {
+            continue;
+        }
+        let start = i.saturating_sub(pad);
+        let end = (i + 1 + pad).min(levels.len());
+        match regions.last_mut() {
+            Some(last) if start <= last.end + min_gap => last.end = last.end.max(end),
+            _ => regions.push(start..end),
+        }
+    }
+    
+    regions.into_iter()
+        .map(|r| r.start * frame_len..(r.end * frame_len).min(samples.len()))
+        .collect()
+}
+
+/// Highest gain the AGC may be configured to apply (+30 dB)
+const MAX_AGC_GAIN: f32 = 31.6;
+
+/// Time-varying gain toward a target RMS level, applied in place. A power envelope
+/// rises with the attack time constant and falls with the release one; gain is
+/// capped at `max_gain` so silence and noise aren't blown up, and output is clipped.
+fn apply_agc(samples: &mut [f32], sample_rate: u32, settings: &AgcSettings) {
+    let coefficient = |ms: u32| (-1.0 / (ms.max(1) as f32 * sample_rate as f32 / 1000.0)).exp();
+    let attack = coefficient(settings.attack_ms);
+    let release = coefficient(settings.release_ms);
+    let target_power = settings.target_level * settings.target_level;
+    // Start from the opening level so the first words aren't over-amplified
+    let lead = samples.len().min(sample_rate as usize / 10);
+    let mut envelope = samples[..lead].iter().map(|s| s * s).sum::<f32>() / lead.max(1) as f32;
+    for sample in samples.iter_mut() {
+        let power = *sample * *sample;
+        let coeff = if power > envelope { attack } else { release };
+        envelope = coeff * envelope + (1.0 - coeff) * power;
+        let gain = (target_power / envelope.max(f32::MIN_POSITIVE)).sqrt().min(settings.max_gain);
+        *sample = (*sample * gain).clamp(-1.0, 1.0);
+    }
+}
+
+/// Applies the configured input conditioning (high-pass, then AGC) before VAD and
+/// resampling. The high-pass runs first so rumble doesn't drive the AGC.
+fn condition_audio(app: &AppHandle, samples: &mut [f32], sample_rate: u32) {
+    if let Some(cutoff_hz) = load_high_pass_hz(app) {
+        apply_high_pass_filter(samples, sample_rate, cutoff_hz);
+    }
+    let agc = load_agc_settings(app);
+    if agc.enabled {
+        apply_agc(samples, sample_rate, &agc);
+    }
+}
+
+/// Longest configurable wait for CPU load to drop before transcribing
+const MAX_LOAD_DEFER_SECONDS: u64 = 120;
+
+/// Window over which system CPU usage is measured while deferring
+const CPU_SAMPLE_MS: u64 = 500;
+
+/// Cumulative (idle, total) CPU time since boot, in platform ticks
+fn system_cpu_times() -> Option<(u64, u64)> {
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::Foundation::FILETIME;
+        let zero = || FILETIME { dwLowDateTime: 0, dwHighDateTime: 0 };
+        let (mut idle, mut kernel, mut user) = (zero(), zero(), zero());
+        if unsafe { windows_sys::Win32::System::Threading::GetSystemTimes(&mut idle, &mut kernel, &mut user) } == 0 {
+            return None;
+        }
+        let ticks = |t: FILETIME| ((t.dwHighDateTime as u64) << 32) | t.dwLowDateTime as u64;
+        // Kernel time includes idle time
+        Some((ticks(idle), ticks(kernel) + ticks(user)))
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let stat = std::fs::read_to_string("/proc/stat").ok()?;
+        let fields: Vec<u64> = stat.lines().next()?
+            .split_whitespace()
+            .skip(1)
+            .filter_map(|f| f.parse().ok())
+            .collect();
+        // user nice system idle iowait irq softirq steal ...
+        let idle = fields.get(3)? + fields.get(4).copied().unwrap_or(0);
+        Some((idle, fields.iter().take(8).sum()))
+    }
+    #[cfg(not(any(windows, target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// System-wide CPU usage in percent, measured over `sample_ms`. None where the
+/// platform can't report it.
+fn cpu_usage_percent(sample_ms: u64) -> Option<f32> {
+    let (idle_before, total_before) = system_cpu_times()?;
+    std::thread::sleep(std::time::Duration::from_millis(sample_ms));
+    let (idle_after, total_after) = system_cpu_times()?;
+    let total = total_after.saturating_sub(total_before);
+    if total == 0 {
+        return None;
+    }
+    let idle = idle_after.saturating_sub(idle_before).min(total);
+    Some(100.0 * (total - idle) as f32 / total as f32)
+}
+
+/// Holds off inference while CPU usage is above the configured threshold, up to the
+/// max defer time. Emits `transcription_deferred` once if it has to wait.
+fn defer_while_busy(app: &AppHandle) {
+    let settings = load_load_defer_settings(app);
+    if !settings.enabled {
+        return;
+    }
+    let start = std::time::Instant::now();
+    let deadline = start + std::time::Duration::from_secs(settings.max_defer_seconds);
+    let mut deferred = false;
+    loop {
+        let Some(usage) = cpu_usage_percent(CPU_SAMPLE_MS) else {
+            return;
+        };
+        if usage < settings.cpu_threshold_percent as f32 {
+            break;
+        }
+        if std::time::Instant::now() >= deadline {
+            println!("[Defer] CPU still at {:.0}%, transcribing anyway", usage);
+            break;
+        }
+        if !deferred {
+            deferred = true;
+            println!("[Defer] CPU at {:.0}%, deferring transcription", usage);
+            let _ = app.emit("transcription_deferred", serde_json::json!({
+                "cpu_percent": usage,
+                "max_defer_seconds": settings.max_defer_seconds,
+            }));
+        }
+    }
+    if deferred {
+        println!("[Defer] Waited {}ms for CPU load to drop", start.elapsed().as_millis());
+    }
+}
+
+/// Removes low-frequency rumble (AC hum, desk bumps) in place with a single-pole
+/// high-pass filter. Cutoffs at or above Nyquist are ignored.
+fn apply_high_pass_filter(samples: &mut [f32], sample_rate: u32, cutoff_hz: f32) {
+    if cutoff_hz <= 0.0 || cutoff_hz >= sample_rate as f32 / 2.0 {
+        return;
+    }
+    let rc = 1.0 / (2.0 * std::f32::consts::PI * cutoff_hz);
+    let dt = 1.0 / sample_rate as f32;
+    let alpha = rc / (rc + dt);
+    
+    let mut prev_in = samples.first().copied().unwrap_or(0.0);
+    let mut prev_out = 0.0f32;
+    for sample in samples.iter_mut() {
+        let input = *sample;
+        prev_out = alpha * (prev_out + input - prev_in);
+        prev_in = input;
+        *sample = prev_out;
+    }
+}
+
+/// Removes non-speech gaps when the VAD filter is enabled, joining speech regions with a short pause.
+/// Returns None when the filter is off or there is nothing to remove. Each filtered
+/// buffer is reported as a `vad_filter_applied` event naming the detector used.
+fn apply_vad_filter(app: &AppHandle, samples: &[f32], sample_rate: u32) -> Option<Vec<f32>> {
+    let settings = load_vad_settings(app);
+    if !settings.enabled {
+        return None;
+    }
+    
+    let regions = detect_speech_regions(samples, sample_rate, &settings);
+    // No speech at all is left to Whisper and the empty-transcript handling
+    if regions.is_empty() || (regions.len() == 1 && regions[0].len() == samples.len()) {
+        return None;
+    }
+    
+    let join = vec![0.0f32; sample_rate as usize * VAD_JOIN_SILENCE_MS / 1000];
+    let kept: usize = regions.iter().map(|r| r.len()).sum();
+    let mut filtered = Vec::with_capacity(kept + join.len() * regions.len());
+    for (i, region) in regions.iter().enumerate() {
+        if i > 0 {
+            filtered.extend_from_slice(&join);
+        }
+        filtered.extend_from_slice(&samples[region.clone()]);
+    }
+    
+    let detector = vad_detector(app);
+    let removed_seconds = samples.len().saturating_sub(filtered.len()) as f32 / sample_rate as f32;
+    println!("[VAD] Kept {} speech regions with the {} detector, removed {:.1}s of non-speech",
+             regions.len(), detector.kind, removed_seconds);
+    let _ = app.emit("vad_filter_applied", serde_json::json!({
+        "detector": detector,
+        "regions": regions.len(),
+        "removed_seconds": removed_seconds,
+    }));
+    Some(filtered)
 }
 
 /// Runs Whisper transcription on the audio buffer
@@ -364,877 +1538,8241 @@ fn run_whisper_on_buffer(
     samples: &[f32],
     sample_rate: u32,
     whisper_state: &SharedWhisper,
-) -> Result<String, String> {
+    options: &DecodingOptions,
+    abort: &AbortToken,
+) -> Result<Transcription, String> {
+    // An abort between chunks or retries stops the next run before it starts
+    if abort.is_aborted() {
+        return Err(TRANSCRIPTION_ABORTED.to_string());
+    }
+    
     // Resample to 16kHz
+    let resample_start = std::time::Instant::now();
     let resampled = resample_to_16khz(samples, sample_rate)?;
+    let resample_ms = resample_start.elapsed().as_millis() as u64;
     
     println!("[Whisper] Resampled {} samples at {}Hz to {} samples at 16kHz", 
              samples.len(), sample_rate, resampled.len());
     
     // Get Whisper context
     let ws = whisper_state.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+    if abort.is_aborted() {
+        return Err(TRANSCRIPTION_ABORTED.to_string());
+    }
     let ctx = ws.ctx.as_ref().ok_or("No Whisper model loaded. Please set a model first.")?;
     
     // Create Whisper state for this transcription
     let mut state = ctx.create_state().map_err(|e| format!("Failed to create state: {:?}", e))?;
     
     // Configure parameters
-    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
-    params.set_language(Some("en"));
-    params.set_n_threads(4);
+    let mut params = FullParams::new(options.sampling.strategy());
+    params.set_temperature(options.temperature);
+    let prompt: String = options.initial_prompt.trim().chars().take(MAX_INITIAL_PROMPT_CHARS).collect();
+    if !prompt.is_empty() {
+        params.set_initial_prompt(&prompt);
+    }
+    let model_name = ws.model_path.as_deref()
+        .and_then(|p| p.file_name())
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if options.translate && is_english_only_model(&model_name) {
+        return Err(format!("Translation needs a multilingual model, but {} is English-only", model_name));
+    }
+    params.set_translate(options.translate);
+    
+    let auto_detect = options.detect_segment_languages || options.language == "auto";
+    let requested_language = if auto_detect { None } else { Some(options.language.as_str()) };
+    let forced_language = enforce_model_language(ws.model_path.as_deref(), requested_language);
+    let n_threads = options.thread_count();
+    params.set_language(forced_language);
+    params.set_n_threads(n_threads);
     params.set_print_special(false);
     params.set_print_progress(false);
     params.set_print_realtime(false);
     params.set_print_timestamps(false);
     
+    // Segmentation: max_len needs token timestamps to find split points
+    params.set_split_on_word(options.split_on_word);
+    if options.max_len > 0 {
+        params.set_token_timestamps(true);
+        params.set_max_len(options.max_len as i32);
+    }
+    
+    let token = abort.clone();
+    params.set_abort_callback_safe(move || token.is_aborted());
+    
     // Run inference
     println!("[Whisper] Starting transcription...");
-    state.full(params, &resampled)
-        .map_err(|e| format!("Transcription failed: {:?}", e))?;
+    let inference_start = std::time::Instant::now();
+    let run = state.full(params, &resampled);
+    if abort.is_aborted() {
+        return Err(TRANSCRIPTION_ABORTED.to_string());
+    }
+    run.map_err(|e| format!("Transcription failed: {:?}", e))?;
+    let inference_ms = inference_start.elapsed().as_millis() as u64;
     
     // Collect segments
     let num_segments = state.full_n_segments()
         .map_err(|e| format!("Failed to get segments: {:?}", e))?;
     
-    let mut result = String::new();
+    let mut raw_segments = Vec::new();
+    let mut segments = Vec::new();
+    let mut prob_sum = 0.0f32;
+    let mut prob_count = 0usize;
+    let token_eot = ctx.token_eot();
     for i in 0..num_segments {
         if let Ok(segment) = state.full_get_segment_text(i) {
-            result.push_str(&segment);
+            // Segment times are in centiseconds
+            let t0 = state.full_get_segment_t0(i).unwrap_or(0).max(0) as u64;
+            let t1 = state.full_get_segment_t1(i).unwrap_or(0).max(0) as u64;
+            segments.push(TranscriptSegment {
+                start_ms: t0 * 10,
+                end_ms: t1 * 10,
+                text: segment.trim().to_string(),
+                language: None,
+            });
+            raw_segments.push(segment);
+        }
+        // Average probability of text tokens (special tokens come after EOT)
+        let num_tokens = state.full_n_tokens(i).unwrap_or(0);
+        for j in 0..num_tokens {
+            if let Ok(token) = state.full_get_token_data(i, j) {
+                if token.id < token_eot {
+                    prob_sum += token.p;
+                    prob_count += 1;
+                }
+            }
+        }
+    }
+    
+    let language = state.full_lang_id_from_state()
+        .ok()
+        .and_then(whisper_rs::get_lang_str)
+        .map(|s| s.to_string());
+    
+    // Whisper detects one language per run, so probe the mel at each segment's start
+    if options.detect_segment_languages && forced_language.is_none() {
+        for segment in segments.iter_mut() {
+            segment.language = state.lang_detect(segment.start_ms as usize, n_threads as usize)
+                .ok()
+                .and_then(|(lang_id, _)| whisper_rs::get_lang_str(lang_id))
+                .map(|s| s.to_string());
         }
     }
+    let confidence = (prob_count > 0).then(|| prob_sum / prob_count as f32);
     
-    let text = result.trim().to_string();
+    let text = options.segment_separator.join(&raw_segments);
     println!("[Whisper] Transcription complete: \"{}\"", text);
     
-    Ok(text)
+    Ok(Transcription {
+        text,
+        segments,
+        language,
+        confidence,
+        resample_ms,
+        inference_ms,
+    })
 }
 
-/// Copies text to the system clipboard
-fn copy_to_clipboard(text: &str) -> Result<(), String> {
-    let mut clipboard = Clipboard::new().map_err(|e| format!("Failed to access clipboard: {:?}", e))?;
-    clipboard.set_text(text.to_string()).map_err(|e| format!("Failed to set clipboard text: {:?}", e))?;
-    println!("[Clipboard] Text copied: \"{}\"", text);
-    Ok(())
+/// Distinct detected segment languages, in order of first appearance
+fn segment_languages(segments: &[TranscriptSegment]) -> Vec<String> {
+    let mut languages: Vec<String> = Vec::new();
+    for language in segments.iter().filter_map(|s| s.language.as_ref()) {
+        if !languages.contains(language) {
+            languages.push(language.clone());
+        }
+    }
+    languages
 }
 
-/// Simulates Ctrl+V keystroke to paste from clipboard
-fn simulate_paste() -> Result<(), String> {
-    // Small delay to ensure the target window is ready
-    std::thread::sleep(std::time::Duration::from_millis(50));
+/// Temperature for the retry of a blank result; high enough to escape a greedy
+/// decode that collapsed to nothing
+const BLANK_RETRY_TEMPERATURE: f32 = 0.4;
+
+/// Candidates sampled during the blank-result retry
+const BLANK_RETRY_BEST_OF: u32 = 5;
+
+/// Speech needed in the audio before a blank result is considered a decoding miss
+const BLANK_RETRY_MIN_SPEECH_MS: usize = 300;
+
+/// Whether a transcript carries no words (empty, punctuation, or Whisper's blank marker)
+fn is_blank_transcript(text: &str) -> bool {
+    let text = text.trim();
+    text == "[BLANK_AUDIO]" || !has_alphanumeric_content(text)
+}
+
+/// When blank retry is on and a transcript came back blank although the audio has
+/// speech by RMS, decodes once more at a higher temperature with several candidates.
+/// Emits `transcription_retry` before and `transcription_retry_result` after the retry.
+fn retry_blank_transcription(
+    app: &AppHandle,
+    whisper_state: &SharedWhisper,
+    samples: &[f32],
+    sample_rate: u32,
+    options: &DecodingOptions,
+    abort: &AbortToken,
+    transcription: Transcription,
+) -> Result<Transcription, String> {
+    if !is_blank_transcript(&transcription.text) || !load_blank_retry(app) {
+        return Ok(transcription);
+    }
+    let speech_samples: usize = detect_speech_regions(samples, sample_rate, &load_vad_settings(app))
+        .iter()
+        .map(|r| r.len())
+        .sum();
+    let speech_ms = speech_samples * 1000 / sample_rate.max(1) as usize;
+    if speech_ms < BLANK_RETRY_MIN_SPEECH_MS {
+        return Ok(transcription);
+    }
     
-    // Press Ctrl
-    simulate(&EventType::KeyPress(Key::ControlLeft))
-        .map_err(|e| format!("Failed to press Ctrl: {:?}", e))?;
-    std::thread::sleep(std::time::Duration::from_millis(20));
+    println!("[Whisper] Blank result with {}ms of speech, retrying at temperature {}", speech_ms, BLANK_RETRY_TEMPERATURE);
+    let _ = app.emit("transcription_retry", serde_json::json!({
+        "reason": "blank",
+        "speech_ms": speech_ms,
+        "temperature": BLANK_RETRY_TEMPERATURE,
+    }));
+    let mut retry_options = options.clone();
+    retry_options.temperature = BLANK_RETRY_TEMPERATURE;
+    if retry_options.sampling.sampling_strategy == SamplingKind::Greedy {
+        retry_options.sampling.best_of = retry_options.sampling.best_of.max(BLANK_RETRY_BEST_OF);
+    }
     
-    // Press V
-    simulate(&EventType::KeyPress(Key::KeyV))
-        .map_err(|e| format!("Failed to press V: {:?}", e))?;
-    std::thread::sleep(std::time::Duration::from_millis(20));
+    let retried = run_whisper_on_buffer(samples, sample_rate, whisper_state, &retry_options, abort);
+    if matches!(&retried, Err(e) if e == TRANSCRIPTION_ABORTED) {
+        return retried;
+    }
+    let recovered = retried.as_ref().is_ok_and(|t| !is_blank_transcript(&t.text));
+    let _ = app.emit("transcription_retry_result", serde_json::json!({ "recovered": recovered }));
+    match retried {
+        Ok(retry) if recovered => {
+            println!("[Whisper] Retry recovered a transcript");
+            Ok(retry)
+        }
+        Ok(_) => {
+            println!("[Whisper] Retry was blank too");
+            Ok(transcription)
+        }
+        Err(e) => {
+            eprintln!("[Whisper] Retry failed: {}", e);
+            Ok(transcription)
+        }
+    }
+}
+
+/// After a failed transcription, retries once with the configured fallback model.
+/// The fallback is loaded into a temporary context so the active model stays selected.
+fn retry_with_fallback_model(
+    app: &AppHandle,
+    whisper_state: &SharedWhisper,
+    samples: &[f32],
+    sample_rate: u32,
+    options: &DecodingOptions,
+    abort: &AbortToken,
+    error: String,
+) -> Result<Transcription, String> {
+    if error == TRANSCRIPTION_ABORTED {
+        return Err(error);
+    }
+    let Some(fallback_id) = load_fallback_model(app) else { return Err(error) };
+    let active_id = whisper_state.lock()
+        .ok()
+        .and_then(|ws| ws.model_path.as_deref().map(model_id_for_path));
+    if active_id.as_deref() == Some(fallback_id.as_str()) {
+        return Err(error);
+    }
     
-    // Release V
-    simulate(&EventType::KeyRelease(Key::KeyV))
-        .map_err(|e| format!("Failed to release V: {:?}", e))?;
-    std::thread::sleep(std::time::Duration::from_millis(20));
+    eprintln!("[Whisper] Transcription failed ({}), retrying with fallback model {}", error, fallback_id);
+    let _ = app.emit("transcription_fallback_started", serde_json::json!({
+        "error": error,
+        "model_id": fallback_id,
+    }));
     
-    // Release Ctrl
-    simulate(&EventType::KeyRelease(Key::ControlLeft))
-        .map_err(|e| format!("Failed to release Ctrl: {:?}", e))?;
+    let result = (|| {
+        let preset = get_all_models(app)
+            .into_iter()
+            .find(|p| p.id == fallback_id)
+            .ok_or_else(|| format!("Unknown model: {}", fallback_id))?;
+        let model_path = get_models_dir(app)?.join(&preset.filename);
+        if !model_path.exists() {
+            return Err(format!("Model not downloaded: {}", preset.filename));
+        }
+        let (ctx, backend) = create_whisper_context(app, &model_path.to_string_lossy())?;
+        let fallback_state: SharedWhisper = Arc::new(Mutex::new(WhisperState {
+            ctx: Some(ctx),
+            model_path: Some(model_path),
+            backend: Some(backend),
+        }));
+        run_whisper_on_buffer(samples, sample_rate, &fallback_state, options, abort)
+    })();
     
-    println!("[Paste] Simulated Ctrl+V");
-    Ok(())
+    match result {
+        Ok(transcription) => {
+            println!("[Whisper] Fallback model {} succeeded", fallback_id);
+            let _ = app.emit("transcription_fallback_succeeded", &fallback_id);
+            Ok(transcription)
+        }
+        Err(e) => {
+            eprintln!("[Whisper] Fallback model {} failed: {}", fallback_id, e);
+            let _ = app.emit("transcription_fallback_failed", &fallback_id);
+            Err(format!("{} (fallback model also failed: {})", error, e))
+        }
+    }
 }
 
-/// Copies text to clipboard and simulates paste
-fn copy_to_clipboard_and_paste(text: &str) -> Result<(), String> {
-    copy_to_clipboard(text)?;
-    simulate_paste()?;
-    Ok(())
+/// Identifies a model by its preset id, or by file stem for custom models
+fn model_id_for_path(path: &std::path::Path) -> String {
+    let filename = path.file_name().map(|f| f.to_string_lossy().to_string()).unwrap_or_default();
+    get_preset_models()
+        .into_iter()
+        .find(|p| p.filename == filename)
+        .map(|p| p.id)
+        .unwrap_or_else(|| path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default())
 }
 
-/// Shows the overlay window and positions it at the bottom center of the screen
-fn show_overlay(app: &AppHandle) {
-    println!("[Overlay] Attempting to show overlay...");
-    if let Some(overlay) = app.get_webview_window("overlay") {
-        // Get the primary monitor (more reliable than current_monitor for hidden windows)
-        let monitor = overlay.primary_monitor()
-            .ok()
-            .flatten()
-            .or_else(|| overlay.current_monitor().ok().flatten());
-        
-        if let Some(monitor) = monitor {
-            let screen_size = monitor.size();
-            let screen_pos = monitor.position();
-            
-            // Get overlay window size
-            if let Ok(overlay_size) = overlay.outer_size() {
-                // Calculate position: horizontally centered, near the bottom
-                let x = screen_pos.x + (screen_size.width as i32 - overlay_size.width as i32) / 2;
-                let y = screen_pos.y + screen_size.height as i32 - overlay_size.height as i32 - 100; // 100px from bottom
-                
-                let _ = overlay.set_position(PhysicalPosition::new(x, y));
-                println!("[Overlay] Positioned at ({}, {})", x, y);
-            }
-        }
-        
-        let _ = overlay.show();
-        println!("[Overlay] Window shown");
-        // Don't set focus - this would steal keyboard events from rdev
-        // The overlay is just a visual indicator
-    } else {
-        println!("[Overlay] ERROR: Could not find overlay window!");
-    }
+/// Whether a model is English-only, going by the `.en` naming convention
+/// ("base.en", "ggml-base.en-q5_1.bin")
+fn is_english_only_model(name: &str) -> bool {
+    let name = name.strip_suffix(".bin").unwrap_or(name);
+    name.ends_with(".en") || name.contains(".en-")
 }
 
-/// Hides the overlay window
-fn hide_overlay(app: &AppHandle) {
-    if let Some(overlay) = app.get_webview_window("overlay") {
-        let _ = overlay.hide();
+/// Language to decode with: English-only models always get English, since
+/// any other language (or auto-detect) just produces garbage
+fn enforce_model_language<'a>(model_path: Option<&std::path::Path>, requested: Option<&'a str>) -> Option<&'a str> {
+    let filename = model_path
+        .and_then(|p| p.file_name())
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if is_english_only_model(&filename) && requested != Some("en") {
+        println!("[Whisper] {} is English-only, ignoring language {:?}", filename, requested.unwrap_or("auto"));
+        return Some("en");
     }
+    requested
 }
 
-/// Starts audio recording using the selected input device (or default if none selected)
-fn start_audio_recording(app: AppHandle, audio_ctx: SharedAudio) {
-    // Get the stop signal before spawning thread
-    let stop_signal = {
-        let ctx = audio_ctx.lock().unwrap();
-        ctx.stop_signal.store(false, Ordering::SeqCst);
-        ctx.stop_signal.clone()
+/// Kind of English number word, used to decide whether words belong to the same number
+#[derive(Clone, Copy, PartialEq)]
+enum NumberWordKind {
+    Unit,
+    Teen,
+    Tens,
+    Hundred,
+    Scale,
+}
+
+/// Looks up an English number word
+fn english_number_word(word: &str) -> Option<(u64, NumberWordKind)> {
+    use NumberWordKind::*;
+    let entry = match word {
+        "zero" => (0, Unit),
+        "one" => (1, Unit),
+        "two" => (2, Unit),
+        "three" => (3, Unit),
+        "four" => (4, Unit),
+        "five" => (5, Unit),
+        "six" => (6, Unit),
+        "seven" => (7, Unit),
+        "eight" => (8, Unit),
+        "nine" => (9, Unit),
+        "ten" => (10, Teen),
+        "eleven" => (11, Teen),
+        "twelve" => (12, Teen),
+        "thirteen" => (13, Teen),
+        "fourteen" => (14, Teen),
+        "fifteen" => (15, Teen),
+        "sixteen" => (16, Teen),
+        "seventeen" => (17, Teen),
+        "eighteen" => (18, Teen),
+        "nineteen" => (19, Teen),
+        "twenty" => (20, Tens),
+        "thirty" => (30, Tens),
+        "forty" => (40, Tens),
+        "fifty" => (50, Tens),
+        "sixty" => (60, Tens),
+        "seventy" => (70, Tens),
+        "eighty" => (80, Tens),
+        "ninety" => (90, Tens),
+        "hundred" => (100, Hundred),
+        "thousand" => (1_000, Scale),
+        "million" => (1_000_000, Scale),
+        "billion" => (1_000_000_000, Scale),
+        _ => return None,
     };
-    
-    // Get the selected microphone from config
-    let selected_mic = load_selected_microphone(&app);
+    Some(entry)
+}
 
-    std::thread::spawn(move || {
-        let host = cpal::default_host();
-        
-        // Find the selected device or fall back to default
-        let device = if let Some(ref mic_name) = selected_mic {
-            // Try to find the selected device
-            host.input_devices()
-                .ok()
-                .and_then(|mut devices| devices.find(|d| d.name().ok().as_ref() == Some(mic_name)))
-                .or_else(|| {
-                    eprintln!("[Audio] Selected device '{}' not found, using default", mic_name);
-                    host.default_input_device()
-                })
-        } else {
-            host.default_input_device()
+/// Parses a word (possibly hyphenated, e.g. "twenty-four") into number word parts
+fn parse_number_token(core: &str) -> Option<Vec<(u64, NumberWordKind)>> {
+    if core.is_empty() {
+        return None;
+    }
+    core.to_lowercase()
+        .split('-')
+        .map(english_number_word)
+        .collect()
+}
+
+/// Builds a single number from consecutive number words
+#[derive(Clone, Default)]
+struct NumberAccumulator {
+    total: u64,
+    current: u64,
+    last: Option<NumberWordKind>,
+}
+
+impl NumberAccumulator {
+    /// Adds a word to the number. Returns false if the word starts a new number instead.
+    fn push(&mut self, value: u64, kind: NumberWordKind) -> bool {
+        use NumberWordKind::*;
+        let after_zero = self.last == Some(Unit) && self.total == 0 && self.current == 0;
+        let accepted = match kind {
+            Unit => matches!(self.last, None | Some(Tens) | Some(Hundred) | Some(Scale))
+                && !(value == 0 && self.last.is_some()),
+            Teen | Tens => matches!(self.last, None | Some(Hundred) | Some(Scale)),
+            Hundred => matches!(self.last, Some(Unit) | Some(Teen) | Some(Tens))
+                && self.current > 0 && self.current < 100,
+            Scale => self.last.is_some() && self.last != Some(Scale) && self.current > 0,
         };
-        
-        let device = match device {
-            Some(d) => d,
-            None => {
-                eprintln!("[Audio] No input device available");
-                let _ = app.emit("audio_error", "No input device available");
-                return;
+        if !accepted || after_zero {
+            return false;
+        }
+        match kind {
+            Unit | Teen | Tens => self.current += value,
+            Hundred => self.current *= value,
+            Scale => {
+                self.total += self.current * value;
+                self.current = 0;
             }
-        };
+        }
+        self.last = Some(kind);
+        true
+    }
 
-        println!("[Audio] Using input device: {}", device.name().unwrap_or_default());
-
-        let config = match device.default_input_config() {
-            Ok(c) => c,
-            Err(e) => {
-                eprintln!("[Audio] Failed to get default input config: {:?}", e);
-                let _ = app.emit("audio_error", format!("Failed to get input config: {:?}", e));
-                return;
-            }
-        };
-
-        println!("[Audio] Default input config: {:?}", config);
+    fn is_empty(&self) -> bool {
+        self.last.is_none()
+    }
 
-        let sample_rate = config.sample_rate().0;
-        let channels = config.channels() as usize;
+    /// Finishes the number, resetting the accumulator
+    fn take(&mut self) -> u64 {
+        let value = self.total + self.current;
+        *self = Self::default();
+        value
+    }
+}
 
-        // Update sample rate in context and clear buffer
-        {
-            let mut ctx = audio_ctx.lock().unwrap();
-            ctx.sample_rate = sample_rate;
-            ctx.buffer.clear();
+/// Splits text into (leading whitespace, word) pairs plus any trailing whitespace,
+/// so text can be rewritten word by word without losing line breaks
+fn split_words(text: &str) -> (Vec<(&str, &str)>, &str) {
+    let mut words = Vec::new();
+    let mut rest = text;
+    loop {
+        let word_start = rest.find(|c: char| !c.is_whitespace()).unwrap_or(rest.len());
+        if word_start == rest.len() {
+            return (words, rest);
         }
+        let after_ws = &rest[word_start..];
+        let word_len = after_ws.find(char::is_whitespace).unwrap_or(after_ws.len());
+        words.push((&rest[..word_start], &after_ws[..word_len]));
+        rest = &after_ws[word_len..];
+    }
+}
 
-        let audio_ctx_clone = audio_ctx.clone();
-        let app_clone = app.clone();
-
-        // Counter for throttling audio_level events
-        let sample_count = Arc::new(Mutex::new(0usize));
-        let sample_count_clone = sample_count.clone();
-
-        let err_fn = |err| eprintln!("[Audio] Stream error: {:?}", err);
-
-        let stream = match config.sample_format() {
-            cpal::SampleFormat::F32 => {
-                device.build_input_stream(
-                    &config.into(),
-                    move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                        let mut ctx = audio_ctx_clone.lock().unwrap();
-                        
-                        // Convert to mono by averaging channels
-                        for frame in data.chunks(channels) {
-                            let sample: f32 = frame.iter().sum::<f32>() / channels as f32;
-                            ctx.buffer.push(sample);
-                        }
+/// Splits a word into its core and trailing punctuation ("four." -> ("four", "."))
+fn split_trailing_punctuation(word: &str) -> (&str, &str) {
+    let core = word.trim_end_matches(|c: char| !c.is_alphanumeric());
+    (core, &word[core.len()..])
+}
 
-                        // Throttle audio_level events: emit every ~2048 samples
-                        let mut count = sample_count_clone.lock().unwrap();
-                        *count += data.len() / channels;
-                        
-                        if *count >= 2048 {
-                            let rms = compute_rms(&ctx.buffer, 4096);
-                            // Normalize RMS to 0-1 range (typical speech is ~0.01-0.1 RMS)
-                            let normalized = (rms * 10.0).min(1.0);
-                            let _ = app_clone.emit("audio_level", normalized);
-                            *count = 0;
-                        }
-                    },
-                    err_fn,
-                    None,
-                )
-            }
-            cpal::SampleFormat::I16 => {
-                device.build_input_stream(
-                    &config.into(),
-                    move |data: &[i16], _: &cpal::InputCallbackInfo| {
-                        let mut ctx = audio_ctx_clone.lock().unwrap();
-                        
-                        for frame in data.chunks(channels) {
-                            let sample: f32 = frame.iter()
-                                .map(|s| s.to_float_sample())
-                                .sum::<f32>() / channels as f32;
-                            ctx.buffer.push(sample);
-                        }
+/// Lowercased word with surrounding punctuation removed, for phrase matching
+fn normalize_command_word(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase()
+}
 
-                        let mut count = sample_count_clone.lock().unwrap();
-                        *count += data.len() / channels;
-                        
-                        if *count >= 2048 {
-                            let rms = compute_rms(&ctx.buffer, 4096);
-                            let normalized = (rms * 10.0).min(1.0);
-                            let _ = app_clone.emit("audio_level", normalized);
-                            *count = 0;
-                        }
-                    },
-                    err_fn,
-                    None,
-                )
+/// Applies voice commands to a transcript. Phrases match whole words, ignoring case and
+/// the punctuation Whisper adds ("New line." matches "new line"); longer phrases win.
+fn apply_voice_commands(settings: &VoiceCommandSettings, text: &str) -> VoiceCommandResult {
+    let mut phrases: Vec<(Vec<String>, VoiceAction)> = settings.phrases.iter()
+        .map(|(phrase, action)| (phrase.split_whitespace().map(normalize_command_word).collect::<Vec<_>>(), *action))
+        .filter(|(words, _)| !words.is_empty())
+        .collect();
+    phrases.sort_by_key(|(words, _)| std::cmp::Reverse(words.len()));
+    
+    let (words, _) = split_words(text);
+    let normalized: Vec<String> = words.iter().map(|(_, w)| normalize_command_word(w)).collect();
+    let mut out = String::new();
+    let mut delete_previous = false;
+    let mut all_caps = false;
+    let mut pending_ws: Option<&str> = None;  // Overrides the next word's leading whitespace
+    let mut i = 0;
+    
+    while i < words.len() {
+        let matched = phrases.iter().find(|(phrase, _)| normalized[i..].starts_with(phrase));
+        if let Some((phrase, action)) = matched {
+            match action {
+                VoiceAction::NewLine | VoiceAction::NewParagraph => {
+                    out.truncate(out.trim_end_matches(' ').len());
+                    out.push_str(if *action == VoiceAction::NewLine { "\n" } else { "\n\n" });
+                    pending_ws = Some("");
+                }
+                VoiceAction::DeleteThat => {
+                    if out.trim().is_empty() {
+                        delete_previous = true;
+                    }
+                    out.clear();
+                    all_caps = false;
+                    pending_ws = Some("");
+                }
+                VoiceAction::AllCaps => {
+                    all_caps = true;
+                    pending_ws.get_or_insert(words[i].0);
+                }
             }
-            cpal::SampleFormat::U16 => {
-                device.build_input_stream(
-                    &config.into(),
-                    move |data: &[u16], _: &cpal::InputCallbackInfo| {
-                        let mut ctx = audio_ctx_clone.lock().unwrap();
-                        
-                        for frame in data.chunks(channels) {
-                            let sample: f32 = frame.iter()
-                                .map(|s| s.to_float_sample())
-                                .sum::<f32>() / channels as f32;
-                            ctx.buffer.push(sample);
-                        }
+            i += phrase.len();
+            continue;
+        }
+        
+        let (ws, word) = words[i];
+        out.push_str(pending_ws.take().unwrap_or(ws));
+        if all_caps {
+            out.push_str(&word.to_uppercase());
+            all_caps = !split_trailing_punctuation(word).1.contains(['.', ',', '!', '?', ';', ':']);
+        } else {
+            out.push_str(word);
+        }
+        i += 1;
+    }
+    
+    VoiceCommandResult {
+        text: out,
+        delete_previous,
+    }
+}
 
-                        let mut count = sample_count_clone.lock().unwrap();
-                        *count += data.len() / channels;
-                        
-                        if *count >= 2048 {
-                            let rms = compute_rms(&ctx.buffer, 4096);
-                            let normalized = (rms * 10.0).min(1.0);
-                            let _ = app_clone.emit("audio_level", normalized);
-                            *count = 0;
-                        }
-                    },
-                    err_fn,
-                    None,
-                )
+/// Converts spelled-out English numbers to digits ("twenty four" -> "24",
+/// "twenty twenty four" -> "2024"). Lone words below ten are left spelled out.
+fn english_words_to_digits(text: &str) -> String {
+    let (words, trailing) = split_words(text);
+    let mut out = String::new();
+    let mut i = 0;
+    
+    while i < words.len() {
+        let (ws, word) = words[i];
+        if parse_number_token(split_trailing_punctuation(word).0).is_none() {
+            out.push_str(ws);
+            out.push_str(word);
+            i += 1;
+            continue;
+        }
+        
+        // Consume a run of number words, ending at punctuation, a line break or a
+        // non-number word
+        let run_start = i;
+        let mut numbers = Vec::new();
+        let mut acc = NumberAccumulator::default();
+        let mut run_punct = "";
+        while i < words.len() {
+            if i > run_start && words[i].0.contains('\n') {
+                break;
             }
-            _ => {
-                eprintln!("[Audio] Unsupported sample format");
-                let _ = app.emit("audio_error", "Unsupported sample format");
-                return;
+            let (core, punct) = split_trailing_punctuation(words[i].1);
+            let lowered = core.to_lowercase();
+            // "and" continues a number only between a hundred/scale word and more digits
+            if lowered == "and" && punct.is_empty() && matches!(
+                acc.last,
+                Some(NumberWordKind::Hundred) | Some(NumberWordKind::Scale)
+            ) && words.get(i + 1)
+                .and_then(|(_, w)| parse_number_token(split_trailing_punctuation(w).0))
+                .is_some()
+            {
+                i += 1;
+                continue;
             }
-        };
-
-        match stream {
-            Ok(s) => {
-                if let Err(e) = s.play() {
-                    eprintln!("[Audio] Failed to start stream: {:?}", e);
-                    let _ = app.emit("audio_error", format!("Failed to start stream: {:?}", e));
-                    return;
+            let Some(parts) = parse_number_token(core) else { break };
+            // Only commit the word if all of its parts fit
+            let mut trial_acc = acc.clone();
+            let mut trial_numbers = numbers.clone();
+            let consumed = parts.into_iter().all(|(value, kind)| {
+                if trial_acc.push(value, kind) {
+                    return true;
                 }
-                
-                println!("[Audio] Recording started");
-                
-                // Keep the stream alive until stop signal is set
-                // The stream is kept in this thread (not shared) to avoid Send/Sync issues
-                while !stop_signal.load(Ordering::SeqCst) {
-                    std::thread::sleep(std::time::Duration::from_millis(50));
+                if trial_acc.is_empty() {
+                    return false;
                 }
-                
-                // Stream is dropped here when we exit the loop
-                println!("[Audio] Stream stopped");
+                trial_numbers.push(trial_acc.take());
+                trial_acc.push(value, kind)
+            });
+            if !consumed {
+                break;
             }
-            Err(e) => {
-                eprintln!("[Audio] Failed to build input stream: {:?}", e);
-                let _ = app.emit("audio_error", format!("Failed to build stream: {:?}", e));
+            acc = trial_acc;
+            numbers = trial_numbers;
+            i += 1;
+            if !punct.is_empty() {
+                run_punct = punct;
+                break;
             }
         }
-    });
-}
-
-/// Stops audio recording and runs Whisper transcription
-fn stop_audio_recording(
-    app: AppHandle, 
-    audio_ctx: SharedAudio, 
-    whisper_state: SharedWhisper,
-    recording_state: Arc<RecordingState>,
-) {
-    // Signal the recording thread to stop
-    {
-        let ctx = audio_ctx.lock().unwrap();
-        ctx.stop_signal.store(true, Ordering::SeqCst);
+        if !acc.is_empty() {
+            numbers.push(acc.take());
+        }
+        
+        if i == run_start {
+            // The first word couldn't start a number (e.g. a bare "hundred")
+            out.push_str(ws);
+            out.push_str(word);
+            i += 1;
+            continue;
+        }
+        
+        out.push_str(ws);
+        let single_small_word = i - run_start == 1 && numbers.len() == 1 && numbers[0] < 10;
+        if single_small_word {
+            out.push_str(words[run_start].1);
+            continue;
+        }
+        // "twenty twenty four" reads as a year, "five five five" as a digit string
+        let is_year = numbers.len() == 2 && numbers.iter().all(|n| (10..100).contains(n));
+        let is_digit_string = numbers.len() > 1 && numbers.iter().all(|n| *n < 10);
+        let separator = if is_year || is_digit_string { "" } else { " " };
+        let digits: Vec<String> = numbers.iter().map(|n| n.to_string()).collect();
+        out.push_str(&digits.join(separator));
+        out.push_str(run_punct);
     }
     
-    // Mark as processing (transcription in progress)
-    recording_state.is_processing.store(true, Ordering::SeqCst);
+    out.push_str(trailing);
+    out
+}
+
+/// Spells out a number in English ("2024" -> "two thousand twenty-four")
+fn english_number_to_words(n: u64) -> String {
+    const UNITS: [&str; 20] = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        "ten", "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen",
+        "seventeen", "eighteen", "nineteen",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+    ];
+    const SCALES: [(u64, &str); 3] = [
+        (1_000_000_000, "billion"),
+        (1_000_000, "million"),
+        (1_000, "thousand"),
+    ];
     
-    // Give a brief moment for the stream to stop
-    std::thread::sleep(std::time::Duration::from_millis(100));
+    fn below_thousand(n: u64) -> String {
+        let mut parts = Vec::new();
+        if n >= 100 {
+            parts.push(format!("{} hundred", UNITS[(n / 100) as usize]));
+        }
+        let rest = n % 100;
+        if rest >= 20 {
+            let tens = TENS[(rest / 10) as usize];
+            if rest % 10 == 0 {
+                parts.push(tens.to_string());
+            } else {
+                parts.push(format!("{}-{}", tens, UNITS[(rest % 10) as usize]));
+            }
+        } else if rest > 0 || n == 0 {
+            parts.push(UNITS[rest as usize].to_string());
+        }
+        parts.join(" ")
+    }
     
-    std::thread::spawn(move || {
-        // Copy buffer and get sample rate
-        let (buffer, sample_rate) = {
-            let mut ctx = audio_ctx.lock().unwrap();
-            let buf = ctx.buffer.clone();
-            let rate = ctx.sample_rate;
-            ctx.buffer.clear(); // Clear buffer for next recording
-            (buf, rate)
-        };
-        
-        let duration = buffer.len() as f32 / sample_rate as f32;
-        println!("[Audio] Recording stopped. Captured {} samples at {} Hz ({:.2} seconds)", 
-                 buffer.len(), sample_rate, duration);
+    if n == 0 {
+        return UNITS[0].to_string();
+    }
+    let mut parts = Vec::new();
+    let mut rest = n;
+    for (scale, name) in SCALES {
+        if rest >= scale {
+            parts.push(format!("{} {}", below_thousand(rest / scale), name));
+            rest %= scale;
+        }
+    }
+    if rest > 0 {
+        parts.push(below_thousand(rest));
+    }
+    parts.join(" ")
+}
 
-        // Emit recording stats
-        let _ = app.emit("recording_complete", serde_json::json!({
-            "samples": buffer.len(),
-            "sample_rate": sample_rate,
-            "duration_seconds": duration
-        }));
-        
-        // Run Whisper transcription - emit to overlay window specifically
-        println!("[Transcription] Emitting transcription_started event");
-        if let Some(overlay) = app.get_webview_window("overlay") {
-            match overlay.emit("transcription_started", ()) {
-                Ok(_) => println!("[Transcription] transcription_started sent to overlay"),
-                Err(e) => println!("[Transcription] Failed to emit to overlay: {:?}", e),
+/// Converts whole numbers written as digits to English words ("42" -> "forty-two")
+fn english_digits_to_words(text: &str) -> String {
+    let (words, trailing) = split_words(text);
+    let mut out = String::new();
+    for (ws, word) in words {
+        out.push_str(ws);
+        let (core, punct) = split_trailing_punctuation(word);
+        let plain = core.replace(',', "");
+        let is_integer = !plain.is_empty()
+            && plain.len() <= 12
+            && plain.chars().all(|c| c.is_ascii_digit())
+            && !(plain.len() > 1 && plain.starts_with('0'));
+        match plain.parse::<u64>() {
+            Ok(n) if is_integer => {
+                out.push_str(&english_number_to_words(n));
+                out.push_str(punct);
             }
-        } else {
-            println!("[Transcription] WARNING: overlay window not found");
+            _ => out.push_str(word),
         }
-        // Also broadcast to all windows for the main app
-        let _ = app.emit("transcription_started", ());
-        
-        match run_whisper_on_buffer(&buffer, sample_rate, &whisper_state) {
-            Ok(text) => {
-                if text.is_empty() {
-                    let _ = app.emit("transcription_error", "No speech detected");
-                    // Hide overlay after a brief delay so user sees the error
-                    std::thread::sleep(std::time::Duration::from_millis(1500));
-                    hide_overlay(&app);
-                } else if text == "[BLANK_AUDIO]" {
-                    // Skip blank audio - don't paste anything
-                    println!("[Whisper] Blank audio detected, skipping paste");
-                    let _ = app.emit("transcription_error", "No speech detected");
-                    std::thread::sleep(std::time::Duration::from_millis(1500));
-                    hide_overlay(&app);
-                } else {
-                    // Copy to clipboard and paste
-                    match copy_to_clipboard_and_paste(&text) {
-                        Ok(()) => {
-                            let _ = app.emit("transcription_done", &text);
-                        }
-                        Err(e) => {
-                            eprintln!("[Clipboard/Paste] Error: {}", e);
-                            // Still emit transcription_done since we got the text
-                            let _ = app.emit("transcription_done", &text);
-                            let _ = app.emit("paste_error", e);
-                        }
-                    }
-                    // Hide overlay after transcription is done
-                    std::thread::sleep(std::time::Duration::from_millis(500));
-                    hide_overlay(&app);
-                }
+    }
+    out.push_str(trailing);
+    out
+}
+
+/// Applies the configured number formatting. Only English is supported for now;
+/// other locales pass through unchanged.
+fn apply_number_formatting(text: &str, settings: &NumberFormatting) -> String {
+    if settings.locale != "en" {
+        return text.to_string();
+    }
+    match settings.mode.as_str() {
+        "digits" => english_words_to_digits(text),
+        "words" => english_digits_to_words(text),
+        _ => text.to_string(),
+    }
+}
+
+/// Whether a transcript contains any letters or digits (Whisper sometimes returns
+/// just "." or "..." for silence)
+fn has_alphanumeric_content(text: &str) -> bool {
+    text.chars().any(|c| c.is_alphanumeric())
+}
+
+/// Copies text to the system clipboard
+fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    let mut clipboard = Clipboard::new().map_err(|e| format!("Failed to access clipboard: {:?}", e))?;
+    clipboard.set_text(text.to_string()).map_err(|e| format!("Failed to set clipboard text: {:?}", e))?;
+    println!("[Clipboard] Text copied: \"{}\"", text);
+    Ok(())
+}
+
+/// Wait before simulating a shortcut, so the target window is ready
+const SHORTCUT_SETTLE_MS: u64 = 50;
+
+/// Wait between the individual key events of a simulated shortcut
+const SHORTCUT_KEY_DELAY_MS: u64 = 20;
+
+/// A simulated shortcut that failed partway through
+struct ShortcutError {
+    message: String,
+    /// The key itself was pressed, so the shortcut may have taken effect
+    key_sent: bool,
+}
+
+/// Simulates a modifier+key shortcut (press modifier, press key, release key, release modifier).
+/// If an event fails, whatever is still held down is released before returning, so
+/// the modifier can't stay stuck for later keystrokes.
+fn simulate_shortcut(modifier: Key, key: Key) -> Result<(), ShortcutError> {
+    // Small delay to ensure the target window is ready
+    std::thread::sleep(std::time::Duration::from_millis(SHORTCUT_SETTLE_MS));
+    
+    let events = [
+        EventType::KeyPress(modifier),
+        EventType::KeyPress(key),
+        EventType::KeyRelease(key),
+        EventType::KeyRelease(modifier),
+    ];
+    for (i, event) in events.iter().enumerate() {
+        if i > 0 {
+            std::thread::sleep(std::time::Duration::from_millis(SHORTCUT_KEY_DELAY_MS));
+        }
+        if let Err(e) = simulate(event) {
+            // Best effort: these may fail the same way
+            if i >= 2 {
+                let _ = simulate(&EventType::KeyRelease(key));
             }
-            Err(e) => {
-                eprintln!("[Whisper] Error: {}", e);
-                let _ = app.emit("transcription_error", e);
-                // Hide overlay after a brief delay so user sees the error
-                std::thread::sleep(std::time::Duration::from_millis(1500));
-                hide_overlay(&app);
+            if i >= 1 {
+                let _ = simulate(&EventType::KeyRelease(modifier));
             }
+            return Err(ShortcutError {
+                message: format!("Failed to simulate {:?}: {:?}", event, e),
+                key_sent: i >= 2,
+            });
         }
-        
-        // Mark processing as complete
-        recording_state.is_processing.store(false, Ordering::SeqCst);
-    });
+    }
+    Ok(())
 }
 
-/// Starts a background thread that listens for global keyboard events.
-/// Detects Right Ctrl key presses to toggle recording state.
-fn start_hotkey_listener(
-    app: AppHandle, 
-    recording_state: Arc<RecordingState>, 
-    audio_ctx: SharedAudio,
-    whisper_state: SharedWhisper,
-) {
-    std::thread::spawn(move || {
-        let callback = move |event: Event| {
-            if let EventType::KeyPress(key) = event.event_type {
-                match key {
-                    Key::ControlLeft => {
-                        // Emit hotkey event for testing UI (left ctrl doesn't trigger recording)
-                        let _ = app.emit("hotkey_event", "LeftCtrl");
-                    }
-                    Key::ControlRight => {
-                        // Emit hotkey event for testing UI
-                        let _ = app.emit("hotkey_event", "RightCtrl");
+/// Modifier for the platform shortcuts (paste, undo): Cmd on macOS, Ctrl elsewhere
+#[cfg(target_os = "macos")]
+const SHORTCUT_MODIFIER: (Key, &str) = (Key::MetaLeft, "Cmd");
+#[cfg(not(target_os = "macos"))]
+const SHORTCUT_MODIFIER: (Key, &str) = (Key::ControlLeft, "Ctrl");
 
-                        let currently_recording = recording_state.is_recording.load(Ordering::SeqCst);
-                        let currently_processing = recording_state.is_processing.load(Ordering::SeqCst);
+/// Simulates the platform paste keystroke (Cmd+V on macOS, Ctrl+V elsewhere)
+fn simulate_paste() -> Result<(), ShortcutError> {
+    let (modifier, label) = SHORTCUT_MODIFIER;
+    simulate_shortcut(modifier, Key::KeyV)?;
+    println!("[Paste] Simulated {}+V", label);
+    Ok(())
+}
 
-                        // Don't start a new recording if we're still processing the previous one
-                        if currently_processing && !currently_recording {
-                            println!("[Hotkey] Ignoring - still processing previous transcription");
-                            return;
-                        }
+/// How long after a paste the undo hotkey can still retract it
+const UNDO_PASTE_WINDOW_SECS: u64 = 120;
 
-                        if !currently_recording {
-                            // Check if a model is loaded before starting recording
-                            let model_loaded = whisper_state.lock()
-                                .map(|ws| ws.ctx.is_some())
-                                .unwrap_or(false);
-                            
-                            if !model_loaded {
-                                // Show "no model" message and auto-hide
-                                println!("[Hotkey] No model loaded, cannot start recording");
-                                
-                                let app_clone = app.clone();
-                                std::thread::spawn(move || {
-                                    show_overlay(&app_clone);
-                                    // Give React time to mount component and set up listeners
-                                    std::thread::sleep(std::time::Duration::from_millis(200));
-                                    println!("[Hotkey] Emitting no_model_selected event");
-                                    let _ = app_clone.emit("no_model_selected", ());
-                                    std::thread::sleep(std::time::Duration::from_millis(2000));
-                                    hide_overlay(&app_clone);
-                                });
-                                return;
-                            }
-                            
-                            // Start recording
-                            recording_state.is_recording.store(true, Ordering::SeqCst);
-                            println!("[Hotkey] Recording started");
-                            
-                            // Show overlay window first, then emit event after a delay
-                            // so React has time to mount and set up event listeners
-                            let app_clone = app.clone();
-                            let audio_ctx_clone = audio_ctx.clone();
-                            std::thread::spawn(move || {
-                                show_overlay(&app_clone);
-                                // Emit recording_started immediately so UI resets to recording state
-                                println!("[Hotkey] Emitting recording_started event");
-                                let _ = app_clone.emit("recording_started", ());
-                                
-                                // Start audio capture
-                                start_audio_recording(app_clone, audio_ctx_clone);
-                            });
-                        } else {
-                            // Stop recording
-                            recording_state.is_recording.store(false, Ordering::SeqCst);
-                            let _ = app.emit("recording_stopped", ());
-                            println!("[Hotkey] Recording stopped");
-                            
-                            // Stop audio capture and run transcription
-                            // (overlay will be hidden after transcription completes)
-                            stop_audio_recording(
-                                app.clone(), 
-                                audio_ctx.clone(), 
-                                whisper_state.clone(),
-                                recording_state.clone(),
-                            );
-                        }
-                    }
-                    Key::Alt => {
-                        // Emit hotkey event for testing UI (future use)
-                        // Note: rdev doesn't distinguish left/right Alt on all platforms
-                        let _ = app.emit("hotkey_event", "Alt");
+/// The last transcript winsper inserted into another app
+pub struct LastPaste {
+    pub at: std::time::Instant,
+    pub chars: usize,
+}
+
+/// The last paste, cleared once it has been undone or deleted
+pub type SharedLastPaste = Arc<Mutex<Option<LastPaste>>>;
+
+/// Records a successful paste so it can be undone or deleted later
+fn record_last_paste(app: &AppHandle, text: &str) {
+    if let Ok(mut last) = app.state::<SharedLastPaste>().lock() {
+        *last = Some(LastPaste {
+            at: std::time::Instant::now(),
+            chars: text.chars().count(),
+        });
+    }
+}
+
+/// Retracts the last paste with the platform undo shortcut (Cmd+Z on macOS, Ctrl+Z elsewhere),
+/// if a paste happened recently and hasn't been undone already
+fn undo_last_paste(app: &AppHandle) {
+    let last = app.state::<SharedLastPaste>().lock().ok().and_then(|mut l| l.take());
+    match last {
+        Some(last) if last.at.elapsed().as_secs() < UNDO_PASTE_WINDOW_SECS => {
+            let (modifier, _) = SHORTCUT_MODIFIER;
+            match simulate_shortcut(modifier, Key::KeyZ) {
+                Ok(()) => {
+                    println!("[Paste] Undid last paste");
+                    let _ = app.emit("paste_undone", ());
+                }
+                Err(e) => {
+                    eprintln!("[Paste] Undo failed: {}", e.message);
+                    let _ = app.emit("paste_error", e.message);
+                }
+            }
+        }
+        _ => println!("[Paste] No recent paste to undo"),
+    }
+}
+
+/// Deletes the last pasted transcript by backspacing over it ("delete that")
+fn delete_last_paste(app: &AppHandle) {
+    let last = app.state::<SharedLastPaste>().lock().ok().and_then(|mut l| l.take());
+    let Some(last) = last.filter(|l| l.at.elapsed().as_secs() < UNDO_PASTE_WINDOW_SECS) else {
+        println!("[Paste] No recent paste to delete");
+        return;
+    };
+    
+    let delay = std::time::Duration::from_millis(load_typing_delay_ms(app));
+    for _ in 0..last.chars {
+        let result = simulate(&EventType::KeyPress(Key::Backspace))
+            .and_then(|_| simulate(&EventType::KeyRelease(Key::Backspace)));
+        if let Err(e) = result {
+            eprintln!("[Paste] Failed to delete last paste: {:?}", e);
+            let _ = app.emit("paste_error", format!("Failed to delete last paste: {:?}", e));
+            return;
+        }
+        std::thread::sleep(delay);
+    }
+    println!("[Paste] Deleted last paste ({} characters)", last.chars);
+    let _ = app.emit("paste_deleted", last.chars);
+}
+
+/// Default wait between the simulated paste and restoring the original clipboard
+const DEFAULT_CLIPBOARD_RESTORE_DELAY_MS: u64 = 300;
+
+/// Longest allowed clipboard restore delay
+const MAX_CLIPBOARD_RESTORE_DELAY_MS: u64 = 5000;
+
+/// Default wait between simulated keystrokes when typing text
+const DEFAULT_TYPING_KEY_DELAY_MS: u64 = 5;
+
+/// Longest allowed wait between typed keystrokes
+const MAX_TYPING_KEY_DELAY_MS: u64 = 200;
+
+/// Maps a character to the key that types it on a US layout, and whether Shift is needed
+fn key_for_char(c: char) -> Option<(Key, bool)> {
+    const LETTERS: [Key; 26] = [
+        Key::KeyA, Key::KeyB, Key::KeyC, Key::KeyD, Key::KeyE, Key::KeyF, Key::KeyG,
+        Key::KeyH, Key::KeyI, Key::KeyJ, Key::KeyK, Key::KeyL, Key::KeyM, Key::KeyN,
+        Key::KeyO, Key::KeyP, Key::KeyQ, Key::KeyR, Key::KeyS, Key::KeyT, Key::KeyU,
+        Key::KeyV, Key::KeyW, Key::KeyX, Key::KeyY, Key::KeyZ,
+    ];
+    const DIGITS: [Key; 10] = [
+        Key::Num0, Key::Num1, Key::Num2, Key::Num3, Key::Num4,
+        Key::Num5, Key::Num6, Key::Num7, Key::Num8, Key::Num9,
+    ];
+    const SHIFTED_DIGITS: &str = ")!@#$%^&*(";
+    
+    if c.is_ascii_lowercase() {
+        return Some((LETTERS[(c as u8 - b'a') as usize], false));
+    }
+    if c.is_ascii_uppercase() {
+        return Some((LETTERS[(c as u8 - b'A') as usize], true));
+    }
+    if c.is_ascii_digit() {
+        return Some((DIGITS[(c as u8 - b'0') as usize], false));
+    }
+    if let Some(i) = SHIFTED_DIGITS.find(c) {
+        return Some((DIGITS[i], true));
+    }
+    let mapped = match c {
+        ' ' => (Key::Space, false),
+        '\n' => (Key::Return, false),
+        '\t' => (Key::Tab, false),
+        '-' => (Key::Minus, false),
+        '_' => (Key::Minus, true),
+        '=' => (Key::Equal, false),
+        '+' => (Key::Equal, true),
+        '[' => (Key::LeftBracket, false),
+        '{' => (Key::LeftBracket, true),
+        ']' => (Key::RightBracket, false),
+        '}' => (Key::RightBracket, true),
+        '\\' => (Key::BackSlash, false),
+        '|' => (Key::BackSlash, true),
+        ';' => (Key::SemiColon, false),
+        ':' => (Key::SemiColon, true),
+        '\'' => (Key::Quote, false),
+        '"' => (Key::Quote, true),
+        ',' => (Key::Comma, false),
+        '<' => (Key::Comma, true),
+        '.' => (Key::Dot, false),
+        '>' => (Key::Dot, true),
+        '/' => (Key::Slash, false),
+        '?' => (Key::Slash, true),
+        '`' => (Key::BackQuote, false),
+        '~' => (Key::BackQuote, true),
+        _ => return None,
+    };
+    Some(mapped)
+}
+
+/// Types text by simulating a key press per character, without touching the clipboard,
+/// waiting `delay_ms` between key events so slow targets don't drop characters.
+/// Characters with no key on a US layout are skipped; returns how many were.
+fn simulate_typing(text: &str, delay_ms: u64) -> Result<usize, String> {
+    let delay = std::time::Duration::from_millis(delay_ms);
+    let mut skipped = 0;
+    for c in text.chars() {
+        let Some((key, shift)) = key_for_char(c) else {
+            skipped += 1;
+            continue;
+        };
+        let mut events = Vec::with_capacity(4);
+        if shift {
+            events.push(EventType::KeyPress(Key::ShiftLeft));
+        }
+        events.push(EventType::KeyPress(key));
+        events.push(EventType::KeyRelease(key));
+        if shift {
+            events.push(EventType::KeyRelease(Key::ShiftLeft));
+        }
+        for event in &events {
+            simulate(event).map_err(|e| format!("Failed to type {:?}: {:?}", c, e))?;
+            std::thread::sleep(delay);
+        }
+    }
+    if skipped > 0 {
+        eprintln!("[Type] Skipped {} characters with no key mapping", skipped);
+    }
+    println!("[Type] Typed {} characters", text.chars().count() - skipped);
+    Ok(skipped)
+}
+
+/// Checks that the clipboard actually holds `text`, catching clipboard managers and
+/// remote-desktop sync that swallow or replace the write before it can be pasted
+fn verify_clipboard_holds(text: &str) -> Result<(), String> {
+    let current = Clipboard::new()
+        .and_then(|mut c| c.get_text())
+        .map_err(|e| format!("Failed to read back clipboard: {:?}", e))?;
+    if current != text {
+        return Err("Clipboard did not hold the transcript at paste time".to_string());
+    }
+    Ok(())
+}
+
+/// Copies text to clipboard and simulates paste. With `preserve_clipboard` on (the
+/// default), the previous clipboard text is put back after the configured restore
+/// delay. Non-text contents such as images can't be read back, so they aren't restored.
+///
+/// With `verify_paste` on, failures winsper can observe (the clipboard write not sticking,
+/// or the keystroke failing to inject before V went out) fall back to typing the text.
+/// Once V was pressed the paste may have landed, so it isn't typed again. Whether another
+/// app consumed the paste can't be observed, so a silently swallowed Ctrl+V isn't caught.
+fn copy_to_clipboard_and_paste(app: &AppHandle, text: &str, target_window: Option<WindowHandle>) -> Result<(), String> {
+    if let Some(target) = target_window {
+        if let Err(e) = ensure_paste_target(app, target) {
+            // Leave the transcript on the clipboard so it can still be pasted by hand
+            let _ = copy_to_clipboard(text);
+            return Err(e);
+        }
+    }
+    
+    let original = if load_preserve_clipboard(app) {
+        let text = Clipboard::new().and_then(|mut c| c.get_text()).ok();
+        if text.is_none() {
+            println!("[Clipboard] Previous contents aren't text, they won't be restored");
+        }
+        text
+    } else {
+        None
+    };
+    
+    let verify = load_verify_paste(app);
+    let clipboard_ready = copy_to_clipboard(text)
+        .and_then(|_| if verify { verify_clipboard_holds(text) } else { Ok(()) });
+    let fallback_reason = match clipboard_ready.map(|_| simulate_paste()) {
+        Ok(Ok(())) => None,
+        // Typing after a paste that may have landed could insert the text twice
+        Ok(Err(e)) if e.key_sent || !verify => return Err(e.message),
+        Ok(Err(e)) => Some(e.message),
+        Err(e) if verify => Some(e),
+        Err(e) => return Err(e),
+    };
+    if let Some(reason) = fallback_reason {
+        eprintln!("[Paste] Paste failed ({}), typing instead", reason);
+        let _ = app.emit("paste_fallback_typing", &reason);
+        simulate_typing(text, load_typing_delay_ms(app))?;
+    }
+    record_last_paste(app, text);
+    
+    if let Some(original) = original {
+        // The target app reads the clipboard asynchronously after the keystroke
+        std::thread::sleep(std::time::Duration::from_millis(load_clipboard_restore_delay_ms(app)));
+        // The paste already landed, so a failed restore is only logged
+        match Clipboard::new().and_then(|mut c| c.set_text(original)) {
+            Ok(()) => println!("[Clipboard] Restored previous clipboard contents"),
+            Err(e) => eprintln!("[Clipboard] Failed to restore clipboard: {:?}", e),
+        }
+    }
+    Ok(())
+}
+
+/// Abbreviations whose trailing period doesn't end a sentence
+const SENTENCE_ABBREVIATIONS: &[&str] = &[
+    "mr", "mrs", "ms", "dr", "prof", "sr", "jr", "st", "vs", "etc", "e.g", "i.e", "approx", "fig", "inc", "ltd",
+];
+
+/// Splits a transcript into sentences on `.`, `!` and `?` followed by whitespace and
+/// a word that isn't lowercase. Decimals never split (no whitespace after the point),
+/// and neither do known abbreviations or single-letter initials.
+fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        current.push(c);
+        if !matches!(c, '.' | '!' | '?') {
+            continue;
+        }
+        // Keep closing quotes and brackets with the sentence they end
+        while let Some(&(_, next)) = chars.peek() {
+            if !matches!(next, '"' | '\'' | ')' | ']' | '.' | '!' | '?') {
+                break;
+            }
+            current.push(next);
+            chars.next();
+        }
+        let rest = chars.peek().map_or("", |&(i, _)| &text[i..]);
+        let next_word = rest.trim_start().chars().next();
+        if !rest.starts_with(char::is_whitespace) || next_word.is_some_and(char::is_lowercase) {
+            continue;
+        }
+        if c == '.' {
+            let word = current.trim_end_matches(['.', '"', '\'', ')', ']'])
+                .rsplit(char::is_whitespace)
+                .next()
+                .unwrap_or("")
+                .to_lowercase();
+            let initial = word.chars().count() == 1 && word.chars().all(char::is_alphabetic);
+            if initial || SENTENCE_ABBREVIATIONS.contains(&word.as_str()) {
+                continue;
+            }
+        }
+        let sentence = current.trim();
+        if !sentence.is_empty() {
+            sentences.push(sentence.to_string());
+        }
+        current.clear();
+    }
+    let rest = current.trim();
+    if !rest.is_empty() {
+        sentences.push(rest.to_string());
+    }
+    sentences
+}
+
+/// Puts a transcript where the output mode says: pasted (optionally per sentence),
+/// only copied for the user to paste by hand, or typed key by key
+fn deliver_output(app: &AppHandle, text: &str, target_window: Option<WindowHandle>) -> Result<(), String> {
+    match load_output_mode(app).as_str() {
+        "copy_only" => copy_to_clipboard(text),
+        "type" => {
+            if let Some(target) = target_window {
+                ensure_paste_target(app, target)?;
+            }
+            let skipped = simulate_typing(text, load_typing_delay_ms(app))?;
+            if skipped > 0 {
+                let _ = app.emit("typing_skipped_characters", skipped);
+            }
+            record_last_paste(app, text);
+            Ok(())
+        }
+        _ => {
+            let split = load_sentence_split_settings(app);
+            if split.enabled {
+                paste_sentences(app, text, target_window, split.delay_ms)
+            } else {
+                copy_to_clipboard_and_paste(app, text, target_window)
+            }
+        }
+    }
+}
+
+/// Longest allowed wait around each Enter when pasting per sentence
+const MAX_SENTENCE_SPLIT_DELAY_MS: u64 = 5000;
+
+/// Pastes a transcript one sentence at a time, pressing Enter between sentences.
+/// Only the first paste checks the target window; the rest follow in the same field.
+fn paste_sentences(app: &AppHandle, text: &str, target_window: Option<WindowHandle>, delay_ms: u64) -> Result<(), String> {
+    let delay = std::time::Duration::from_millis(delay_ms);
+    let sentences = split_sentences(text);
+    for (i, sentence) in sentences.iter().enumerate() {
+        if i > 0 {
+            std::thread::sleep(delay);
+            simulate(&EventType::KeyPress(Key::Return))
+                .and_then(|_| simulate(&EventType::KeyRelease(Key::Return)))
+                .map_err(|e| format!("Failed to simulate Enter: {:?}", e))?;
+            std::thread::sleep(delay);
+        }
+        copy_to_clipboard_and_paste(app, sentence, if i == 0 { target_window } else { None })?;
+    }
+    println!("[Paste] Pasted {} sentences separately", sentences.len());
+    Ok(())
+}
+
+/// Native handle of a top-level window, used to check paste focus
+pub type WindowHandle = isize;
+
+/// The window that currently has keyboard focus, where the platform can tell
+fn foreground_window() -> Option<WindowHandle> {
+    #[cfg(windows)]
+    {
+        let hwnd = unsafe { windows_sys::Win32::UI::WindowsAndMessaging::GetForegroundWindow() } as WindowHandle;
+        (hwnd != 0).then_some(hwnd)
+    }
+    #[cfg(not(windows))]
+    {
+        None
+    }
+}
+
+/// Asks the OS to bring a window back to the foreground
+fn focus_window(window: WindowHandle) -> bool {
+    #[cfg(windows)]
+    {
+        unsafe { windows_sys::Win32::UI::WindowsAndMessaging::SetForegroundWindow(window as _) != 0 }
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = window;
+        false
+    }
+}
+
+/// Checks the paste target window still has focus. Depending on the focus check
+/// mode, a changed focus aborts the paste or is moved back first; a window
+/// remembered at recording start is always refocused.
+fn ensure_paste_target(app: &AppHandle, target: WindowHandle) -> Result<(), String> {
+    let current = foreground_window();
+    if current == Some(target) {
+        return Ok(());
+    }
+    
+    let mode = load_paste_focus_check(app);
+    let refocus = mode == "refocus" || load_paste_target(app) == "remembered";
+    let refocused = refocus && focus_window(target) && {
+        std::thread::sleep(std::time::Duration::from_millis(SHORTCUT_SETTLE_MS));
+        foreground_window() == Some(target)
+    };
+    println!("[Paste] Focus changed before paste ({:?} -> {:?}), refocused: {}", target, current, refocused);
+    let _ = app.emit("paste_focus_changed", serde_json::json!({
+        "mode": mode,
+        "refocused": refocused,
+    }));
+    if refocused {
+        Ok(())
+    } else {
+        Err("Focus changed before paste; the transcript was left on the clipboard".to_string())
+    }
+}
+
+/// Default pause between showing the transcribing state and starting inference
+const DEFAULT_PRE_TRANSCRIPTION_DELAY_MS: u64 = 50;
+
+/// Longest allowed pre-transcription delay
+const MAX_PRE_TRANSCRIPTION_DELAY_MS: u64 = 1000;
+
+/// Default overlay fade-in/fade-out duration
+const DEFAULT_OVERLAY_ANIMATION_MS: u64 = 150;
+
+/// Upper bound on the overlay animation, so a bad config can't leave it lingering
+const MAX_OVERLAY_ANIMATION_MS: u64 = 1000;
+
+/// Overlay size (logical pixels) and opacity, applied each time it's shown
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverlayAppearance {
+    pub width: u32,
+    pub height: u32,
+    pub opacity: f32,
+}
+
+impl Default for OverlayAppearance {
+    fn default() -> Self {
+        // Matches the overlay window definition in tauri.conf.json
+        Self {
+            width: 300,
+            height: 80,
+            opacity: 1.0,
+        }
+    }
+}
+
+impl OverlayAppearance {
+    /// Clamps to ranges that keep the overlay visible and its content legible
+    fn clamped(self) -> Self {
+        Self {
+            width: self.width.clamp(150, 600),
+            height: self.height.clamp(40, 200),
+            opacity: self.opacity.clamp(0.2, 1.0),
+        }
+    }
+}
+
+/// Bumped on every show, so a delayed hide can tell the overlay was shown again meanwhile
+#[derive(Default)]
+pub struct OverlayVisibility {
+    generation: AtomicU64,
+}
+
+/// Label of the overlay window declared in tauri.conf.json
+const OVERLAY_WINDOW: &str = "overlay";
+
+/// The overlay window, or None when it doesn't exist (headless runs, embedders that
+/// leave it out). Every overlay interaction goes through this, so the
+/// record -> transcribe -> paste flow behaves the same with or without it.
+fn overlay_window<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Option<tauri::WebviewWindow<R>> {
+    app.get_webview_window(OVERLAY_WINDOW)
+}
+
+/// Sends an event to the overlay window only; a no-op without an overlay
+fn emit_to_overlay<R: tauri::Runtime, S: Serialize + Clone>(app: &tauri::AppHandle<R>, event: &str, payload: S) {
+    if let Some(overlay) = overlay_window(app) {
+        if let Err(e) = overlay.emit(event, payload) {
+            eprintln!("[Overlay] Failed to emit {}: {:?}", event, e);
+        }
+    }
+}
+
+/// Shows the overlay window and positions it at the bottom center of the screen.
+/// The show is still broadcast as `overlay_will_show` when there is no overlay.
+fn show_overlay(app: &AppHandle) {
+    // Cancels any hide still waiting on its fade-out
+    app.state::<OverlayVisibility>().generation.fetch_add(1, Ordering::SeqCst);
+    let _ = app.emit("overlay_will_show", load_overlay_animation_ms(app));
+    
+    if let Some(overlay) = overlay_window(app) {
+        // Apply the configured size; opacity is applied by the overlay's own styling
+        let appearance = load_overlay_appearance(app);
+        let _ = overlay.set_size(tauri::LogicalSize::new(appearance.width, appearance.height));
+        let _ = app.emit("overlay_appearance", &appearance);
+        
+        // Use the configured monitor if it's still connected
+        let preferred = load_overlay_monitor(app).and_then(|wanted| {
+            let found = overlay.available_monitors()
+                .ok()?
+                .into_iter()
+                .enumerate()
+                .find(|(i, m)| monitor_id(m, *i) == wanted)
+                .map(|(_, m)| m);
+            if found.is_none() {
+                println!("[Overlay] Configured monitor '{}' not connected, using primary", wanted);
+            }
+            found
+        });
+        
+        // Otherwise the primary monitor (more reliable than current_monitor for hidden windows)
+        let monitor = preferred
+            .or_else(|| overlay.primary_monitor().ok().flatten())
+            .or_else(|| overlay.current_monitor().ok().flatten());
+        
+        if let Some(monitor) = monitor {
+            let screen_size = monitor.size();
+            let screen_pos = monitor.position();
+            
+            // Overlay size in physical pixels (the resize above may not have landed yet)
+            let overlay_width = (appearance.width as f64 * monitor.scale_factor()) as i32;
+            let overlay_height = (appearance.height as f64 * monitor.scale_factor()) as i32;
+            
+            // Calculate position: horizontally centered, near the bottom
+            let x = screen_pos.x + (screen_size.width as i32 - overlay_width) / 2;
+            let y = screen_pos.y + screen_size.height as i32 - overlay_height - 100; // 100px from bottom
+            
+            let _ = overlay.set_position(PhysicalPosition::new(x, y));
+            println!("[Overlay] Positioned at ({}, {})", x, y);
+        }
+        
+        let _ = overlay.show();
+        println!("[Overlay] Window shown");
+        // Don't set focus - this would steal keyboard events from rdev
+        // The overlay is just a visual indicator
+    }
+}
+
+/// Stable identifier for a monitor: its name, or its position in the list if unnamed
+fn monitor_id(monitor: &tauri::Monitor, index: usize) -> String {
+    monitor.name()
+        .cloned()
+        .unwrap_or_else(|| format!("monitor-{}", index))
+}
+
+/// Hides the overlay window once its fade-out animation has had time to finish
+fn hide_overlay(app: &AppHandle) {
+    let animation_ms = load_overlay_animation_ms(app);
+    let _ = app.emit("overlay_will_hide", animation_ms);
+    let Some(overlay) = overlay_window(app) else { return };
+    if animation_ms == 0 {
+        let _ = overlay.hide();
+        return;
+    }
+    
+    let generation = app.state::<OverlayVisibility>().generation.load(Ordering::SeqCst);
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(animation_ms));
+        // Skip the hide if the overlay was shown again during the fade
+        if app.state::<OverlayVisibility>().generation.load(Ordering::SeqCst) == generation {
+            let _ = overlay.hide();
+        }
+    });
+}
+
+/// How often the recording thread checks for an utterance endpoint
+const ENDPOINT_POLL_MS: u64 = 50;
+
+/// RMS level above which audio counts as speech for endpointing
+const ENDPOINT_SPEECH_RMS: f32 = 0.01;
+
+/// Trailing silence that ends an utterance in continuous dictation
+const ENDPOINT_SILENCE_MS: u64 = 1200;
+
+/// Silence with no speech at all that ends continuous dictation, so it never loops on an empty room
+const CONTINUOUS_IDLE_TIMEOUT_MS: u64 = 30_000;
+
+/// What the endpointer decided after a poll
+enum EndpointEvent {
+    /// Speech was followed by enough silence to end the utterance
+    UtteranceEnd,
+    /// No speech at all for the idle timeout
+    Idle,
+}
+
+/// Energy-based utterance endpointing for continuous dictation
+struct Endpointer {
+    speech_rms: f32,
+    heard_speech: bool,
+    silence_ms: u64,
+    detector: VadDetector,
+}
+
+impl Endpointer {
+    /// Creates an endpointer counting levels at or above `speech_rms` as speech
+    fn new(speech_rms: f32, detector: VadDetector) -> Self {
+        println!("[Audio] Endpointing with the {} detector", detector.kind);
+        Self {
+            speech_rms,
+            heard_speech: false,
+            silence_ms: 0,
+            detector,
+        }
+    }
+    
+    /// Feeds the RMS level of the latest poll window
+    fn update(&mut self, level: f32) -> Option<EndpointEvent> {
+        if level >= self.speech_rms {
+            self.heard_speech = true;
+            self.silence_ms = 0;
+            return None;
+        }
+        self.silence_ms += ENDPOINT_POLL_MS;
+        if self.heard_speech && self.silence_ms >= ENDPOINT_SILENCE_MS {
+            Some(EndpointEvent::UtteranceEnd)
+        } else if !self.heard_speech && self.silence_ms >= CONTINUOUS_IDLE_TIMEOUT_MS {
+            Some(EndpointEvent::Idle)
+        } else {
+            None
+        }
+    }
+}
+
+/// Gap between callbacks, beyond the audio they delivered, that counts as a dropout
+const DROPOUT_TOLERANCE_MS: f64 = 20.0;
+
+/// Minimum spacing between `audio_dropout_warning` events, so an overloaded system isn't flooded
+const DROPOUT_WARNING_INTERVAL_MS: u64 = 1000;
+
+/// Payload of the `audio_dropout_warning` event
+#[derive(Clone, Serialize)]
+pub struct AudioDropoutWarning {
+    pub lost_ms: u64,        // Audio lost since the previous warning
+    pub total_lost_ms: u64,  // Audio lost over the whole recording so far
+    pub dropouts: u32,       // Number of gaps detected so far
+}
+
+/// Per-recording noise gate state, applied to samples as they are captured
+struct NoiseGate {
+    threshold: f32,
+    attack_step: f32,
+    release_step: f32,
+    hold_samples: usize,
+    hold_left: usize,
+    envelope_decay: f32,
+    envelope: f32,
+    gain: f32,
+}
+
+impl NoiseGate {
+    fn new(settings: &NoiseGateSettings, sample_rate: u32) -> Self {
+        let samples_for = |ms: u32| (ms as f32 * sample_rate as f32 / 1000.0).max(1.0);
+        Self {
+            threshold: settings.threshold,
+            attack_step: 1.0 / samples_for(settings.attack_ms),
+            release_step: 1.0 / samples_for(settings.release_ms),
+            hold_samples: samples_for(settings.hold_ms) as usize,
+            hold_left: 0,
+            // ~10ms peak follower, so single zero crossings don't close the gate
+            envelope_decay: (-1.0 / samples_for(10)).exp(),
+            envelope: 0.0,
+            gain: 0.0,
+        }
+    }
+    
+    /// Gates captured samples in place
+    fn process(&mut self, samples: &mut [f32]) {
+        for sample in samples.iter_mut() {
+            self.envelope = sample.abs().max(self.envelope * self.envelope_decay);
+            if self.envelope >= self.threshold {
+                self.hold_left = self.hold_samples;
+            }
+            if self.hold_left > 0 {
+                self.hold_left -= 1;
+                self.gain = (self.gain + self.attack_step).min(1.0);
+            } else {
+                self.gain = (self.gain - self.release_step).max(0.0);
+            }
+            *sample *= self.gain;
+        }
+    }
+}
+
+/// Detects dropped capture callbacks by comparing callback timestamps with the audio delivered
+#[derive(Default)]
+struct DropoutDetector {
+    last: Option<(cpal::StreamInstant, usize)>,  // Previous callback's capture time and frame count
+    pending_lost_ms: f64,
+    total_lost_ms: f64,
+    dropouts: u32,
+    last_warning: Option<std::time::Instant>,
+}
+
+impl DropoutDetector {
+    /// Feeds one callback, returning a warning when audio was lost and one is due
+    fn observe(&mut self, info: &cpal::InputCallbackInfo, frames: usize, sample_rate: u32) -> Option<AudioDropoutWarning> {
+        let capture = info.timestamp().capture;
+        let previous = self.last.replace((capture, frames));
+        let (prev_capture, prev_frames) = previous?;
+        
+        let elapsed_ms = capture.duration_since(&prev_capture)?.as_secs_f64() * 1000.0;
+        let delivered_ms = prev_frames as f64 * 1000.0 / sample_rate as f64;
+        let gap_ms = elapsed_ms - delivered_ms;
+        if gap_ms > DROPOUT_TOLERANCE_MS {
+            self.pending_lost_ms += gap_ms;
+            self.total_lost_ms += gap_ms;
+            self.dropouts += 1;
+        }
+        
+        if self.pending_lost_ms <= 0.0 {
+            return None;
+        }
+        let due = self.last_warning
+            .is_none_or(|t| t.elapsed() >= std::time::Duration::from_millis(DROPOUT_WARNING_INTERVAL_MS));
+        if !due {
+            return None;
+        }
+        self.last_warning = Some(std::time::Instant::now());
+        let warning = AudioDropoutWarning {
+            lost_ms: self.pending_lost_ms.round() as u64,
+            total_lost_ms: self.total_lost_ms.round() as u64,
+            dropouts: self.dropouts,
+        };
+        self.pending_lost_ms = 0.0;
+        Some(warning)
+    }
+}
+
+/// Lists input devices with unique ids. The id is the device name, with " #2", " #3", ...
+/// appended to later devices sharing a name (e.g. two identical USB mics).
+fn enumerate_input_devices() -> Result<Vec<(String, cpal::Device)>, String> {
+    let devices = cpal::default_host().input_devices()
+        .map_err(|e| format!("Failed to enumerate devices: {:?}", e))?;
+    Ok(with_unique_ids(devices))
+}
+
+/// Lists output devices with the same unique ids as input devices
+fn enumerate_output_devices() -> Result<Vec<(String, cpal::Device)>, String> {
+    let devices = cpal::default_host().output_devices()
+        .map_err(|e| format!("Failed to enumerate devices: {:?}", e))?;
+    Ok(with_unique_ids(devices))
+}
+
+/// Pairs devices with the unique ids described on `enumerate_input_devices`
+fn with_unique_ids(devices: impl Iterator<Item = cpal::Device>) -> Vec<(String, cpal::Device)> {
+    let mut seen: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    devices
+        .filter_map(|device| {
+            let name = device.name().ok()?;
+            let count = seen.entry(name.clone()).or_insert(0);
+            *count += 1;
+            let id = if *count == 1 { name } else { format!("{} #{}", name, count) };
+            Some((id, device))
+        })
+        .collect()
+}
+
+/// Finds the selected input device by id, falling back to the default if it isn't connected
+fn find_input_device(selected_mic: Option<&str>) -> Option<cpal::Device> {
+    resolve_input_device(selected_mic).map(|(device, _)| device)
+}
+
+/// Like `find_input_device`, but also reports whether the selected device was
+/// missing and the default was used instead
+fn resolve_input_device(selected_mic: Option<&str>) -> Option<(cpal::Device, bool)> {
+    if let Some(mic_id) = selected_mic {
+        // Try to find the selected device
+        let found = enumerate_input_devices()
+            .ok()
+            .and_then(|devices| devices.into_iter().find(|(id, _)| id == mic_id))
+            .map(|(_, device)| device);
+        if let Some(device) = found {
+            return Some((device, false));
+        }
+        eprintln!("[Audio] Selected device '{}' not found, using default", mic_id);
+        return cpal::default_host().default_input_device().map(|device| (device, true));
+    }
+    cpal::default_host().default_input_device().map(|device| (device, false))
+}
+
+/// Converts interleaved samples to mono by averaging channels
+fn push_mono_samples<T>(data: &[T], channels: usize, out: &mut Vec<f32>)
+where
+    T: cpal::SizedSample,
+    f32: cpal::FromSample<T>,
+{
+    for frame in data.chunks(channels) {
+        let sum: f32 = frame.iter().map(|s| <f32 as cpal::FromSample<T>>::from_sample_(*s)).sum();
+        out.push(sum / channels as f32);
+    }
+}
+
+/// Builds an input stream that appends mono samples to a shared buffer,
+/// for short measurements outside of a recording
+fn build_probe_stream(
+    device: &cpal::Device,
+    config: &cpal::SupportedStreamConfig,
+    samples: Arc<Mutex<Vec<f32>>>,
+) -> Result<cpal::Stream, String> {
+    fn build<T>(
+        device: &cpal::Device,
+        config: &cpal::StreamConfig,
+        samples: Arc<Mutex<Vec<f32>>>,
+    ) -> Result<cpal::Stream, cpal::BuildStreamError>
+    where
+        T: cpal::SizedSample,
+        f32: cpal::FromSample<T>,
+    {
+        let channels = config.channels as usize;
+        device.build_input_stream(
+            config,
+            move |data: &[T], _: &cpal::InputCallbackInfo| {
+                if let Ok(mut buf) = samples.lock() {
+                    push_mono_samples(data, channels, &mut buf);
+                }
+            },
+            |err| eprintln!("[Audio] Probe stream error: {:?}", err),
+            None,
+        )
+    }
+    
+    let stream_config = config.config();
+    let stream = match config.sample_format() {
+        cpal::SampleFormat::F32 => build::<f32>(device, &stream_config, samples),
+        cpal::SampleFormat::I16 => build::<i16>(device, &stream_config, samples),
+        cpal::SampleFormat::U16 => build::<u16>(device, &stream_config, samples),
+        _ => return Err("Unsupported sample format".to_string()),
+    };
+    stream.map_err(|e| format!("Failed to build stream: {:?}", e))
+}
+
+/// Per-channel downmix weights normalized to sum to 1. Falls back to an equal
+/// average when no weights are configured or they don't match the channel count.
+fn downmix_weights(configured: Option<&[f32]>, channels: usize) -> Vec<f32> {
+    if let Some(weights) = configured {
+        let sum: f32 = weights.iter().sum();
+        if weights.len() == channels && sum > 0.0 {
+            println!("[Audio] Mixing channels with weights {:?}", weights);
+            return weights.iter().map(|w| w / sum).collect();
+        }
+        eprintln!("[Audio] Channel weights {:?} don't match {} channels, averaging instead", weights, channels);
+    }
+    vec![1.0 / channels as f32; channels]
+}
+
+/// Looks for a 16kHz mono input config on the device, so capture needs no resampling
+fn find_native_16khz_config(device: &cpal::Device) -> Option<cpal::SupportedStreamConfig> {
+    let target = cpal::SampleRate(WHISPER_SAMPLE_RATE);
+    device.supported_input_configs()
+        .ok()?
+        .filter(|range| range.channels() == 1)
+        .filter(|range| matches!(
+            range.sample_format(),
+            cpal::SampleFormat::F32 | cpal::SampleFormat::I16 | cpal::SampleFormat::U16
+        ))
+        .find_map(|range| range.try_with_sample_rate(target))
+}
+
+/// Starts audio recording using the selected input device (or default if none selected)
+/// When `resume` is true the existing buffer is kept and new audio is appended to it.
+fn start_audio_recording(app: AppHandle, audio_ctx: SharedAudio, resume: bool) {
+    // Get the stop signal before spawning thread. A recording ended before capture got
+    // going (e.g. a push-to-talk tap) must stay stopped; stopping signals under this
+    // lock, so the check can't race the reset.
+    let recording_state = app.state::<Arc<RecordingState>>().inner().clone();
+    let signals = {
+        let ctx = audio_ctx.lock().unwrap();
+        recording_state.is_recording.load(Ordering::SeqCst).then(|| {
+            ctx.stop_signal.store(false, Ordering::SeqCst);
+            ctx.stream_stopped.store(false, Ordering::SeqCst);
+            (ctx.stop_signal.clone(), ctx.stream_stopped.clone())
+        })
+    };
+    let Some((stop_signal, stream_stopped)) = signals else {
+        println!("[Audio] Recording ended before capture started");
+        if !recording_state.is_processing.load(Ordering::SeqCst) {
+            hide_overlay(&app);
+        }
+        return;
+    };
+    
+    // Get the selected microphone from config
+    let selected_mic = load_selected_microphone(&app);
+
+    std::thread::spawn(move || {
+        // Declared first so it drops last, after the stream below
+        let _stopped_guard = StreamStoppedGuard(stream_stopped);
+
+        let device = match resolve_input_device(selected_mic.as_deref()) {
+            Some((d, fell_back)) => {
+                if fell_back {
+                    let _ = app.emit("selected_mic_unavailable", serde_json::json!({
+                        "selected": selected_mic,
+                        "fallback": d.name().ok(),
+                    }));
+                }
+                d
+            }
+            None => {
+                eprintln!("[Audio] No input device available");
+                let _ = app.emit("audio_error", "No input device available");
+                return;
+            }
+        };
+
+        println!("[Audio] Using input device: {}", device.name().unwrap_or_default());
+
+        // Prefer capturing 16kHz mono natively so the resample step can be skipped,
+        // unless a weighted channel mix needs the individual channels. Shared capture
+        // keeps the device's own format so other apps' streams aren't disturbed.
+        let channel_weights = load_channel_weights(&app);
+        let shared = load_shared_capture(&app);
+        let native = if channel_weights.is_some() || shared { None } else { find_native_16khz_config(&device) };
+        let config = match native {
+            Some(c) => {
+                println!("[Audio] Using native 16kHz mono input config: {:?}", c);
+                c
+            }
+            None => match device.default_input_config() {
+                Ok(c) => {
+                    println!("[Audio] Default input config: {:?}", c);
+                    c
+                }
+                Err(e) => {
+                    eprintln!("[Audio] Failed to get default input config: {:?}", e);
+                    let _ = app.emit("audio_error", format!("Failed to get input config: {:?}", e));
+                    return;
+                }
+            },
+        };
+
+        let sample_rate = config.sample_rate().0;
+        let channels = config.channels() as usize;
+        let weights = downmix_weights(channel_weights.as_deref(), channels);
+        
+        // Apply the requested buffer size if the device supports it
+        let mut stream_config = config.config();
+        if shared {
+            println!("[Audio] Shared capture: using the device's default format and buffer size");
+        } else if let Some(frames) = load_audio_buffer_frames(&app) {
+            match config.buffer_size() {
+                cpal::SupportedBufferSize::Range { min, max } if (*min..=*max).contains(&frames) => {
+                    stream_config.buffer_size = cpal::BufferSize::Fixed(frames);
+                    println!("[Audio] Using fixed buffer size: {} frames", frames);
+                }
+                supported => {
+                    println!("[Audio] Buffer size {} not supported ({:?}), using device default", frames, supported);
+                }
+            }
+        }
+
+        // Update sample rate in context and clear buffer (unless resuming a paused recording)
+        {
+            let mut ctx = audio_ctx.lock().unwrap();
+            if !resume {
+                ctx.buffer.clear();
+                if let Ok(mut levels) = app.state::<SharedLevelHistory>().lock() {
+                    levels.clear();
+                }
+                if let Some(stale) = ctx.disk.take() {
+                    stale.discard();
+                }
+                if load_record_to_disk(&app) {
+                    match DiskRecording::create(sample_rate) {
+                        Ok(disk) => ctx.disk = Some(disk),
+                        Err(e) => eprintln!("[Audio] {}, keeping the recording in memory", e),
+                    }
+                }
+            } else if ctx.sample_rate != sample_rate && (ctx.disk.is_some() || !ctx.buffer.is_empty()) {
+                // The device rate changed while paused. Audio held in memory is brought to
+                // the new rate; a recording file's header already has the old one.
+                let converted = match ctx.disk {
+                    Some(_) => Err("the recording file was started at the old rate".to_string()),
+                    None => resample(&ctx.buffer, ctx.sample_rate, sample_rate).map(|r| r.into_owned()),
+                };
+                match converted {
+                    Ok(buffer) => {
+                        println!("[Audio] Resampled paused audio from {}Hz to {}Hz", ctx.sample_rate, sample_rate);
+                        ctx.buffer = buffer;
+                    }
+                    Err(e) => {
+                        // Transcribe what was recorded before the pause instead of resuming
+                        eprintln!("[Audio] Can't resume at {}Hz after pausing at {}Hz: {}", sample_rate, ctx.sample_rate, e);
+                        let _ = app.emit("audio_error", format!("Microphone sample rate changed while paused: {}", e));
+                        drop(ctx);
+                        // Stop from another thread: stopping waits for this thread to finish
+                        let app_clone = app.clone();
+                        std::thread::spawn(move || end_recording_from_app(&app_clone));
+                        return;
+                    }
+                }
+            }
+            ctx.sample_rate = sample_rate;
+        }
+
+        let audio_ctx_clone = audio_ctx.clone();
+        let app_clone = app.clone();
+
+        // Counter for throttling audio_level events
+        let sample_count = Arc::new(Mutex::new(0usize));
+        let sample_count_clone = sample_count.clone();
+        
+        // Keeps short bangs from pegging the level meter
+        let meter = Arc::new(Mutex::new(LevelMeter::new(load_transient_rejection_ratio(&app))));
+        let meter_clone = meter.clone();
+        // Looked up here so the callback doesn't go through the app's state map
+        let levels_clone = app.state::<SharedLevelHistory>().inner().clone();
+        
+        // Watches callback timing for audio lost to system load
+        let dropouts = Arc::new(Mutex::new(DropoutDetector::default()));
+        let dropouts_clone = dropouts.clone();
+        
+        let gate_settings = load_noise_gate_settings(&app);
+        let gate = Arc::new(Mutex::new(gate_settings.enabled.then(|| NoiseGate::new(&gate_settings, sample_rate))));
+        let gate_clone = gate.clone();
+
+        let err_fn = |err| eprintln!("[Audio] Stream error: {:?}", err);
+
+        let stream = match config.sample_format() {
+            cpal::SampleFormat::F32 => {
+                device.build_input_stream(
+                    &stream_config,
+                    move |data: &[f32], info: &cpal::InputCallbackInfo| {
+                        let mut ctx = audio_ctx_clone.lock().unwrap();
+                        let start = ctx.buffer.len();
+                        
+                        // Convert to mono with the channel weights (an average by default)
+                        for frame in data.chunks(channels) {
+                            let sample: f32 = frame.iter().zip(&weights).map(|(s, w)| s * w).sum();
+                            ctx.buffer.push(sample);
+                        }
+                        if let Some(gate) = gate_clone.lock().unwrap().as_mut() {
+                            gate.process(&mut ctx.buffer[start..]);
+                        }
+
+                        // Throttle audio_level events: emit every ~2048 samples
+                        let mut count = sample_count_clone.lock().unwrap();
+                        *count += data.len() / channels;
+                        
+                        if *count >= 2048 {
+                            let rms = meter_clone.lock().unwrap().level(compute_rms(&ctx.buffer, 4096));
+                            // Normalize RMS to 0-1 range (typical speech is ~0.01-0.1 RMS)
+                            let normalized = (rms * 10.0).min(1.0);
+                            emit_audio_level(&app_clone, &levels_clone, normalized);
+                            *count = 0;
+                        }
+                        
+                        if let Some(warning) = dropouts_clone.lock().unwrap().observe(info, data.len() / channels, sample_rate) {
+                            eprintln!("[Audio] Dropout detected: ~{}ms of audio lost", warning.lost_ms);
+                            let _ = app_clone.emit("audio_dropout_warning", warning);
+                        }
+                    },
+                    err_fn,
+                    None,
+                )
+            }
+            cpal::SampleFormat::I16 => {
+                device.build_input_stream(
+                    &stream_config,
+                    move |data: &[i16], info: &cpal::InputCallbackInfo| {
+                        let mut ctx = audio_ctx_clone.lock().unwrap();
+                        let start = ctx.buffer.len();
+                        
+                        for frame in data.chunks(channels) {
+                            let sample: f32 = frame.iter()
+                                .zip(&weights)
+                                .map(|(s, w)| s.to_float_sample() * w)
+                                .sum();
+                            ctx.buffer.push(sample);
+                        }
+                        if let Some(gate) = gate_clone.lock().unwrap().as_mut() {
+                            gate.process(&mut ctx.buffer[start..]);
+                        }
+
+                        let mut count = sample_count_clone.lock().unwrap();
+                        *count += data.len() / channels;
+                        
+                        if *count >= 2048 {
+                            let rms = meter_clone.lock().unwrap().level(compute_rms(&ctx.buffer, 4096));
+                            let normalized = (rms * 10.0).min(1.0);
+                            emit_audio_level(&app_clone, &levels_clone, normalized);
+                            *count = 0;
+                        }
+                        
+                        if let Some(warning) = dropouts_clone.lock().unwrap().observe(info, data.len() / channels, sample_rate) {
+                            eprintln!("[Audio] Dropout detected: ~{}ms of audio lost", warning.lost_ms);
+                            let _ = app_clone.emit("audio_dropout_warning", warning);
+                        }
+                    },
+                    err_fn,
+                    None,
+                )
+            }
+            cpal::SampleFormat::U16 => {
+                device.build_input_stream(
+                    &stream_config,
+                    move |data: &[u16], info: &cpal::InputCallbackInfo| {
+                        let mut ctx = audio_ctx_clone.lock().unwrap();
+                        let start = ctx.buffer.len();
+                        
+                        for frame in data.chunks(channels) {
+                            let sample: f32 = frame.iter()
+                                .zip(&weights)
+                                .map(|(s, w)| s.to_float_sample() * w)
+                                .sum();
+                            ctx.buffer.push(sample);
+                        }
+                        if let Some(gate) = gate_clone.lock().unwrap().as_mut() {
+                            gate.process(&mut ctx.buffer[start..]);
+                        }
+
+                        let mut count = sample_count_clone.lock().unwrap();
+                        *count += data.len() / channels;
+                        
+                        if *count >= 2048 {
+                            let rms = meter_clone.lock().unwrap().level(compute_rms(&ctx.buffer, 4096));
+                            let normalized = (rms * 10.0).min(1.0);
+                            emit_audio_level(&app_clone, &levels_clone, normalized);
+                            *count = 0;
+                        }
+                        
+                        if let Some(warning) = dropouts_clone.lock().unwrap().observe(info, data.len() / channels, sample_rate) {
+                            eprintln!("[Audio] Dropout detected: ~{}ms of audio lost", warning.lost_ms);
+                            let _ = app_clone.emit("audio_dropout_warning", warning);
+                        }
+                    },
+                    err_fn,
+                    None,
+                )
+            }
+            _ => {
+                eprintln!("[Audio] Unsupported sample format");
+                let _ = app.emit("audio_error", "Unsupported sample format");
+                return;
+            }
+        };
+
+        match stream {
+            Ok(s) => {
+                if let Err(e) = s.play() {
+                    eprintln!("[Audio] Failed to start stream: {:?}", e);
+                    let _ = app.emit("audio_error", format!("Failed to start stream: {:?}", e));
+                    return;
+                }
+                
+                println!("[Audio] Recording started");
+                
+                // In continuous dictation, end each utterance on trailing silence
+                let continuous = app.state::<Arc<RecordingState>>().continuous.load(Ordering::SeqCst);
+                let mut endpointer = continuous.then(|| {
+                    let speech_rms = load_vad_settings(&app).threshold.unwrap_or(ENDPOINT_SPEECH_RMS);
+                    Endpointer::new(speech_rms, vad_detector(&app))
+                });
+                
+                // A forgotten recording is stopped and transcribed once it hits the limit.
+                // The limit guards memory, so it doesn't apply when recording to disk.
+                let to_disk = audio_ctx.lock().unwrap().disk.is_some();
+                let max_samples = load_max_recording_seconds(&app)
+                    .filter(|_| !to_disk)
+                    .map(|seconds| (seconds as usize).saturating_mul(sample_rate as usize));
+                let mut limit_reached = false;
+                
+                // Keep the stream alive until stop signal is set
+                // The stream is kept in this thread (not shared) to avoid Send/Sync issues
+                while !stop_signal.load(Ordering::SeqCst) {
+                    std::thread::sleep(std::time::Duration::from_millis(ENDPOINT_POLL_MS));
+                    spill_to_disk(&audio_ctx);
+                    let captured = {
+                        let ctx = audio_ctx.lock().unwrap();
+                        ctx.buffer.len() + ctx.disk.as_ref().map_or(0, |d| d.samples_written)
+                    };
+                    
+                    if let Some(max) = max_samples.filter(|&max| !limit_reached && captured >= max) {
+                        limit_reached = true;
+                        let seconds = max / sample_rate as usize;
+                        println!("[Audio] Reached the {}s recording limit, stopping", seconds);
+                        let _ = app.emit("recording_limit_reached", seconds);
+                        // Stop from another thread: stopping waits for this loop to exit
+                        endpointer = None;
+                        let app_clone = app.clone();
+                        std::thread::spawn(move || end_recording_from_app(&app_clone));
+                    }
+                    
+                    let Some(ep) = endpointer.as_mut() else { continue };
+                    let level = {
+                        let ctx = audio_ctx.lock().unwrap();
+                        compute_rms(&ctx.buffer, (sample_rate as u64 * ENDPOINT_POLL_MS / 1000) as usize)
+                    };
+                    match ep.update(level) {
+                        Some(EndpointEvent::UtteranceEnd) => {
+                            println!("[Audio] Utterance ended on silence ({} detector)", ep.detector.kind);
+                            let _ = app.emit("utterance_ended", &ep.detector);
+                            // Stop from another thread: stopping waits for this loop to exit
+                            endpointer = None;
+                            let app_clone = app.clone();
+                            std::thread::spawn(move || end_recording_from_app(&app_clone));
+                        }
+                        Some(EndpointEvent::Idle) => {
+                            println!("[Audio] No speech for {}s, ending continuous dictation", CONTINUOUS_IDLE_TIMEOUT_MS / 1000);
+                            recording_state.continuous.store(false, Ordering::SeqCst);
+                            let _ = app.emit("continuous_dictation_ended", "idle");
+                            // Nothing was said, so the silence is discarded rather than transcribed.
+                            // Cancelling only signals this loop and waits on its own thread.
+                            endpointer = None;
+                            cancel_recording(&app, &recording_state, &audio_ctx);
+                        }
+                        None => {}
+                    }
+                }
+                
+                // Stream is dropped here when we exit the loop
+                let detector = dropouts.lock().unwrap();
+                if detector.dropouts > 0 {
+                    println!("[Audio] {} dropouts this recording, ~{}ms of audio lost", detector.dropouts, detector.total_lost_ms.round());
+                }
+                println!("[Audio] Stream stopped");
+            }
+            Err(e) => {
+                eprintln!("[Audio] Failed to build input stream: {:?}", e);
+                let _ = app.emit("audio_error", format!("Failed to build stream: {:?}", e));
+            }
+        }
+    });
+}
+
+/// Length of a start/stop cue tone
+const CUE_DURATION_MS: u64 = 80;
+
+/// Cue tone pitches: rising into recording, lower when it stops
+const CUE_START_HZ: f32 = 880.0;
+const CUE_STOP_HZ: f32 = 660.0;
+
+/// Cue volume, kept low so it doesn't startle
+const CUE_VOLUME: f32 = 0.2;
+
+/// Builds an output stream that plays one short sine tone, then silence
+fn build_cue_stream(
+    device: &cpal::Device,
+    config: &cpal::SupportedStreamConfig,
+    frequency_hz: f32,
+) -> Result<cpal::Stream, String> {
+    fn build<T>(
+        device: &cpal::Device,
+        config: &cpal::StreamConfig,
+        frequency_hz: f32,
+    ) -> Result<cpal::Stream, cpal::BuildStreamError>
+    where
+        T: cpal::SizedSample + cpal::FromSample<f32>,
+    {
+        let channels = config.channels as usize;
+        let rate = config.sample_rate.0 as f32;
+        let total = (rate * CUE_DURATION_MS as f32 / 1000.0) as usize;
+        let fade = rate * 0.005;  // 5ms ramps so the tone doesn't click
+        let mut n = 0usize;
+        device.build_output_stream(
+            config,
+            move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
+                for frame in data.chunks_mut(channels) {
+                    let value = if n < total {
+                        let envelope = (n.min(total - n) as f32 / fade).min(1.0);
+                        (2.0 * std::f32::consts::PI * frequency_hz * n as f32 / rate).sin() * CUE_VOLUME * envelope
+                    } else {
+                        0.0
+                    };
+                    n += 1;
+                    for sample in frame.iter_mut() {
+                        *sample = T::from_sample(value);
+                    }
+                }
+            },
+            |err| eprintln!("[Audio] Cue stream error: {:?}", err),
+            None,
+        )
+    }
+    
+    let stream_config = config.config();
+    let stream = match config.sample_format() {
+        cpal::SampleFormat::F32 => build::<f32>(device, &stream_config, frequency_hz),
+        cpal::SampleFormat::I16 => build::<i16>(device, &stream_config, frequency_hz),
+        cpal::SampleFormat::U16 => build::<u16>(device, &stream_config, frequency_hz),
+        _ => return Err("Unsupported sample format".to_string()),
+    };
+    stream.map_err(|e| format!("Failed to build stream: {:?}", e))
+}
+
+/// Plays a short cue tone on the configured cue output device (or the default
+/// output) if audio cues are on. Runs in the background; join the returned handle
+/// to wait until the tone has finished.
+fn play_cue(app: &AppHandle, frequency_hz: f32) -> Option<std::thread::JoinHandle<()>> {
+    if !load_audio_cues(app) {
+        return None;
+    }
+    let selected = load_cue_output_device(app);
+    Some(std::thread::spawn(move || {
+        let result = (|| {
+            let device = selected.as_deref()
+                .and_then(|id| {
+                    enumerate_output_devices().ok()?
+                        .into_iter()
+                        .find(|(device_id, _)| device_id == id)
+                        .map(|(_, device)| device)
+                })
+                .or_else(|| cpal::default_host().default_output_device())
+                .ok_or("No output device available")?;
+            let config = device.default_output_config()
+                .map_err(|e| format!("Failed to get output config: {:?}", e))?;
+            let stream = build_cue_stream(&device, &config, frequency_hz)?;
+            stream.play().map_err(|e| format!("Failed to start stream: {:?}", e))?;
+            std::thread::sleep(std::time::Duration::from_millis(CUE_DURATION_MS + 50));
+            Ok::<(), String>(())
+        })();
+        if let Err(e) = result {
+            eprintln!("[Audio] Failed to play cue: {}", e);
+        }
+    }))
+}
+
+/// Longest run of words matched when stitching overlapping chunk transcripts
+const OVERLAP_MATCH_MAX_WORDS: usize = 10;
+
+/// How far back into the previous transcript an overlap match is searched, in words
+const OVERLAP_SEARCH_WORDS: usize = 20;
+
+/// Finds where the transcript of an overlapping chunk picks up the text so far: the
+/// words `next` opens with are looked for near the end of `previous`. Returns the
+/// byte offset in `previous` where they start.
+fn find_text_overlap(previous: &str, next: &str) -> Option<usize> {
+    let normalize = |word: &str| -> String {
+        word.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+    };
+    let previous_words: Vec<(usize, String)> = previous.split_whitespace()
+        .map(|w| (w.as_ptr() as usize - previous.as_ptr() as usize, normalize(w)))
+        .collect();
+    let next_words: Vec<String> = next.split_whitespace().map(normalize).collect();
+    
+    let search_from = previous_words.len().saturating_sub(OVERLAP_SEARCH_WORDS);
+    for k in (2..=OVERLAP_MATCH_MAX_WORDS.min(next_words.len())).rev() {
+        let head = &next_words[..k];
+        if head.iter().any(|w| w.is_empty()) {
+            continue;
+        }
+        let found = (search_from..(previous_words.len() + 1).saturating_sub(k))
+            .find(|&i| previous_words[i..i + k].iter().map(|(_, w)| w).eq(head.iter()));
+        if let Some(i) = found {
+            return Some(previous_words[i].0);
+        }
+    }
+    None
+}
+
+/// Appends the transcript of an overlapping chunk to the text so far. Where the two
+/// overlap (see `find_text_overlap`) the previous text is cut so the overlap isn't
+/// repeated; otherwise they're joined with `separator`.
+fn merge_overlapping_text(previous: &str, next: &str, separator: char) -> String {
+    if let Some(cut) = find_text_overlap(previous, next) {
+        return format!("{}{}", &previous[..cut], next);
+    }
+    match (previous.is_empty(), next.is_empty()) {
+        (true, _) => next.to_string(),
+        (_, true) => previous.to_string(),
+        _ => format!("{}{}{}", previous, separator, next),
+    }
+}
+
+/// Transcribes a recording spilled to disk piece by piece, so the whole session is
+/// never in memory at once. Consecutive chunks overlap by `DISK_CHUNK_OVERLAP_SECONDS`
+/// and their transcripts are stitched. Emits `transcription_progress` after each chunk.
+fn transcribe_wav_in_chunks(
+    app: &AppHandle,
+    path: &std::path::Path,
+    whisper_state: &SharedWhisper,
+    options: &DecodingOptions,
+    abort: &AbortToken,
+) -> Result<Transcription, String> {
+    let mut reader = hound::WavReader::open(path)
+        .map_err(|e| format!("Failed to open recording file: {:?}", e))?;
+    let sample_rate = reader.spec().sample_rate;
+    let chunk_len = DISK_CHUNK_SECONDS * sample_rate as usize;
+    let overlap_len = DISK_CHUNK_OVERLAP_SECONDS * sample_rate as usize;
+    let overlap_ms = (DISK_CHUNK_OVERLAP_SECONDS * 1000) as u64;
+    let total_chunks = (reader.len() as usize).div_ceil(chunk_len).max(1);
+    
+    let mut samples = reader.samples::<f32>();
+    let mut combined = Transcription {
+        text: String::new(),
+        segments: Vec::new(),
+        language: None,
+        confidence: None,
+        resample_ms: 0,
+        inference_ms: 0,
+    };
+    let mut confidences = Vec::new();
+    let mut previous_tail: Vec<f32> = Vec::new();
+    for chunk_index in 0..total_chunks {
+        if abort.is_aborted() {
+            return Err(TRANSCRIPTION_ABORTED.to_string());
+        }
+        let fresh: Vec<f32> = samples.by_ref()
+            .take(chunk_len)
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("Failed to read recording file: {:?}", e))?;
+        if fresh.is_empty() {
+            break;
+        }
+        // Lead in with the end of the previous chunk
+        let lead_in = previous_tail.len();
+        let mut chunk = std::mem::take(&mut previous_tail);
+        chunk.extend_from_slice(&fresh);
+        previous_tail = fresh[fresh.len().saturating_sub(overlap_len)..].to_vec();
+        
+        condition_audio(app, &mut chunk, sample_rate);
+        let filtered = apply_vad_filter(app, &chunk, sample_rate);
+        let audio = filtered.as_deref().unwrap_or(&chunk);
+        let transcription = run_whisper_on_buffer(audio, sample_rate, whisper_state, options, abort)
+            .or_else(|e| retry_with_fallback_model(app, whisper_state, audio, sample_rate, options, abort, e))
+            .and_then(|t| retry_blank_transcription(app, whisper_state, audio, sample_rate, options, abort, t))?;
+        
+        // Segments that start inside the lead-in were already covered by the previous chunk
+        let lead_in_ms = if lead_in > 0 { overlap_ms } else { 0 };
+        let offset_ms = (chunk_index * DISK_CHUNK_SECONDS * 1000) as u64 - lead_in_ms;
+        combined.segments.extend(transcription.segments.into_iter()
+            .filter(|segment| segment.start_ms >= lead_in_ms)
+            .map(|segment| TranscriptSegment {
+                start_ms: segment.start_ms + offset_ms,
+                end_ms: segment.end_ms + offset_ms,
+                ..segment
+            }));
+        if !is_blank_transcript(&transcription.text) {
+            let separator = if options.segment_separator == SegmentSeparator::Newline { '\n' } else { ' ' };
+            combined.text = merge_overlapping_text(&combined.text, &transcription.text, separator);
+        }
+        combined.language = combined.language.or(transcription.language);
+        confidences.extend(transcription.confidence);
+        combined.resample_ms += transcription.resample_ms;
+        combined.inference_ms += transcription.inference_ms;
+        
+        println!("[Whisper] Transcribed chunk {}/{}", chunk_index + 1, total_chunks);
+        let _ = app.emit("transcription_progress", serde_json::json!({
+            "chunk": chunk_index + 1,
+            "total_chunks": total_chunks,
+        }));
+    }
+    combined.confidence = (!confidences.is_empty())
+        .then(|| confidences.iter().sum::<f32>() / confidences.len() as f32);
+    Ok(combined)
+}
+
+/// Stops audio recording and runs Whisper transcription
+fn stop_audio_recording(
+    app: AppHandle, 
+    audio_ctx: SharedAudio, 
+    whisper_state: SharedWhisper,
+    recording_state: Arc<RecordingState>,
+) {
+    let stop_requested = std::time::Instant::now();
+    // Where the paste must land: the window remembered at recording start, or
+    // the one focused now that recording stops
+    let target_window = if load_paste_target(&app) == "remembered" {
+        Some(recording_state.start_window.load(Ordering::SeqCst)).filter(|&w| w != 0)
+    } else if load_paste_focus_check(&app) != "off" {
+        foreground_window()
+    } else {
+        None
+    };
+    
+    // Signal the recording thread to stop
+    {
+        let ctx = audio_ctx.lock().unwrap();
+        ctx.stop_signal.store(true, Ordering::SeqCst);
+    }
+    
+    // Mark as processing (transcription in progress)
+    recording_state.is_processing.store(true, Ordering::SeqCst);
+    let session = recording_state.session_id.fetch_add(1, Ordering::SeqCst) + 1;
+    
+    std::thread::spawn(move || {
+        // Wait for the stream to be dropped so no late callbacks land after the copy,
+        // and so the stop cue isn't recorded
+        wait_for_stream_stopped(&audio_ctx);
+        play_cue(&app, CUE_STOP_HZ);
+        
+        // Copy buffer and get sample rate
+        let (mut buffer, sample_rate, disk) = {
+            let mut ctx = audio_ctx.lock().unwrap();
+            let buf = ctx.buffer.clone();
+            let rate = ctx.sample_rate;
+            ctx.buffer.clear(); // Clear buffer for next recording
+            (buf, rate, ctx.disk.take())
+        };
+        
+        // A recording spilled to disk is transcribed from the file; only its tail is in memory
+        let mut total_samples = buffer.len();
+        let disk_path = disk.and_then(|disk| match disk.finish(&buffer) {
+            Ok((path, samples)) => {
+                total_samples = samples;
+                buffer = Vec::new();
+                Some(path)
+            }
+            Err(e) => {
+                eprintln!("[Audio] {}, transcribing only the audio still in memory", e);
+                None
+            }
+        });
+        let stop_ms = stop_requested.elapsed().as_millis() as u64;
+        match &disk_path {
+            Some(path) => retain_recording_file(&app, path),
+            None => retain_recording(&app, &buffer, sample_rate),
+        }
+        
+        let duration = total_samples as f32 / sample_rate as f32;
+        println!("[Audio] Recording stopped. Captured {} samples at {} Hz ({:.2} seconds)", 
+                 total_samples, sample_rate, duration);
+
+        // Emit recording stats unless turned off (or the audio is on disk)
+        if load_emit_recording_stats(&app) && disk_path.is_none() {
+            let peak = buffer.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+            let clipped = buffer.iter().filter(|s| s.abs() >= 0.99).count();
+            let clipping_percent = if buffer.is_empty() { 0.0 } else { clipped as f32 / buffer.len() as f32 * 100.0 };
+            let speech_samples: usize = detect_speech_regions(&buffer, sample_rate, &load_vad_settings(&app))
+                .iter()
+                .map(|r| r.len())
+                .sum();
+            let _ = app.emit("recording_complete", serde_json::json!({
+                "samples": buffer.len(),
+                "sample_rate": sample_rate,
+                "duration_seconds": duration,
+                "peak": peak,
+                "clipping_percent": clipping_percent,
+                "speech_seconds": speech_samples as f32 / sample_rate as f32
+            }));
+        }
+        
+        // Run Whisper transcription - emit to overlay window specifically
+        println!("[Transcription] Emitting transcription_started event");
+        emit_to_overlay(&app, "transcription_started", ());
+        // Also broadcast to all windows for the main app
+        let _ = app.emit("transcription_started", ());
+        
+        // Let the overlay render its transcribing state before inference takes the CPU
+        let pre_delay_ms = load_pre_transcription_delay_ms(&app);
+        if pre_delay_ms > 0 {
+            std::thread::sleep(std::time::Duration::from_millis(pre_delay_ms));
+        }
+        // Waiting for CPU load happens before the watchdog starts counting
+        defer_while_busy(&app);
+        
+        let decoding = load_decoding_options(&app);
+        let abort = AbortToken::default();
+        if let Ok(mut current) = recording_state.processing_abort.lock() {
+            *current = abort.clone();
+        }
+        let timed_out = start_processing_watchdog(&app, &recording_state, &whisper_state, session, &abort, duration);
+        let result = match &disk_path {
+            Some(path) => transcribe_wav_in_chunks(&app, path, &whisper_state, &decoding, &abort),
+            None => {
+                condition_audio(&app, &mut buffer, sample_rate);
+                let filtered = apply_vad_filter(&app, &buffer, sample_rate);
+                let audio = filtered.as_deref().unwrap_or(&buffer);
+                run_whisper_on_buffer(audio, sample_rate, &whisper_state, &decoding, &abort)
+                    .or_else(|e| retry_with_fallback_model(&app, &whisper_state, audio, sample_rate, &decoding, &abort, e))
+                    .and_then(|t| retry_blank_transcription(&app, &whisper_state, audio, sample_rate, &decoding, &abort, t))
+            }
+        }
+        .map(|mut transcription| {
+            transcription.text = post_process_transcript(&app, &transcription.text);
+            transcription
+        });
+        if result.is_ok() {
+            let model_path = whisper_state.lock().ok().and_then(|ws| ws.model_path.clone());
+            if let Some(path) = model_path {
+                record_model_use(&app, &model_id_for_path(&path));
+            }
+        }
+        
+        // A long recording only exists in its file: keep it unless it was transcribed
+        let timed_out = timed_out.swap(true, Ordering::SeqCst);
+        if let Some(path) = &disk_path {
+            match (&result, timed_out) {
+                (Ok(_), false) => {
+                    let _ = std::fs::remove_file(path);
+                }
+                (Err(e), _) => keep_recording_file(&app, path, e),
+                (Ok(_), true) => keep_recording_file(&app, path, TRANSCRIPTION_ABORTED),
+            }
+        }
+        
+        // The watchdog already recovered the app; a newer session may be running by now
+        if timed_out {
+            eprintln!("[Watchdog] Dropping result of timed out session {}", session);
+            return;
+        }
+        
+        match result {
+            Ok(transcription) => {
+                let text = transcription.text.clone();
+                let skip_punctuation_only = load_skip_punctuation_only(&app);
+                if text.is_empty() || (skip_punctuation_only && !has_alphanumeric_content(&text)) {
+                    let _ = app.emit("transcription_error", "No speech detected");
+                    // Hide overlay after a brief delay so user sees the error
+                    std::thread::sleep(std::time::Duration::from_millis(1500));
+                    hide_overlay(&app);
+                } else if text == "[BLANK_AUDIO]" {
+                    // Skip blank audio - don't paste anything
+                    println!("[Whisper] Blank audio detected, skipping paste");
+                    let _ = app.emit("transcription_error", "No speech detected");
+                    std::thread::sleep(std::time::Duration::from_millis(1500));
+                    hide_overlay(&app);
+                } else {
+                    // Spoken commands ("new line", "delete that", ...) become edits rather than text
+                    let commands = load_voice_commands(&app);
+                    let (text, delete_previous) = if commands.enabled {
+                        let result = apply_voice_commands(&commands, &text);
+                        (result.text, result.delete_previous)
+                    } else {
+                        (text, false)
+                    };
+                    
+                    let model_id = whisper_state.lock()
+                        .ok()
+                        .and_then(|ws| ws.model_path.as_deref().map(model_id_for_path));
+                    let payload = TranscriptionResult {
+                        version: 2,
+                        word_count: text.split_whitespace().count(),
+                        text: text.clone(),
+                        model_id,
+                        language: transcription.language.clone(),
+                        duration_seconds: duration,
+                        inference_ms: transcription.inference_ms,
+                        confidence: transcription.confidence,
+                        segment_languages: segment_languages(&transcription.segments),
+                    };
+                    if decoding.language == "auto" {
+                        if let Some(language) = &payload.language {
+                            println!("[Whisper] Detected language: {}", language);
+                            let _ = app.emit("language_detected", language);
+                        }
+                    }
+                    if payload.segment_languages.len() > 1 {
+                        println!("[Whisper] Multiple languages detected: {:?}", payload.segment_languages);
+                        let _ = app.emit("multiple_languages_detected", &payload.segment_languages);
+                    }
+                    let webhook = load_webhook_settings(&app);
+                    if !text.is_empty() {
+                        write_journal_entry(&app, &text);
+                    }
+                    
+                    // Copy to clipboard and paste, at most once per recording
+                    let paste_start = std::time::Instant::now();
+                    if recording_state.claim_paste(session) {
+                        if delete_previous {
+                            delete_last_paste(&app);
+                        }
+                        // A replacing webhook that fails falls back to paste so the transcript isn't lost
+                        let has_text = !text.is_empty();
+                        let delivered = has_text && webhook.is_enabled() && webhook.replace_paste
+                            && deliver_to_webhook(&app, &webhook, &payload);
+                        if has_text && !delivered {
+                            if let Err(e) = deliver_output(&app, &text, target_window) {
+                                eprintln!("[Clipboard/Paste] Error: {}", e);
+                                let _ = app.emit("paste_error", e);
+                            }
+                        }
+                        if has_text && webhook.is_enabled() && !webhook.replace_paste {
+                            let app_clone = app.clone();
+                            let payload = payload.clone();
+                            std::thread::spawn(move || {
+                                deliver_to_webhook(&app_clone, &webhook, &payload);
+                            });
+                        }
+                    } else {
+                        println!("[Paste] Session {} already pasted, skipping", session);
+                    }
+                    record_latency(&app, LatencyBreakdown {
+                        stop_ms,
+                        resample_ms: transcription.resample_ms,
+                        inference_ms: transcription.inference_ms,
+                        paste_ms: paste_start.elapsed().as_millis() as u64,
+                        total_ms: stop_requested.elapsed().as_millis() as u64,
+                    });
+                    // Emit transcription_done exactly once, whether or not the paste succeeded
+                    let _ = app.emit("transcription_done", &text);
+                    if text.is_empty() {
+                        // Only commands were spoken; nothing to keep
+                    } else if let Err(e) = append_history(&app, HistoryEntry {
+                        timestamp: unix_now(),
+                        text: text.clone(),
+                        model_id: payload.model_id.clone(),
+                        duration_seconds: duration,
+                    }) {
+                        eprintln!("[History] Failed to save transcript: {}", e);
+                    }
+                    let _ = app.emit("transcription_result", payload);
+                    // Hide overlay after transcription is done
+                    std::thread::sleep(std::time::Duration::from_millis(500));
+                    hide_overlay(&app);
+                }
+            }
+            Err(e) => {
+                eprintln!("[Whisper] Error: {}", e);
+                let _ = app.emit("transcription_error", e);
+                // Hide overlay after a brief delay so user sees the error
+                std::thread::sleep(std::time::Duration::from_millis(1500));
+                hide_overlay(&app);
+            }
+        }
+        
+        // Mark processing as complete
+        recording_state.is_processing.store(false, Ordering::SeqCst);
+        
+        // Start the next recording right away if one was queued during processing,
+        // or if continuous dictation is on
+        let queued = recording_state.start_queued.swap(false, Ordering::SeqCst);
+        let continuous = recording_state.continuous.load(Ordering::SeqCst);
+        if (queued || continuous) && !recording_state.is_recording.load(Ordering::SeqCst) {
+            let audio_ctx = app.state::<SharedAudio>().inner().clone();
+            if !begin_recording(&app, &recording_state, &audio_ctx, &whisper_state) && continuous {
+                recording_state.continuous.store(false, Ordering::SeqCst);
+                let _ = app.emit("continuous_dictation_ended", "no_model");
+            }
+        }
+    });
+}
+
+/// How long a timed-out transcription may keep the model locked after its abort
+/// before the model is reported as wedged
+const WATCHDOG_RELEASE_GRACE_MS: u64 = 5000;
+
+/// Watches a transcription and, if it runs past the configured timeout, aborts it and
+/// hides the overlay. The timeout applies per `DISK_CHUNK_SECONDS` of audio, so long
+/// recordings get proportionally longer to transcribe. `is_processing` is only cleared once the aborted run has released
+/// the model, so the next recording doesn't block on it; a run that doesn't let go in
+/// time (inference stuck outside its abort checks) is reported via `model_wedged`.
+/// The returned flag is set once either side finishes; whoever sets it first wins.
+fn start_processing_watchdog(
+    app: &AppHandle,
+    recording_state: &Arc<RecordingState>,
+    whisper_state: &SharedWhisper,
+    session: u64,
+    abort: &AbortToken,
+    audio_seconds: f32,
+) -> Arc<AtomicBool> {
+    let finished = Arc::new(AtomicBool::new(false));
+    let chunks = (audio_seconds / DISK_CHUNK_SECONDS as f32).ceil().max(1.0) as u64;
+    let timeout_seconds = load_processing_timeout_seconds(app).saturating_mul(chunks);
+    if timeout_seconds == 0 {
+        return finished;
+    }
+    
+    let app = app.clone();
+    let recording_state = recording_state.clone();
+    let whisper_state = whisper_state.clone();
+    let abort = abort.clone();
+    let watched = finished.clone();
+    std::thread::spawn(move || {
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(timeout_seconds);
+        while std::time::Instant::now() < deadline {
+            if watched.load(Ordering::SeqCst) {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        if watched.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        
+        eprintln!("[Watchdog] Session {} still transcribing after {}s, aborting", session, timeout_seconds);
+        abort.abort();
+        hide_overlay(&app);
+        
+        // Recovered only once the aborted run lets go of the model
+        let aborted_at = std::time::Instant::now();
+        let mut wedged = false;
+        while matches!(whisper_state.try_lock(), Err(std::sync::TryLockError::WouldBlock)) {
+            if !wedged && aborted_at.elapsed() >= std::time::Duration::from_millis(WATCHDOG_RELEASE_GRACE_MS) {
+                wedged = true;
+                eprintln!("[Watchdog] Session {} did not release the model after aborting", session);
+                let _ = app.emit("model_wedged", serde_json::json!({
+                    "session": session,
+                    "message": "The model is stuck. Restart winsper to reload it.",
+                }));
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        
+        recording_state.is_processing.store(false, Ordering::SeqCst);
+        println!("[Watchdog] Session {} released the model, recovered", session);
+        let _ = app.emit("transcription_timeout", serde_json::json!({
+            "session": session,
+            "timeout_seconds": timeout_seconds,
+            "wedged": wedged,
+        }));
+    });
+    finished
+}
+
+/// Attempts at appending to the journal before giving up, for files briefly locked
+/// by an editor or sync client
+const JOURNAL_WRITE_ATTEMPTS: u32 = 3;
+
+/// Wait between journal append attempts
+const JOURNAL_RETRY_MS: u64 = 500;
+
+/// Formats Unix seconds as `YYYY-MM-DD HH:MM:SS` in UTC
+fn format_utc_timestamp(secs: u64) -> String {
+    format!("{} {:02}:{:02}:{:02}", utc_date(secs), secs / 3600 % 24, secs / 60 % 60, secs % 60)
+}
+
+/// Formats a transcript as one journal line. Line breaks (e.g. from the newline
+/// segment separator) are folded into spaces so each entry stays on its own line.
+fn format_journal_entry(text: &str, timestamp: Option<u64>) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match timestamp {
+        Some(secs) => format!("[{} UTC] {}\n", format_utc_timestamp(secs), text),
+        None => format!("{}\n", text),
+    }
+}
+
+/// Appends a formatted entry to the journal file
+fn append_to_journal(path: &str, entry: &str) -> Result<(), String> {
+    use std::io::Write;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open journal file: {:?}", e))?;
+    file.write_all(entry.as_bytes())
+        .map_err(|e| format!("Failed to write journal file: {:?}", e))
+}
+
+/// A journal entry waiting to be written: (file path, formatted line)
+type JournalEntry = (String, String);
+
+/// Journal entries are handed to one writer thread so they land in dictation
+/// order, even while an earlier entry is still being retried
+pub struct JournalQueue(Mutex<std::sync::mpsc::Sender<JournalEntry>>);
+
+impl JournalQueue {
+    /// Starts the writer thread. Each entry is retried while the file is locked
+    /// elsewhere; failures are reported via `journal_error`.
+    fn start(app: AppHandle) -> Self {
+        let (sender, receiver) = std::sync::mpsc::channel::<JournalEntry>();
+        std::thread::spawn(move || {
+            for (path, entry) in receiver {
+                for attempt in 1..=JOURNAL_WRITE_ATTEMPTS {
+                    match append_to_journal(&path, &entry) {
+                        Ok(()) => break,
+                        Err(e) if attempt < JOURNAL_WRITE_ATTEMPTS => {
+                            eprintln!("[Journal] {}, retrying", e);
+                            std::thread::sleep(std::time::Duration::from_millis(JOURNAL_RETRY_MS));
+                        }
+                        Err(e) => {
+                            eprintln!("[Journal] {}, entry dropped", e);
+                            let _ = app.emit("journal_error", e);
+                        }
+                    }
+                }
+            }
+        });
+        Self(Mutex::new(sender))
+    }
+}
+
+/// Queues a transcript for the journal, timestamped now if timestamps are on
+fn write_journal_entry(app: &AppHandle, text: &str) {
+    let settings = load_journal_settings(app);
+    if !settings.is_enabled() {
+        return;
+    }
+    let entry = format_journal_entry(text, settings.timestamps.then(unix_now));
+    if let Ok(queue) = app.state::<JournalQueue>().0.lock() {
+        let _ = queue.send((settings.path, entry));
+    }
+}
+
+/// POSTs a transcript to the webhook as JSON
+fn post_to_webhook(settings: &WebhookSettings, payload: &TranscriptionResult) -> Result<(), String> {
+    let body = serde_json::to_string(payload)
+        .map_err(|e| format!("Failed to serialize transcript: {:?}", e))?;
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_millis(settings.timeout_ms))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {:?}", e))?;
+    
+    let response = tauri::async_runtime::block_on(
+        client.post(&settings.url)
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+    ).map_err(|e| format!("Webhook request failed: {:?}", e))?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Webhook returned {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Delivers a transcript to the webhook, returning whether it was accepted.
+/// Failures are reported via `webhook_error` rather than dropping the transcript silently.
+fn deliver_to_webhook(app: &AppHandle, settings: &WebhookSettings, payload: &TranscriptionResult) -> bool {
+    match post_to_webhook(settings, payload) {
+        Ok(()) => {
+            println!("[Webhook] Delivered transcript to {}", settings.url);
+            true
+        }
+        Err(e) => {
+            eprintln!("[Webhook] {}", e);
+            let _ = app.emit("webhook_error", e);
+            false
+        }
+    }
+}
+
+/// Days of history kept when no retention has been chosen
+const DEFAULT_HISTORY_RETENTION_DAYS: u32 = 30;
+
+/// One dictation in the transcription history
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: u64,  // Unix seconds
+    pub text: String,
+    pub model_id: Option<String>,
+    pub duration_seconds: f32,
+}
+
+/// A day's history file
+#[derive(Serialize)]
+pub struct HistoryArchive {
+    pub date: String,  // YYYY-MM-DD (UTC)
+    pub entries: usize,
+    pub size_bytes: u64,
+}
+
+/// Current time in Unix seconds
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Formats a Unix timestamp as a UTC `YYYY-MM-DD` date (civil-from-days conversion)
+fn utc_date(unix_secs: u64) -> String {
+    let z = (unix_secs / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Checks a date is in `YYYY-MM-DD` form, so it's safe to use as a file name
+fn is_history_date(date: &str) -> bool {
+    date.len() == 10 && date.char_indices().all(|(i, c)| {
+        if i == 4 || i == 7 { c == '-' } else { c.is_ascii_digit() }
+    })
+}
+
+/// Reads a day's history, skipping any lines that don't parse (e.g. a torn final write)
+fn read_history_file(path: &std::path::Path) -> Vec<HistoryEntry> {
+    std::fs::read_to_string(path)
+        .map(|content| content.lines().filter_map(|line| serde_json::from_str(line).ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Appends an entry to its day's history file, then prunes archives past the retention period.
+/// Does nothing unless history is turned on.
+fn append_history(app: &AppHandle, entry: HistoryEntry) -> Result<(), String> {
+    if !load_history_enabled(app) {
+        return Ok(());
+    }
+    let path = get_history_dir(app)?.join(format!("{}.jsonl", utc_date(entry.timestamp)));
+    let mut line = serde_json::to_string(&entry)
+        .map_err(|e| format!("Failed to serialize history entry: {:?}", e))?;
+    line.push('\n');
+    
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open history file: {:?}", e))?;
+    std::io::Write::write_all(&mut file, line.as_bytes())
+        .map_err(|e| format!("Failed to write history: {:?}", e))?;
+    
+    prune_history(app);
+    Ok(())
+}
+
+/// Deletes day archives older than the configured retention period (if any)
+fn prune_history(app: &AppHandle) {
+    let Some(days) = load_history_retention_days(app) else { return };
+    let Ok(history_dir) = get_history_dir(app) else { return };
+    let cutoff = utc_date(unix_now().saturating_sub(days as u64 * 86_400));
+    
+    for (date, path) in list_history_files(&history_dir) {
+        if date < cutoff {
+            match std::fs::remove_file(&path) {
+                Ok(()) => println!("[History] Pruned archive {}", date),
+                Err(e) => eprintln!("[History] Failed to prune {}: {:?}", date, e),
+            }
+        }
+    }
+}
+
+/// Lists the day archives in the history directory as (date, path), oldest first
+fn list_history_files(history_dir: &std::path::Path) -> Vec<(String, PathBuf)> {
+    let mut files: Vec<(String, PathBuf)> = std::fs::read_dir(history_dir)
+        .map(|entries| entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "jsonl"))
+            .filter_map(|p| {
+                let date = p.file_stem()?.to_string_lossy().to_string();
+                is_history_date(&date).then_some((date, p))
+            })
+            .collect())
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// The most recent recording, kept (bounded) so it can be re-transcribed
+pub struct RetainedAudio {
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+    pub expires_at: Option<std::time::Instant>,  // None = keep until replaced
+}
+
+pub type SharedRetainedAudio = Arc<Mutex<Option<RetainedAudio>>>;
+
+/// Share of physical memory in use at which the retained recording is dropped
+const MEMORY_PRESSURE_PERCENT: u64 = 90;
+
+/// Share of physical memory in use, in percent. None where the platform can't report it.
+fn memory_load_percent() -> Option<u64> {
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::System::SystemInformation::{GlobalMemoryStatusEx, MEMORYSTATUSEX};
+        let mut status: MEMORYSTATUSEX = unsafe { std::mem::zeroed() };
+        status.dwLength = std::mem::size_of::<MEMORYSTATUSEX>() as u32;
+        if unsafe { GlobalMemoryStatusEx(&mut status) } == 0 {
+            return None;
+        }
+        Some(status.dwMemoryLoad as u64)
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        let field_kb = |name: &str| meminfo.lines()
+            .find_map(|line| line.strip_prefix(name))
+            .and_then(|rest| rest.trim().trim_end_matches("kB").trim().parse::<u64>().ok());
+        let (total, available) = (field_kb("MemTotal:")?, field_kb("MemAvailable:")?);
+        (total > 0).then(|| 100 - available.min(total) * 100 / total)
+    }
+    #[cfg(not(any(windows, target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// Drops the retained recording once its timeout has passed or memory runs short.
+/// Checked whenever the retained audio is used and when a recording starts.
+fn drop_stale_retained_audio(slot: &mut Option<RetainedAudio>) {
+    let Some(retained) = slot.as_ref() else { return };
+    if retained.expires_at.is_some_and(|t| std::time::Instant::now() >= t) {
+        println!("[Audio] Retained recording expired");
+    } else if memory_load_percent().is_some_and(|load| load >= MEMORY_PRESSURE_PERCENT) {
+        println!("[Audio] Dropping retained recording, memory is short");
+    } else {
+        return;
+    }
+    *slot = None;
+}
+
+/// Keeps the tail of a recording for re-transcription, capped to the configured
+/// number of seconds and dropped once the retention timeout has passed
+fn retain_recording(app: &AppHandle, buffer: &[f32], sample_rate: u32) {
+    let settings = load_retention_settings(app);
+    let retained = app.state::<SharedRetainedAudio>().inner().clone();
+    
+    let max_samples = (settings.max_seconds as usize).saturating_mul(sample_rate as usize);
+    if max_samples == 0 || buffer.is_empty() {
+        if let Ok(mut slot) = retained.lock() {
+            *slot = None;
+        }
+        return;
+    }
+    
+    let start = buffer.len().saturating_sub(max_samples);
+    let expires_at = (settings.timeout_seconds > 0)
+        .then(|| std::time::Instant::now() + std::time::Duration::from_secs(settings.timeout_seconds));
+    if let Ok(mut slot) = retained.lock() {
+        *slot = Some(RetainedAudio {
+            samples: buffer[start..].to_vec(),
+            sample_rate,
+            expires_at,
+        });
+    }
+    if start > 0 {
+        println!("[Audio] Retained last {}s of {:.1}s recording", settings.max_seconds, buffer.len() as f32 / sample_rate as f32);
+    }
+}
+
+/// Retains the tail of a recording that was spilled to disk, read back from its file
+fn retain_recording_file(app: &AppHandle, path: &std::path::Path) {
+    let max_seconds = load_retention_settings(app).max_seconds;
+    let tail = (|| -> Result<(Vec<f32>, u32), String> {
+        let mut reader = hound::WavReader::open(path)
+            .map_err(|e| format!("Failed to open recording file: {:?}", e))?;
+        let sample_rate = reader.spec().sample_rate;
+        let max_samples = (max_seconds as u32).saturating_mul(sample_rate);
+        reader.seek(reader.len().saturating_sub(max_samples))
+            .map_err(|e| format!("Failed to seek recording file: {:?}", e))?;
+        let samples = reader.samples::<f32>()
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("Failed to read recording file: {:?}", e))?;
+        Ok((samples, sample_rate))
+    })();
+    match tail {
+        Ok((samples, sample_rate)) => retain_recording(app, &samples, sample_rate),
+        Err(e) => {
+            eprintln!("[Audio] Not retaining recording: {}", e);
+            retain_recording(app, &[], 0);
+        }
+    }
+}
+
+/// Leaves a spilled recording's file in place after its transcription failed, and
+/// emits `recording_saved_for_retry` with its path so it can be transcribed again
+fn keep_recording_file(app: &AppHandle, path: &std::path::Path, error: &str) {
+    eprintln!("[Audio] Kept recording at {} for retry ({})", path.display(), error);
+    let _ = app.emit("recording_saved_for_retry", serde_json::json!({
+        "path": path.to_string_lossy(),
+        "error": error,
+    }));
+}
+
+/// Number of recent audio levels kept for replay to a late-mounting overlay
+/// (a few seconds at one level per ~2048 samples)
+const LEVEL_HISTORY_LEN: usize = 128;
+
+/// Audio levels of the current recording, newest last
+pub type SharedLevelHistory = Arc<Mutex<std::collections::VecDeque<f32>>>;
+
+/// Stores an audio level for overlay replay and emits it as an `audio_level` event.
+/// Called from the audio callback, so the history is skipped rather than waited on.
+fn emit_audio_level(app: &AppHandle, levels: &SharedLevelHistory, level: f32) {
+    if let Ok(mut levels) = levels.try_lock() {
+        if levels.len() == LEVEL_HISTORY_LEN {
+            levels.pop_front();
+        }
+        levels.push_back(level);
+    }
+    let _ = app.emit("audio_level", level);
+}
+
+/// Number of recent latency breakdowns kept for `get_latency_history`
+const LATENCY_HISTORY_LEN: usize = 20;
+
+/// Recent latency breakdowns, newest last
+pub type SharedLatencyHistory = Arc<Mutex<std::collections::VecDeque<LatencyBreakdown>>>;
+
+/// Stores a latency breakdown and emits it as a `latency_breakdown` event
+fn record_latency(app: &AppHandle, breakdown: LatencyBreakdown) {
+    println!("[Latency] stop {}ms, resample {}ms, inference {}ms, paste {}ms, total {}ms",
+             breakdown.stop_ms, breakdown.resample_ms, breakdown.inference_ms,
+             breakdown.paste_ms, breakdown.total_ms);
+    let _ = app.emit("latency_breakdown", &breakdown);
+    if let Ok(mut history) = app.state::<SharedLatencyHistory>().lock() {
+        if history.len() == LATENCY_HISTORY_LEN {
+            history.pop_front();
+        }
+        history.push_back(breakdown);
+    }
+}
+
+/// Starts a recording: shows the overlay and begins audio capture.
+/// If no model is loaded, shows the "no model" overlay instead and returns false.
+fn begin_recording(
+    app: &AppHandle,
+    recording_state: &Arc<RecordingState>,
+    audio_ctx: &SharedAudio,
+    whisper_state: &SharedWhisper,
+) -> bool {
+    // Check if a model is loaded before starting recording
+    let model_loaded = whisper_state.lock()
+        .map(|ws| ws.ctx.is_some())
+        .unwrap_or(false);
+    
+    if !model_loaded {
+        println!("[Hotkey] No model loaded, cannot start recording");
+        
+        // In background mode (or after an autostart launch) the window stays hidden
+        // until now, so this is the moment to surface it for model setup
+        let autostarted = std::env::args().any(|arg| arg == "--minimized");
+        if autostarted || load_background_mode(app) {
+            show_main_window(app);
+            let _ = app.emit("no_model_selected", ());
+            return false;
+        }
+        
+        // Without an overlay there's nothing to show the message in
+        if overlay_window(app).is_none() {
+            let _ = app.emit("no_model_selected", ());
+            return false;
+        }
+        
+        // Show "no model" message and auto-hide
+        let app_clone = app.clone();
+        std::thread::spawn(move || {
+            show_overlay(&app_clone);
+            // Give React time to mount component and set up listeners
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            println!("[Hotkey] Emitting no_model_selected event");
+            let _ = app_clone.emit("no_model_selected", ());
+            std::thread::sleep(std::time::Duration::from_millis(2000));
+            hide_overlay(&app_clone);
+        });
+        return false;
+    }
+    
+    // The new recording is about to take memory, so let go of stale retained audio
+    if let Ok(mut slot) = app.state::<SharedRetainedAudio>().lock() {
+        drop_stale_retained_audio(&mut slot);
+    }
+    
+    // Start recording
+    recording_state.is_recording.store(true, Ordering::SeqCst);
+    recording_state.start_window.store(foreground_window().unwrap_or(0), Ordering::SeqCst);
+    println!("[Hotkey] Recording started");
+    let start_cue = play_cue(app, CUE_START_HZ);
+    
+    // Show overlay window first, then emit event after a delay
+    // so React has time to mount and set up event listeners
+    let app_clone = app.clone();
+    let audio_ctx_clone = audio_ctx.clone();
+    std::thread::spawn(move || {
+        show_overlay(&app_clone);
+        // Emit recording_started immediately so UI resets to recording state
+        println!("[Hotkey] Emitting recording_started event");
+        let _ = app_clone.emit("recording_started", ());
+        
+        // Start audio capture once the start cue is over, so it isn't recorded
+        if let Some(cue) = start_cue {
+            let _ = cue.join();
+        }
+        start_audio_recording(app_clone, audio_ctx_clone, false);
+    });
+    true
+}
+
+/// Stops the current recording and transcribes it
+/// (overlay will be hidden after transcription completes). Does nothing if
+/// another path already stopped it, so concurrent stops can't run twice.
+fn end_recording(
+    app: &AppHandle,
+    recording_state: &Arc<RecordingState>,
+    audio_ctx: &SharedAudio,
+    whisper_state: &SharedWhisper,
+) {
+    if !recording_state.is_recording.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    recording_state.is_paused.store(false, Ordering::SeqCst);
+    let _ = app.emit("recording_stopped", ());
+    println!("[Hotkey] Recording stopped");
+    
+    stop_audio_recording(
+        app.clone(),
+        audio_ctx.clone(),
+        whisper_state.clone(),
+        recording_state.clone(),
+    );
+}
+
+/// Push-to-talk presses released sooner than this are treated as accidental taps
+const PUSH_TO_TALK_MIN_HOLD_MS: u64 = 300;
+
+/// Starts a recording, or stops and transcribes the current one (the record hotkey)
+fn toggle_recording(
+    app: &AppHandle,
+    recording_state: &Arc<RecordingState>,
+    audio_ctx: &SharedAudio,
+    whisper_state: &SharedWhisper,
+) {
+    let currently_recording = recording_state.is_recording.load(Ordering::SeqCst);
+    let currently_processing = recording_state.is_processing.load(Ordering::SeqCst);
+
+    // Don't start a new recording if we're still processing the previous one;
+    // either queue it to start once processing finishes, or drop it
+    if currently_processing && !currently_recording {
+        if load_queue_during_processing(app) {
+            println!("[Hotkey] Still processing - queued next recording");
+            recording_state.start_queued.store(true, Ordering::SeqCst);
+            let _ = app.emit("recording_request", "queued");
+        } else {
+            println!("[Hotkey] Ignoring - still processing previous transcription");
+            let _ = app.emit("recording_request", "dropped");
+        }
+        return;
+    }
+
+    if !currently_recording {
+        begin_recording(app, recording_state, audio_ctx, whisper_state);
+    } else {
+        // Pressing the hotkey mid-utterance also ends continuous dictation
+        if recording_state.continuous.swap(false, Ordering::SeqCst) {
+            let _ = app.emit("continuous_dictation_ended", "hotkey");
+        }
+        end_recording(app, recording_state, audio_ctx, whisper_state);
+    }
+}
+
+/// Discards the current recording without transcribing it
+fn cancel_recording(app: &AppHandle, recording_state: &Arc<RecordingState>, audio_ctx: &SharedAudio) {
+    if !recording_state.is_recording.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    recording_state.is_paused.store(false, Ordering::SeqCst);
+    if recording_state.continuous.swap(false, Ordering::SeqCst) {
+        let _ = app.emit("continuous_dictation_ended", "cancel");
+    }
+    audio_ctx.lock().unwrap().stop_signal.store(true, Ordering::SeqCst);
+    
+    let app = app.clone();
+    let audio_ctx = audio_ctx.clone();
+    std::thread::spawn(move || {
+        wait_for_stream_stopped(&audio_ctx);
+        {
+            let mut ctx = audio_ctx.lock().unwrap();
+            ctx.buffer.clear();
+            if let Some(disk) = ctx.disk.take() {
+                disk.discard();
+            }
+        }
+        println!("[Hotkey] Recording cancelled");
+        let _ = app.emit("recording_cancelled", ());
+        hide_overlay(&app);
+    });
+}
+
+/// Loads the next downloaded model after the active one, wrapping around
+fn cycle_model(app: &AppHandle, whisper_state: &SharedWhisper) -> Result<(), String> {
+    let models_dir = get_models_dir(app)?;
+    let downloaded: Vec<(PresetModel, PathBuf)> = get_all_models(app).into_iter()
+        .map(|model| {
+            let path = models_dir.join(&model.filename);
+            (model, path)
+        })
+        .filter(|(model, path)| path.exists() && model_file_issue(path, &model.size).is_none())
+        .collect();
+    let active = whisper_state.lock().ok().and_then(|ws| ws.model_path.clone());
+    let next = active.as_ref()
+        .and_then(|active| downloaded.iter().position(|(_, path)| path == active))
+        .map_or(0, |i| (i + 1) % downloaded.len());
+    let (model, path) = downloaded.get(next).ok_or_else(|| "No downloaded models to switch to".to_string())?;
+    if active.as_ref() == Some(path) {
+        return Ok(());
+    }
+    
+    let (ctx, backend) = create_whisper_context(app, &path.to_string_lossy())?;
+    {
+        let mut ws = whisper_state.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+        ws.ctx = Some(ctx);
+        ws.model_path = Some(path.clone());
+        ws.backend = Some(backend);
+    }
+    save_selected_model(app, &model.id)?;
+    println!("[Hotkey] Switched to model {}", model.id);
+    warm_up_gpu(app, whisper_state);
+    let _ = app.emit("model_cycled", serde_json::json!({
+        "model_id": model.id,
+        "name": model.name,
+    }));
+    Ok(())
+}
+
+/// Runs the action bound to tapping Alt: "cancel" discards the current recording,
+/// "cycle_model" switches to the next downloaded model and "record" works like
+/// the record hotkey
+fn run_alt_action(
+    app: &AppHandle,
+    action: &str,
+    recording_state: &Arc<RecordingState>,
+    audio_ctx: &SharedAudio,
+    whisper_state: &SharedWhisper,
+) {
+    match action {
+        "cancel" => cancel_recording(app, recording_state, audio_ctx),
+        "cycle_model" => {
+            if recording_state.is_recording.load(Ordering::SeqCst) || recording_state.is_processing.load(Ordering::SeqCst) {
+                println!("[Hotkey] Not switching models mid-recording");
+                return;
+            }
+            // Loading a model takes seconds; keep it off the hook thread
+            let app = app.clone();
+            let whisper_state = whisper_state.clone();
+            std::thread::spawn(move || {
+                if let Err(e) = cycle_model(&app, &whisper_state) {
+                    eprintln!("[Hotkey] Failed to switch model: {}", e);
+                    let _ = app.emit("model_cycle_failed", e);
+                }
+            });
+        }
+        "record" => toggle_recording(app, recording_state, audio_ctx, whisper_state),
+        _ => {}
+    }
+}
+
+/// Stops the current recording using the app's managed state, for callers
+/// outside the hotkey listener (e.g. the audio thread)
+fn end_recording_from_app(app: &AppHandle) {
+    let recording_state = app.state::<Arc<RecordingState>>().inner().clone();
+    if !recording_state.is_recording.load(Ordering::SeqCst) {
+        return;
+    }
+    let audio_ctx = app.state::<SharedAudio>().inner().clone();
+    let whisper_state = app.state::<SharedWhisper>().inner().clone();
+    end_recording(app, &recording_state, &audio_ctx, &whisper_state);
+}
+
+/// Seconds between attempts to restart a keyboard listener that failed to start
+const HOTKEY_LISTENER_RETRY_SECS: u64 = 10;
+
+/// Last keyboard listener failure reason, if the listener couldn't start
+pub type SharedHotkeyStatus = Arc<Mutex<Option<String>>>;
+
+/// Platform-specific steps for fixing a keyboard listener that won't start
+fn hotkey_listener_remediation() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "Grant Winsper Accessibility and Input Monitoring access in System Settings > Privacy & Security, then restart the app."
+    } else if cfg!(target_os = "linux") {
+        "Make sure you're running an X11 session and that your user can read input devices (e.g. is in the 'input' group)."
+    } else {
+        "Check that security software isn't blocking keyboard hooks, then restart the app."
+    }
+}
+
+/// Keys that can be bound to the configurable hotkeys, by config name.
+/// Modifiers are left out since they're pressed constantly as part of other shortcuts.
+const BINDABLE_HOTKEYS: &[(&str, Key)] = &[
+    ("F1", Key::F1), ("F2", Key::F2), ("F3", Key::F3), ("F4", Key::F4),
+    ("F5", Key::F5), ("F6", Key::F6), ("F7", Key::F7), ("F8", Key::F8),
+    ("F9", Key::F9), ("F10", Key::F10), ("F11", Key::F11), ("F12", Key::F12),
+    ("Insert", Key::Insert), ("Pause", Key::Pause), ("ScrollLock", Key::ScrollLock),
+    ("PrintScreen", Key::PrintScreen), ("Home", Key::Home), ("End", Key::End),
+    ("PageUp", Key::PageUp), ("PageDown", Key::PageDown),
+];
+
+/// Looks up a bindable hotkey by its config name
+fn parse_hotkey(name: &str) -> Option<Key> {
+    BINDABLE_HOTKEYS.iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, key)| *key)
+}
+
+/// Known system actions on bindable keys. The listener only observes keys, so the
+/// system action still fires alongside winsper's. Best effort: other apps' global
+/// shortcuts can't be detected.
+fn hotkey_conflict(name: &str) -> Option<&'static str> {
+    let key = parse_hotkey(name)?;
+    match key {
+        Key::PrintScreen => Some("takes a screenshot or opens the OS snipping tool"),
+        Key::F1 => Some("opens help in most applications"),
+        Key::F5 => Some("refreshes browsers and file managers"),
+        Key::F11 if cfg!(target_os = "macos") => Some("shows the desktop in macOS"),
+        Key::F11 => Some("toggles full screen in browsers and many applications"),
+        Key::F12 if cfg!(target_os = "windows") => Some("breaks into an attached debugger and opens browser devtools"),
+        Key::F12 => Some("opens browser developer tools"),
+        Key::ScrollLock => Some("is often remapped by KVM switches and keyboard firmware"),
+        Key::Pause => Some("breaks running console programs"),
+        _ => None,
+    }
+}
+
+/// Emits `hotkey_conflict_warning` if a newly bound key also triggers a system action
+fn warn_hotkey_conflict(app: &AppHandle, name: Option<&str>, action: &str) {
+    let Some((name, reason)) = name.and_then(|n| Some((n, hotkey_conflict(n)?))) else { return };
+    println!("[Hotkey] {} bound to {} also {}", name, action, reason);
+    let _ = app.emit("hotkey_conflict_warning", serde_json::json!({
+        "hotkey": name,
+        "action": action,
+        "reason": reason,
+    }));
+}
+
+/// Configurable hotkeys, cached so the listener doesn't read config on every key event
+#[derive(Default)]
+pub struct HotkeyBindings {
+    pub settings: Option<Key>,    // Shows and focuses the main window
+    pub undo_paste: Option<Key>,  // Retracts the last paste
+    pub alt_action: String,       // Action run when Alt is tapped alone ("none" to ignore)
+    pub push_to_talk: bool,       // Record while the record key is held instead of toggling
+}
+
+impl HotkeyBindings {
+    /// Reads all bindings from config
+    fn load(app: &AppHandle) -> Self {
+        Self {
+            settings: load_settings_hotkey(app).as_deref().and_then(parse_hotkey),
+            undo_paste: load_undo_paste_hotkey(app).as_deref().and_then(parse_hotkey),
+            alt_action: load_alt_action(app),
+            push_to_talk: load_hotkey_mode(app) == "push_to_talk",
+        }
+    }
+    
+    /// Name of the action a key is bound to, if any
+    fn action_for(&self, key: Key) -> Option<&'static str> {
+        if self.settings == Some(key) {
+            Some("settings")
+        } else if self.undo_paste == Some(key) {
+            Some("undo_paste")
+        } else {
+            None
+        }
+    }
+}
+
+/// Parses a hotkey name for `action`, rejecting keys already bound to another action
+fn parse_hotkey_binding(bindings: &HotkeyBindings, name: Option<&str>, action: &str) -> Result<Option<Key>, String> {
+    let Some(name) = name else { return Ok(None) };
+    let key = parse_hotkey(name).ok_or_else(|| format!("Unsupported hotkey: {}", name))?;
+    match bindings.action_for(key) {
+        Some(other) if other != action => Err(format!("{} is already bound to {}", name, other)),
+        _ => Ok(Some(key)),
+    }
+}
+
+pub type SharedHotkeyBindings = Arc<Mutex<HotkeyBindings>>;
+
+/// Shows and focuses the main window
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Starts a background thread that listens for global keyboard events.
+/// Detects Right Ctrl key presses to toggle recording state.
+fn start_hotkey_listener(
+    app: AppHandle, 
+    recording_state: Arc<RecordingState>, 
+    audio_ctx: SharedAudio,
+    whisper_state: SharedWhisper,
+) {
+    std::thread::spawn(move || {
+        let mut attempt: u32 = 0;
+        loop {
+            attempt += 1;
+            let callback = {
+                let app = app.clone();
+                let recording_state = recording_state.clone();
+                let audio_ctx = audio_ctx.clone();
+                let whisper_state = whisper_state.clone();
+                let hotkey_status = app.state::<SharedHotkeyStatus>().inner().clone();
+                let bindings = app.state::<SharedHotkeyBindings>().inner().clone();
+                let mut alt_tapped = false;
+                // When the held recording started, and the stop cycle it belongs to
+                let mut push_to_talk_started: Option<(std::time::Instant, u64)> = None;
+                move |event: Event| {
+                    // Receiving any event means a retried listener is now working
+                    if let Ok(mut status) = hotkey_status.lock() {
+                        if status.take().is_some() {
+                            println!("[Hotkey] Keyboard listener recovered");
+                            let _ = app.emit("hotkey_listener_recovered", ());
+                        }
+                    }
+                    
+                    // Alt is part of countless shortcuts, so its action only runs when it is
+                    // tapped alone: released with no other key or click while it was held
+                    match event.event_type {
+                        EventType::KeyPress(key) if key != Key::Alt => alt_tapped = false,
+                        EventType::ButtonPress(_) => alt_tapped = false,
+                        EventType::KeyRelease(Key::Alt) if std::mem::take(&mut alt_tapped) => {
+                            let action = bindings.lock().map(|b| b.alt_action.clone()).unwrap_or_default();
+                            run_alt_action(&app, &action, &recording_state, &audio_ctx, &whisper_state);
+                        }
+                        // Push-to-talk: releasing the record key stops the recording it started,
+                        // unless that one already ended another way (limit, endpoint, UI)
+                        EventType::KeyRelease(Key::ControlRight) => {
+                            let still_recording = |session| recording_state.is_recording.load(Ordering::SeqCst)
+                                && recording_state.session_id.load(Ordering::SeqCst) == session;
+                            if let Some((started, _)) = push_to_talk_started.take().filter(|&(_, session)| still_recording(session)) {
+                                recording_state.continuous.store(false, Ordering::SeqCst);
+                                if started.elapsed() < std::time::Duration::from_millis(PUSH_TO_TALK_MIN_HOLD_MS) {
+                                    println!("[Hotkey] Push-to-talk tap too short, discarding");
+                                    cancel_recording(&app, &recording_state, &audio_ctx);
+                                } else {
+                                    end_recording(&app, &recording_state, &audio_ctx, &whisper_state);
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                    
+                    if let EventType::KeyPress(key) = event.event_type {
+                        match key {
+                            Key::ControlLeft => {
+                                // Emit hotkey event for testing UI (left ctrl doesn't trigger recording)
+                                let _ = app.emit("hotkey_event", "LeftCtrl");
+                            }
+                            Key::ControlRight => {
+                                // Emit hotkey event for testing UI
+                                let _ = app.emit("hotkey_event", "RightCtrl");
+                                let push_to_talk = bindings.lock().map(|b| b.push_to_talk).unwrap_or(false);
+                                if !push_to_talk {
+                                    toggle_recording(&app, &recording_state, &audio_ctx, &whisper_state);
+                                } else if recording_state.is_recording.load(Ordering::SeqCst) {
+                                    // Key repeat while held
+                                } else if recording_state.is_processing.load(Ordering::SeqCst) {
+                                    println!("[Hotkey] Ignoring - still processing previous transcription");
+                                    let _ = app.emit("recording_request", "dropped");
+                                } else if begin_recording(&app, &recording_state, &audio_ctx, &whisper_state) {
+                                    let session = recording_state.session_id.load(Ordering::SeqCst);
+                                    push_to_talk_started = Some((std::time::Instant::now(), session));
+                                }
+                            }
+                            Key::Alt => {
+                                // Emit hotkey event for testing UI
+                                // Note: rdev doesn't distinguish left/right Alt on all platforms
+                                let _ = app.emit("hotkey_event", "Alt");
+                                alt_tapped = true;
+                            }
+                            other => {
+                                let action = bindings.lock().ok().and_then(|b| b.action_for(other));
+                                match action {
+                                    Some("settings") => {
+                                        println!("[Hotkey] Settings hotkey pressed");
+                                        show_main_window(&app);
+                                    }
+                                    Some("undo_paste") => {
+                                        // Simulate from another thread; injecting keys inside the hook can stall it
+                                        let app = app.clone();
+                                        std::thread::spawn(move || undo_last_paste(&app));
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+
+            match listen(callback) {
+                Ok(()) => break,
+                Err(err) => {
+                    let reason = format!("{:?}", err);
+                    eprintln!("[Hotkey] Failed to start keyboard listener (attempt {}): {}", attempt, reason);
+                    if let Ok(mut status) = app.state::<SharedHotkeyStatus>().lock() {
+                        *status = Some(reason.clone());
+                    }
+                    let _ = app.emit("hotkey_listener_failed", serde_json::json!({
+                        "reason": reason,
+                        "attempt": attempt,
+                        "remediation": hotkey_listener_remediation(),
+                    }));
+                    std::thread::sleep(std::time::Duration::from_secs(HOTKEY_LISTENER_RETRY_SECS));
+                }
+            }
+        }
+    });
+}
+
+/// Default time a transcription may run before the watchdog aborts it
+const DEFAULT_PROCESSING_TIMEOUT_SECONDS: u64 = 120;
+
+/// How long quitting waits for an in-flight transcription to unwind
+const SHUTDOWN_GRACE_MS: u64 = 2000;
+
+/// Quits the app. Any in-flight transcription is aborted first and the model
+/// released, so inference is never killed midway while it holds the GPU.
+fn shutdown(app: &AppHandle) {
+    let recording_state = app.state::<Arc<RecordingState>>().inner().clone();
+    let whisper_state = app.state::<SharedWhisper>().inner().clone();
+    
+    if recording_state.is_processing.load(Ordering::SeqCst) {
+        println!("[Shutdown] Aborting in-flight transcription");
+        if let Ok(abort) = recording_state.processing_abort.lock() {
+            abort.abort();
+        }
+    }
+    
+    let deadline = std::time::Instant::now() + std::time::Duration::from_millis(SHUTDOWN_GRACE_MS);
+    loop {
+        match whisper_state.try_lock() {
+            Ok(mut ws) => {
+                ws.ctx = None;
+                break;
+            }
+            Err(std::sync::TryLockError::WouldBlock) if std::time::Instant::now() < deadline => {
+                std::thread::sleep(std::time::Duration::from_millis(50));
+            }
+            Err(_) => {
+                eprintln!("[Shutdown] Transcription did not stop in time, exiting anyway");
+                break;
+            }
+        }
+    }
+    
+    println!("[Shutdown] Exiting");
+    app.exit(0);
+}
+
+/// GPU backends compiled into this build
+fn compiled_gpu_backends() -> Vec<&'static str> {
+    let mut backends = Vec::new();
+    if cfg!(feature = "cuda") {
+        backends.push("cuda");
+    }
+    if cfg!(feature = "vulkan") {
+        backends.push("vulkan");
+    }
+    if cfg!(feature = "metal") {
+        backends.push("metal");
+    }
+    backends
+}
+
+/// Resolves the preferred backend against what this build supports.
+/// Falls back to the first compiled GPU backend (or CPU) and emits a warning on mismatch.
+fn resolve_gpu_backend(app: &AppHandle, preferred: &str) -> String {
+    let available = compiled_gpu_backends();
+    match preferred {
+        "cpu" => "cpu".to_string(),
+        "auto" => available.first().copied().unwrap_or("cpu").to_string(),
+        wanted if available.contains(&wanted) => wanted.to_string(),
+        wanted => {
+            let fallback = available.first().copied().unwrap_or("cpu");
+            eprintln!("[Whisper] Preferred backend '{}' not available in this build, using {}", wanted, fallback);
+            let _ = app.emit("gpu_backend_warning", serde_json::json!({
+                "preferred": wanted,
+                "selected": fallback,
+                "available": available,
+            }));
+            fallback.to_string()
+        }
+    }
+}
+
+/// Creates a Whisper context for a model file, applying the configured backend.
+/// Returns the context and the backend it was created with. If the GPU backend
+/// fails to initialize, the model is loaded on the CPU and `gpu_init_failed` is emitted.
+fn create_whisper_context(app: &AppHandle, path: &str) -> Result<(WhisperContext, String), String> {
+    let gpu = load_gpu_settings(app);
+    let backend = resolve_gpu_backend(app, &gpu.backend);
+    
+    let mut params = WhisperContextParameters::default();
+    params.use_gpu(backend != "cpu");
+    params.gpu_device(gpu.device);
+    
+    let ctx = match WhisperContext::new_with_params(path, params) {
+        Ok(ctx) => ctx,
+        Err(e) if backend != "cpu" => {
+            eprintln!("[Whisper] {} init failed ({:?}), loading on CPU", backend, e);
+            let _ = app.emit("gpu_init_failed", serde_json::json!({
+                "backend": backend,
+                "reason": format!("{:?}", e),
+            }));
+            let mut params = WhisperContextParameters::default();
+            params.use_gpu(false);
+            let ctx = WhisperContext::new_with_params(path, params)
+                .map_err(|e| format!("Failed to load Whisper model: {:?}", e))?;
+            println!("[Whisper] Context created with backend: cpu (fallback)");
+            return Ok((ctx, "cpu".to_string()));
+        }
+        Err(e) => return Err(format!("Failed to load Whisper model: {:?}", e)),
+    };
+    
+    println!("[Whisper] Context created with backend: {}", backend);
+    Ok((ctx, backend))
+}
+
+/// Runs a tiny inference on the loaded model to check GPU acceleration works,
+/// emitting the outcome as a `gpu_validation` event
+fn run_gpu_validation(app: &AppHandle, whisper_state: &SharedWhisper) -> serde_json::Value {
+    let preferred = load_gpu_settings(app).backend;
+    let backend = whisper_state.lock().ok().and_then(|ws| ws.backend.clone());
+    
+    let report = match backend.as_deref() {
+        None => serde_json::json!({ "status": "skipped", "reason": "No model loaded" }),
+        Some("cpu") if preferred != "cpu" => serde_json::json!({
+            "status": "fallback",
+            "backend": "cpu",
+            "reason": "No GPU backend available in this build",
+        }),
+        Some("cpu") => serde_json::json!({ "status": "skipped", "reason": "GPU disabled" }),
+        Some(backend) => {
+            // One second of silence is enough to exercise the full GPU path
+            let silence = vec![0.0f32; WHISPER_SAMPLE_RATE as usize];
+            let start = std::time::Instant::now();
+            match run_whisper_on_buffer(&silence, WHISPER_SAMPLE_RATE, whisper_state, &load_decoding_options(app), &AbortToken::default()) {
+                Ok(_) => serde_json::json!({
+                    "status": "ok",
+                    "backend": backend,
+                    "elapsed_ms": start.elapsed().as_millis() as u64,
+                }),
+                Err(e) => serde_json::json!({
+                    "status": "failed",
+                    "backend": backend,
+                    "reason": e,
+                }),
+            }
+        }
+    };
+    
+    println!("[GPU] Validation result: {}", report);
+    let _ = app.emit("gpu_validation", &report);
+    report
+}
+
+/// When GPU warmup is on and the loaded model runs on a GPU backend, runs the GPU
+/// validation inference in the background so the first dictation doesn't pay for
+/// kernel compilation and buffer allocation
+fn warm_up_gpu(app: &AppHandle, whisper_state: &SharedWhisper) {
+    if !load_gpu_warmup(app) {
+        return;
+    }
+    let backend = whisper_state.lock().ok().and_then(|ws| ws.backend.clone());
+    if backend.is_none_or(|b| b == "cpu") {
+        return;
+    }
+    
+    let app = app.clone();
+    let whisper_state = whisper_state.clone();
+    std::thread::spawn(move || {
+        let report = run_gpu_validation(&app, &whisper_state);
+        emit_gpu_warmup_result(&app, &report);
+    });
+}
+
+/// Reports a validation run as the GPU warmup, emitting `gpu_warmup_done` or
+/// `gpu_warmup_failed`. Runs that were skipped or fell back to CPU emit nothing.
+fn emit_gpu_warmup_result(app: &AppHandle, report: &serde_json::Value) {
+    let backend = report.get("backend").and_then(|b| b.as_str()).unwrap_or_default();
+    match report.get("status").and_then(|s| s.as_str()) {
+        Some("ok") => {
+            let elapsed_ms = report.get("elapsed_ms").and_then(|ms| ms.as_u64()).unwrap_or(0);
+            println!("[GPU] Warmup on {} finished in {}ms", backend, elapsed_ms);
+            let _ = app.emit("gpu_warmup_done", serde_json::json!({
+                "backend": backend,
+                "elapsed_ms": elapsed_ms,
+            }));
+        }
+        Some("failed") => {
+            let reason = report.get("reason").and_then(|r| r.as_str()).unwrap_or_default();
+            eprintln!("[GPU] Warmup on {} failed: {}", backend, reason);
+            let _ = app.emit("gpu_warmup_failed", reason);
+        }
+        _ => {}
+    }
+}
+
+/// Tauri command to set the active Whisper model
+#[tauri::command]
+fn set_active_model(app: AppHandle, path: String, state: tauri::State<SharedWhisper>) -> Result<String, String> {
+    println!("[Whisper] Loading model from: {}", path);
+    
+    let model_path = PathBuf::from(&path);
+    
+    if !model_path.exists() {
+        return Err(format!("Model file not found: {}", path));
+    }
+    
+    // Load the Whisper context
+    let (ctx, backend) = create_whisper_context(&app, &path)?;
+    
+    // Store in state
+    let mut ws = state.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+    ws.ctx = Some(ctx);
+    ws.model_path = Some(model_path.clone());
+    ws.backend = Some(backend);
+    
+    // Save the selection so the same file is auto-loaded on the next start
+    let _ = save_selected_model_path(&app, &model_path);
+    drop(ws);
+    
+    println!("[Whisper] Model loaded successfully");
+    warm_up_gpu(&app, &state);
+    
+    Ok(format!("Model loaded: {}", path))
+}
+
+/// Tauri command to get current model path
+#[tauri::command]
+fn get_active_model(state: tauri::State<SharedWhisper>) -> Option<String> {
+    let ws = state.lock().ok()?;
+    ws.model_path.as_ref().map(|p| p.to_string_lossy().to_string())
+}
+
+/// Get the models directory path
+fn get_models_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {:?}", e))?;
+    let models_dir = app_data_dir.join("models");
+    
+    // Create directory if it doesn't exist
+    if !models_dir.exists() {
+        std::fs::create_dir_all(&models_dir)
+            .map_err(|e| models_dir_not_writable(app, &models_dir, &format!("{:?}", e)))?;
+    }
+    
+    Ok(models_dir)
+}
+
+/// Reports an unwritable models directory to the frontend and builds a user-facing error
+fn models_dir_not_writable(app: &AppHandle, models_dir: &std::path::Path, reason: &str) -> String {
+    let path = models_dir.to_string_lossy().to_string();
+    eprintln!("[Models] Models directory not writable: {} ({})", path, reason);
+    let _ = app.emit("models_dir_not_writable", serde_json::json!({
+        "path": path,
+        "reason": reason,
+    }));
+    format!("The models folder {} is not writable. Check its permissions or choose a different data directory.", path)
+}
+
+/// Gets the models directory, first checking with a probe file that it can be written to.
+/// Read-only locations still work for loading, so only writers need this.
+fn get_writable_models_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let models_dir = get_models_dir(app)?;
+    let probe = models_dir.join(".write-test");
+    std::fs::write(&probe, b"winsper")
+        .map_err(|e| models_dir_not_writable(app, &models_dir, &format!("{:?}", e)))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(models_dir)
+}
+
+/// Get the history directory path (one JSON-lines file per day)
+fn get_history_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {:?}", e))?;
+    let history_dir = app_data_dir.join("history");
+    
+    // Create directory if it doesn't exist
+    if !history_dir.exists() {
+        std::fs::create_dir_all(&history_dir)
+            .map_err(|e| format!("Failed to create history directory: {:?}", e))?;
+    }
+    
+    Ok(history_dir)
+}
+
+/// Get the config file path
+fn get_config_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {:?}", e))?;
+    
+    // Create directory if it doesn't exist
+    if !app_data_dir.exists() {
+        std::fs::create_dir_all(&app_data_dir)
+            .map_err(|e| format!("Failed to create app data directory: {:?}", e))?;
+    }
+    
+    Ok(app_data_dir.join("config.json"))
+}
+
+/// Load the full config
+fn load_config(app: &AppHandle) -> serde_json::Value {
+    let config_path = match get_config_path(app) {
+        Ok(p) => p,
+        Err(_) => return serde_json::json!({}),
+    };
+    
+    if !config_path.exists() {
+        return serde_json::json!({});
+    }
+    
+    std::fs::read_to_string(&config_path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or(serde_json::json!({}))
+}
+
+/// Serializes config read-modify-write cycles, so two settings saved at once
+/// can't each write back a config missing the other's change
+static CONFIG_LOCK: Mutex<()> = Mutex::new(());
+
+/// Numbers temp files so concurrent saves never write to the same one
+static CONFIG_TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Takes the config lock. Hold it from `load_config` through `save_config` when
+/// changing settings, and release it before calling anything that saves config itself.
+fn lock_config() -> std::sync::MutexGuard<'static, ()> {
+    CONFIG_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Loads config, applies `change` and saves the result, holding the config lock
+/// throughout so no other change can land in between. The lock isn't re-entrant,
+/// so `change` must not call the `save_*` helpers.
+fn update_config<T>(
+    app: &AppHandle,
+    change: impl FnOnce(&mut serde_json::Value) -> Result<T, String>,
+) -> Result<T, String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    let result = change(&mut config)?;
+    save_config(app, &config)?;
+    Ok(result)
+}
+
+/// Changes the setting stored under `key` through `update_config`, starting from
+/// its default when it's missing or unreadable, like the `load_*` helpers do
+fn update_setting<S, T>(
+    app: &AppHandle,
+    key: &str,
+    change: impl FnOnce(&mut S) -> Result<T, String>,
+) -> Result<T, String>
+where
+    S: Serialize + serde::de::DeserializeOwned + Default,
+{
+    update_config(app, |config| {
+        let mut setting: S = config.get(key)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let result = change(&mut setting)?;
+        config[key] = serde_json::to_value(&setting)
+            .map_err(|e| format!("Failed to serialize {}: {:?}", key, e))?;
+        Ok(result)
+    })
+}
+
+/// Save the full config. Written to a temp file and renamed over the real one,
+/// so a crash mid-write can't leave a truncated config behind.
+fn save_config(app: &AppHandle, config: &serde_json::Value) -> Result<(), String> {
+    use std::io::Write;
+    
+    let config_path = get_config_path(app)?;
+    let contents = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize config: {:?}", e))?;
+    // Never replace a good config with one that doesn't read back the same
+    let round_trip: serde_json::Value = serde_json::from_str(&contents)
+        .map_err(|e| format!("Config failed to round-trip: {:?}", e))?;
+    if &round_trip != config {
+        return Err("Config failed to round-trip".to_string());
+    }
+    
+    let temp_path = config_path.with_extension(format!(
+        "json.{}-{}.tmp",
+        std::process::id(),
+        CONFIG_TEMP_COUNTER.fetch_add(1, Ordering::SeqCst),
+    ));
+    let mut file = std::fs::File::create(&temp_path)
+        .map_err(|e| format!("Failed to save config: {:?}", e))?;
+    file.write_all(contents.as_bytes())
+        .and_then(|_| file.sync_all())
+        .map_err(|e| format!("Failed to save config: {:?}", e))?;
+    drop(file);
+    std::fs::rename(&temp_path, &config_path)
+        .map_err(|e| format!("Failed to save config: {:?}", e))?;
+    Ok(())
+}
+
+/// Save the selected model ID to config
+fn save_selected_model(app: &AppHandle, model_id: &str) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["selected_model"] = serde_json::json!(model_id);
+    config["selected_model_path"] = serde_json::Value::Null;
+    save_config(app, &config)?;
+    println!("[Config] Saved selected model: {}", model_id);
+    Ok(())
+}
+
+/// Save a model selected by file path, so arbitrary model files are auto-loaded
+/// like presets. The id is kept alongside for profiles and history.
+fn save_selected_model_path(app: &AppHandle, path: &std::path::Path) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["selected_model"] = serde_json::json!(model_id_for_path(path));
+    config["selected_model_path"] = serde_json::json!(path.to_string_lossy());
+    save_config(app, &config)?;
+    println!("[Config] Saved selected model path: {}", path.display());
+    Ok(())
+}
+
+/// Load the selected model file path, if the model was selected by path
+fn load_selected_model_path(app: &AppHandle) -> Option<PathBuf> {
+    let config = load_config(app);
+    config.get("selected_model_path")
+        .and_then(|v| v.as_str())
+        .map(PathBuf::from)
+}
+
+/// Records that a model was just loaded or transcribed with
+fn record_model_use(app: &AppHandle, model_id: &str) {
+    let result = update_config(app, |config| {
+        if !config.get("model_last_used").is_some_and(|v| v.is_object()) {
+            config["model_last_used"] = serde_json::json!({});
+        }
+        config["model_last_used"][model_id] = serde_json::json!(unix_now());
+        Ok(())
+    });
+    if let Err(e) = result {
+        eprintln!("[Config] Failed to save model usage: {}", e);
+    }
+}
+
+/// Load when each model was last loaded or used, by model ID
+fn load_model_last_used(app: &AppHandle) -> std::collections::HashMap<String, u64> {
+    let config = load_config(app);
+    config.get("model_last_used")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Load the selected model ID from config
+fn load_selected_model(app: &AppHandle) -> Option<String> {
+    let config = load_config(app);
+    config.get("selected_model")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+/// Save the selected microphone to config
+fn save_selected_microphone(app: &AppHandle, device_name: Option<&str>) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["selected_microphone"] = match device_name {
+        Some(name) => serde_json::json!(name),
+        None => serde_json::Value::Null,
+    };
+    save_config(app, &config)?;
+    println!("[Config] Saved selected microphone: {:?}", device_name);
+    Ok(())
+}
+
+/// Save the output device cues play on (None = default output)
+fn save_cue_output_device(app: &AppHandle, device_id: Option<&str>) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["cue_output_device"] = match device_id {
+        Some(id) => serde_json::json!(id),
+        None => serde_json::Value::Null,
+    };
+    save_config(app, &config)?;
+    println!("[Config] Saved cue output device: {:?}", device_id);
+    Ok(())
+}
+
+/// Load the output device cues play on
+fn load_cue_output_device(app: &AppHandle) -> Option<String> {
+    let config = load_config(app);
+    config.get("cue_output_device")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+/// Save whether start/stop cue tones are played
+fn save_audio_cues(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["audio_cues"] = serde_json::json!(enabled);
+    save_config(app, &config)?;
+    println!("[Config] Saved audio cues: {}", enabled);
+    Ok(())
+}
+
+/// Load whether start/stop cue tones are played (off by default)
+fn load_audio_cues(app: &AppHandle) -> bool {
+    let config = load_config(app);
+    config.get("audio_cues")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Load the selected microphone from config
+fn load_selected_microphone(app: &AppHandle) -> Option<String> {
+    let config = load_config(app);
+    config.get("selected_microphone")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+/// Save the monitor the overlay should appear on (None = primary)
+fn save_overlay_monitor(app: &AppHandle, monitor_id: Option<&str>) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["overlay_monitor"] = match monitor_id {
+        Some(id) => serde_json::json!(id),
+        None => serde_json::Value::Null,
+    };
+    save_config(app, &config)?;
+    println!("[Config] Saved overlay monitor: {:?}", monitor_id);
+    Ok(())
+}
+
+/// Load the monitor the overlay should appear on
+fn load_overlay_monitor(app: &AppHandle) -> Option<String> {
+    let config = load_config(app);
+    config.get("overlay_monitor")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+/// Save the overlay fade animation duration in milliseconds
+fn save_overlay_animation_ms(app: &AppHandle, ms: u64) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["overlay_animation_ms"] = serde_json::json!(ms);
+    save_config(app, &config)?;
+    println!("[Config] Saved overlay animation duration: {}ms", ms);
+    Ok(())
+}
+
+/// Load the overlay fade animation duration in milliseconds
+fn load_overlay_animation_ms(app: &AppHandle) -> u64 {
+    let config = load_config(app);
+    config.get("overlay_animation_ms")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_OVERLAY_ANIMATION_MS)
+        .min(MAX_OVERLAY_ANIMATION_MS)
+}
+
+/// Save the overlay size and opacity
+fn save_overlay_appearance(app: &AppHandle, appearance: &OverlayAppearance) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["overlay_appearance"] = serde_json::to_value(appearance)
+        .map_err(|e| format!("Failed to serialize overlay appearance: {:?}", e))?;
+    save_config(app, &config)?;
+    println!("[Config] Saved overlay appearance: {}x{} at {:.0}%",
+             appearance.width, appearance.height, appearance.opacity * 100.0);
+    Ok(())
+}
+
+/// Load the overlay size and opacity
+fn load_overlay_appearance(app: &AppHandle) -> OverlayAppearance {
+    let config = load_config(app);
+    config.get("overlay_appearance")
+        .and_then(|v| serde_json::from_value::<OverlayAppearance>(v.clone()).ok())
+        .unwrap_or_default()
+        .clamped()
+}
+
+/// Load the imported custom models from config
+fn load_custom_models(app: &AppHandle) -> Vec<CustomModel> {
+    let config = load_config(app);
+    config.get("custom_models")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Change the imported custom models in one locked load-modify-save
+fn update_custom_models(app: &AppHandle, change: impl FnOnce(&mut Vec<CustomModel>)) -> Result<(), String> {
+    let count = update_setting(app, "custom_models", |models: &mut Vec<CustomModel>| {
+        change(models);
+        Ok(models.len())
+    })?;
+    println!("[Config] Saved {} custom models", count);
+    Ok(())
+}
+
+/// Load the per-channel downmix weights (None = average all channels)
+fn load_channel_weights(app: &AppHandle) -> Option<Vec<f32>> {
+    let config = load_config(app);
+    config.get("channel_weights")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+}
+
+/// Save the per-channel downmix weights
+fn save_channel_weights(app: &AppHandle, weights: Option<&[f32]>) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["channel_weights"] = match weights {
+        Some(w) => serde_json::json!(w),
+        None => serde_json::Value::Null,
+    };
+    save_config(app, &config)?;
+    println!("[Config] Saved channel weights: {:?}", weights);
+    Ok(())
+}
+
+/// Load the level meter's transient rejection ratio (0 = off)
+fn load_transient_rejection_ratio(app: &AppHandle) -> f32 {
+    let config = load_config(app);
+    config.get("transient_rejection_ratio")
+        .and_then(|v| v.as_f64())
+        .map(|v| v as f32)
+        .unwrap_or(DEFAULT_TRANSIENT_REJECTION_RATIO)
+}
+
+/// Save the level meter's transient rejection ratio (0 = off)
+fn save_transient_rejection_ratio(app: &AppHandle, ratio: f32) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["transient_rejection_ratio"] = serde_json::json!(ratio);
+    save_config(app, &config)?;
+    println!("[Config] Saved transient rejection ratio: {}", ratio);
+    Ok(())
+}
+
+/// Load the longest a recording may run before it is stopped (None = no limit).
+/// Recordings spilled to disk aren't limited.
+fn load_max_recording_seconds(app: &AppHandle) -> Option<u64> {
+    let config = load_config(app);
+    let seconds = config.get("max_recording_seconds")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_MAX_RECORDING_SECONDS);
+    (seconds > 0).then_some(seconds)
+}
+
+/// Save the longest a recording may run before it is stopped (0 = no limit)
+fn save_max_recording_seconds(app: &AppHandle, seconds: u64) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["max_recording_seconds"] = serde_json::json!(seconds);
+    save_config(app, &config)?;
+    println!("[Config] Saved max recording length: {}s", seconds);
+    Ok(())
+}
+
+/// Load whether capture sticks to the device's default format and buffer size
+fn load_shared_capture(app: &AppHandle) -> bool {
+    let config = load_config(app);
+    config.get("shared_capture")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Save whether capture sticks to the device's default format and buffer size
+fn save_shared_capture(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["shared_capture"] = serde_json::json!(enabled);
+    save_config(app, &config)?;
+    println!("[Config] Saved shared capture: {}", enabled);
+    Ok(())
+}
+
+/// Load the requested capture buffer size in frames (None = device default)
+fn load_audio_buffer_frames(app: &AppHandle) -> Option<u32> {
+    let config = load_config(app);
+    config.get("audio_buffer_frames")
+        .and_then(|v| v.as_u64())
+        .map(|v| v as u32)
+}
+
+/// Save the requested capture buffer size in frames
+fn save_audio_buffer_frames(app: &AppHandle, frames: Option<u32>) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["audio_buffer_frames"] = match frames {
+        Some(f) => serde_json::json!(f),
+        None => serde_json::Value::Null,
+    };
+    save_config(app, &config)?;
+    println!("[Config] Saved audio buffer size: {:?}", frames);
+    Ok(())
+}
+
+/// Config keys captured by a dictation profile. `decoding` carries the language and
+/// initial prompt along with the sampling parameters.
+const PROFILE_SETTING_KEYS: &[&str] = &[
+    "selected_model",
+    "number_formatting",
+    "replacement_rules",
+    "decoding",
+    "vad",
+    "voice_commands",
+    "output_mode",
+    "hotkey_mode",
+];
+
+/// Current shareable preset format; newer files are rejected on import
+const SHAREABLE_PRESET_VERSION: u32 = 1;
+
+/// Config keys a shareable preset carries: decoding and formatting only
+const SHAREABLE_SETTING_KEYS: &[&str] = &[
+    "decoding",
+    "vad",
+    "high_pass_hz",
+    "number_formatting",
+    "replacement_rules",
+    "voice_commands",
+    "trailing_phrases",
+    "sentence_split",
+];
+
+/// Checks a shareable preset setting parses as the type its key is read as
+fn validate_shareable_setting(key: &str, value: &serde_json::Value) -> Result<(), String> {
+    fn check<T: serde::de::DeserializeOwned>(value: &serde_json::Value) -> Result<(), String> {
+        serde_json::from_value::<T>(value.clone()).map(|_| ()).map_err(|e| e.to_string())
+    }
+    let result = match key {
+        "decoding" => check::<DecodingOptions>(value),
+        "vad" => check::<VadSettings>(value),
+        "high_pass_hz" => check::<Option<f32>>(value),
+        "number_formatting" => check::<NumberFormatting>(value),
+        "replacement_rules" => check::<Vec<ReplacementRule>>(value),
+        "voice_commands" => check::<VoiceCommandSettings>(value),
+        "trailing_phrases" => check::<TrailingPhraseSettings>(value),
+        "sentence_split" => check::<SentenceSplitSettings>(value),
+        _ => return Err(format!("Preset contains unsupported setting: {}", key)),
+    };
+    result.map_err(|e| format!("Invalid {} in preset: {}", key, e))
+}
+
+/// Load the saved dictation profiles from config
+fn load_profiles(app: &AppHandle) -> Vec<Profile> {
+    let config = load_config(app);
+    config.get("profiles")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Change the dictation profiles in one locked load-modify-save
+fn update_profiles(
+    app: &AppHandle,
+    change: impl FnOnce(&mut Vec<Profile>) -> Result<(), String>,
+) -> Result<(), String> {
+    let count = update_setting(app, "profiles", |profiles: &mut Vec<Profile>| {
+        change(profiles)?;
+        Ok(profiles.len())
+    })?;
+    println!("[Config] Saved {} profiles", count);
+    Ok(())
+}
+
+/// Load whether blank results on audio with speech are retried once
+fn load_blank_retry(app: &AppHandle) -> bool {
+    let config = load_config(app);
+    config.get("blank_retry")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Save whether blank results on audio with speech are retried once
+fn save_blank_retry(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["blank_retry"] = serde_json::json!(enabled);
+    save_config(app, &config)?;
+    println!("[Config] Saved blank retry: {}", enabled);
+    Ok(())
+}
+
+/// Load whether punctuation-only transcripts are treated as "no speech" (default true)
+fn load_skip_punctuation_only(app: &AppHandle) -> bool {
+    let config = load_config(app);
+    config.get("skip_punctuation_only")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+/// Save whether punctuation-only transcripts are treated as "no speech"
+fn save_skip_punctuation_only(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["skip_punctuation_only"] = serde_json::json!(enabled);
+    save_config(app, &config)?;
+    println!("[Config] Saved skip punctuation-only: {}", enabled);
+    Ok(())
+}
+
+/// Load whether a hotkey press during processing is queued rather than dropped
+fn load_queue_during_processing(app: &AppHandle) -> bool {
+    let config = load_config(app);
+    config.get("queue_during_processing")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Save whether a hotkey press during processing is queued rather than dropped
+fn save_queue_during_processing(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["queue_during_processing"] = serde_json::json!(enabled);
+    save_config(app, &config)?;
+    println!("[Config] Saved queue during processing: {}", enabled);
+    Ok(())
+}
+
+/// Load the retained-recording bounds from config
+fn load_retention_settings(app: &AppHandle) -> RetentionSettings {
+    let config = load_config(app);
+    config.get("retention")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Save the retained-recording bounds to config
+fn save_retention_settings(app: &AppHandle, settings: &RetentionSettings) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["retention"] = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize retention settings: {:?}", e))?;
+    save_config(app, &config)?;
+    println!("[Config] Saved retention: {}s, timeout {}s", settings.max_seconds, settings.timeout_seconds);
+    Ok(())
+}
+
+/// Load the decoding settings from config
+fn load_decoding_options(app: &AppHandle) -> DecodingOptions {
+    let config = load_config(app);
+    config.get("decoding")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Change the decoding settings in one locked load-modify-save
+fn update_decoding_options<T>(app: &AppHandle, change: impl FnOnce(&mut DecodingOptions) -> T) -> Result<T, String> {
+    let result = update_setting(app, "decoding", |options: &mut DecodingOptions| Ok(change(options)))?;
+    println!("[Config] Saved decoding options");
+    Ok(result)
+}
+
+/// Save whether the clipboard is restored after pasting
+fn save_preserve_clipboard(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["preserve_clipboard"] = serde_json::json!(enabled);
+    save_config(app, &config)?;
+    println!("[Config] Saved preserve clipboard: {}", enabled);
+    Ok(())
+}
+
+/// Load the voice command settings from config
+fn load_voice_commands(app: &AppHandle) -> VoiceCommandSettings {
+    let config = load_config(app);
+    config.get("voice_commands")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Save the voice command settings to config
+fn save_voice_commands(app: &AppHandle, settings: &VoiceCommandSettings) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["voice_commands"] = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize voice commands: {:?}", e))?;
+    save_config(app, &config)?;
+    println!("[Config] Saved voice commands");
+    Ok(())
+}
+
+/// Save how many days of history archives are kept (None = keep forever)
+fn save_history_retention_days(app: &AppHandle, days: Option<u32>) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["history_retention_days"] = match days {
+        Some(d) => serde_json::json!(d),
+        None => serde_json::Value::Null,
+    };
+    save_config(app, &config)?;
+    println!("[Config] Saved history retention: {:?} days", days);
+    Ok(())
+}
+
+/// Load how many days of history archives are kept (`DEFAULT_HISTORY_RETENTION_DAYS`
+/// until set; an explicit null keeps them forever)
+fn load_history_retention_days(app: &AppHandle) -> Option<u32> {
+    let config = load_config(app);
+    match config.get("history_retention_days") {
+        None => Some(DEFAULT_HISTORY_RETENTION_DAYS),
+        Some(v) => v.as_u64().map(|v| v as u32),
+    }
+}
+
+/// Save whether dictations are kept in the transcription history
+fn save_history_enabled(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["history_enabled"] = serde_json::json!(enabled);
+    save_config(app, &config)?;
+    println!("[Config] Saved history enabled: {}", enabled);
+    Ok(())
+}
+
+/// Load whether dictations are kept in the transcription history (off by default)
+fn load_history_enabled(app: &AppHandle) -> bool {
+    let config = load_config(app);
+    config.get("history_enabled")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Save whether pastes are verified, falling back to typing
+fn save_verify_paste(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["verify_paste"] = serde_json::json!(enabled);
+    save_config(app, &config)?;
+    println!("[Config] Saved verify paste: {}", enabled);
+    Ok(())
+}
+
+/// Load whether pastes are verified, falling back to typing
+fn load_verify_paste(app: &AppHandle) -> bool {
+    let config = load_config(app);
+    config.get("verify_paste")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Load the paste focus check mode: "off", "abort" or "refocus"
+fn load_paste_focus_check(app: &AppHandle) -> String {
+    let config = load_config(app);
+    config.get("paste_focus_check")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "off".to_string())
+}
+
+/// Save the paste focus check mode
+fn save_paste_focus_check(app: &AppHandle, mode: &str) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["paste_focus_check"] = serde_json::json!(mode);
+    save_config(app, &config)?;
+    println!("[Config] Saved paste focus check: {}", mode);
+    Ok(())
+}
+
+/// Load where pastes go: "active" (whatever has focus) or "remembered" (the
+/// window focused when recording started)
+fn load_paste_target(app: &AppHandle) -> String {
+    let config = load_config(app);
+    config.get("paste_target")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "active".to_string())
+}
+
+/// Save where pastes go
+fn save_paste_target(app: &AppHandle, target: &str) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["paste_target"] = serde_json::json!(target);
+    save_config(app, &config)?;
+    println!("[Config] Saved paste target: {}", target);
+    Ok(())
+}
+
+/// Load which downloaded model is auto-selected when none is saved: "accurate" or "fast"
+fn load_auto_model_preference(app: &AppHandle) -> String {
+    let config = load_config(app);
+    config.get("auto_model_preference")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "accurate".to_string())
+}
+
+/// Save which downloaded model is auto-selected when none is saved
+fn save_auto_model_preference(app: &AppHandle, preference: &str) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["auto_model_preference"] = serde_json::json!(preference);
+    save_config(app, &config)?;
+    println!("[Config] Saved auto model preference: {}", preference);
+    Ok(())
+}
+
+/// Save whether the `recording_complete` stats event is emitted
+fn save_emit_recording_stats(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["emit_recording_stats"] = serde_json::json!(enabled);
+    save_config(app, &config)?;
+    println!("[Config] Saved emit recording stats: {}", enabled);
+    Ok(())
+}
+
+/// Load whether the `recording_complete` stats event is emitted (on by default)
+fn load_emit_recording_stats(app: &AppHandle) -> bool {
+    let config = load_config(app);
+    config.get("emit_recording_stats")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+/// Save the hotkey that undoes the last paste (None = unbound)
+fn save_undo_paste_hotkey(app: &AppHandle, hotkey: Option<&str>) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["undo_paste_hotkey"] = match hotkey {
+        Some(name) => serde_json::json!(name),
+        None => serde_json::Value::Null,
+    };
+    save_config(app, &config)?;
+    println!("[Config] Saved undo paste hotkey: {:?}", hotkey);
+    Ok(())
+}
+
+/// Load the hotkey that undoes the last paste
+fn load_undo_paste_hotkey(app: &AppHandle) -> Option<String> {
+    let config = load_config(app);
+    config.get("undo_paste_hotkey")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+/// Save the last noise floor calibration
+fn save_noise_calibration(app: &AppHandle, calibration: &NoiseCalibration) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["noise_calibration"] = serde_json::to_value(calibration)
+        .map_err(|e| format!("Failed to serialize noise calibration: {:?}", e))?;
+    save_config(app, &config)
+}
+
+/// Load the last noise floor calibration
+fn load_noise_calibration(app: &AppHandle) -> Option<NoiseCalibration> {
+    let config = load_config(app);
+    config.get("noise_calibration")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+}
+
+/// Save the hotkey that opens the settings window (None = unbound)
+fn save_settings_hotkey(app: &AppHandle, hotkey: Option<&str>) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["settings_hotkey"] = match hotkey {
+        Some(name) => serde_json::json!(name),
+        None => serde_json::Value::Null,
+    };
+    save_config(app, &config)?;
+    println!("[Config] Saved settings hotkey: {:?}", hotkey);
+    Ok(())
+}
+
+/// Load the hotkey that opens the settings window
+fn load_settings_hotkey(app: &AppHandle) -> Option<String> {
+    let config = load_config(app);
+    config.get("settings_hotkey")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+/// Load how the record key works: "toggle" (press to start, press again to stop)
+/// or "push_to_talk" (record while held)
+fn load_hotkey_mode(app: &AppHandle) -> String {
+    let config = load_config(app);
+    config.get("hotkey_mode")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "toggle".to_string())
+}
+
+/// Save how the record key works
+fn save_hotkey_mode(app: &AppHandle, mode: &str) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["hotkey_mode"] = serde_json::json!(mode);
+    save_config(app, &config)?;
+    println!("[Config] Saved hotkey mode: {}", mode);
+    Ok(())
+}
+
+/// Load the wait between simulated keystrokes in type mode
+fn load_typing_delay_ms(app: &AppHandle) -> u64 {
+    let config = load_config(app);
+    config.get("typing_delay_ms")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_TYPING_KEY_DELAY_MS)
+        .min(MAX_TYPING_KEY_DELAY_MS)
+}
+
+/// Save the wait between simulated keystrokes in type mode
+fn save_typing_delay_ms(app: &AppHandle, ms: u64) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["typing_delay_ms"] = serde_json::json!(ms);
+    save_config(app, &config)?;
+    println!("[Config] Saved typing delay: {}ms", ms);
+    Ok(())
+}
+
+/// Load how transcripts are output: "paste", "copy_only" or "type"
+fn load_output_mode(app: &AppHandle) -> String {
+    let config = load_config(app);
+    config.get("output_mode")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "paste".to_string())
+}
+
+/// Save how transcripts are output
+fn save_output_mode(app: &AppHandle, mode: &str) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["output_mode"] = serde_json::json!(mode);
+    save_config(app, &config)?;
+    println!("[Config] Saved output mode: {}", mode);
+    Ok(())
+}
+
+/// Load the action run when Alt is tapped alone
+fn load_alt_action(app: &AppHandle) -> String {
+    let config = load_config(app);
+    config.get("alt_action")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "none".to_string())
+}
+
+/// Save the action run when Alt is tapped alone
+fn save_alt_action(app: &AppHandle, action: &str) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["alt_action"] = serde_json::json!(action);
+    save_config(app, &config)?;
+    println!("[Config] Saved Alt action: {}", action);
+    Ok(())
+}
+
+/// Save the model to retry with when transcription fails (None = no retry)
+fn save_fallback_model(app: &AppHandle, model_id: Option<&str>) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["fallback_model"] = match model_id {
+        Some(id) => serde_json::json!(id),
+        None => serde_json::Value::Null,
+    };
+    save_config(app, &config)?;
+    println!("[Config] Saved fallback model: {:?}", model_id);
+    Ok(())
+}
+
+/// Load the model to retry with when transcription fails
+fn load_fallback_model(app: &AppHandle) -> Option<String> {
+    let config = load_config(app);
+    config.get("fallback_model")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+/// Load whether the clipboard is restored after pasting
+fn load_preserve_clipboard(app: &AppHandle) -> bool {
+    let config = load_config(app);
+    config.get("preserve_clipboard")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+/// Save the delay before the original clipboard is restored
+fn save_clipboard_restore_delay_ms(app: &AppHandle, ms: u64) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["clipboard_restore_delay_ms"] = serde_json::json!(ms);
+    save_config(app, &config)?;
+    println!("[Config] Saved clipboard restore delay: {}ms", ms);
+    Ok(())
+}
+
+/// Load the delay before the original clipboard is restored
+fn load_clipboard_restore_delay_ms(app: &AppHandle) -> u64 {
+    let config = load_config(app);
+    config.get("clipboard_restore_delay_ms")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_CLIPBOARD_RESTORE_DELAY_MS)
+        .min(MAX_CLIPBOARD_RESTORE_DELAY_MS)
+}
+
+/// Save the pause between showing the transcribing state and starting inference
+fn save_pre_transcription_delay_ms(app: &AppHandle, ms: u64) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["pre_transcription_delay_ms"] = serde_json::json!(ms);
+    save_config(app, &config)?;
+    println!("[Config] Saved pre-transcription delay: {}ms", ms);
+    Ok(())
+}
+
+/// Load the pause between showing the transcribing state and starting inference
+fn load_pre_transcription_delay_ms(app: &AppHandle) -> u64 {
+    let config = load_config(app);
+    config.get("pre_transcription_delay_ms")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_PRE_TRANSCRIPTION_DELAY_MS)
+        .min(MAX_PRE_TRANSCRIPTION_DELAY_MS)
+}
+
+/// Load the defer-under-load settings from config
+fn load_load_defer_settings(app: &AppHandle) -> LoadDeferSettings {
+    let config = load_config(app);
+    config.get("defer_under_load")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Save the defer-under-load settings to config
+fn save_load_defer_settings(app: &AppHandle, settings: &LoadDeferSettings) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["defer_under_load"] = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize defer-under-load settings: {:?}", e))?;
+    save_config(app, &config)?;
+    println!("[Config] Saved defer-under-load settings");
+    Ok(())
+}
+
+/// Load the journal output settings from config
+fn load_journal_settings(app: &AppHandle) -> JournalSettings {
+    let config = load_config(app);
+    config.get("journal")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Save the journal output settings to config
+fn save_journal_settings(app: &AppHandle, settings: &JournalSettings) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["journal"] = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize journal settings: {:?}", e))?;
+    save_config(app, &config)?;
+    println!("[Config] Saved journal settings");
+    Ok(())
+}
+
+/// Load the webhook output settings from config
+fn load_webhook_settings(app: &AppHandle) -> WebhookSettings {
+    let config = load_config(app);
+    config.get("webhook")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Save the webhook output settings to config
+fn save_webhook_settings(app: &AppHandle, settings: &WebhookSettings) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["webhook"] = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize webhook settings: {:?}", e))?;
+    save_config(app, &config)?;
+    println!("[Config] Saved webhook settings");
+    Ok(())
+}
+
+/// Save the selected VAD model (None = no model)
+fn save_vad_model(app: &AppHandle, model_id: Option<&str>) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["vad_model"] = match model_id {
+        Some(id) => serde_json::json!(id),
+        None => serde_json::Value::Null,
+    };
+    save_config(app, &config)?;
+    println!("[Config] Saved VAD model: {:?}", model_id);
+    Ok(())
+}
+
+/// Load the selected VAD model from config
+fn load_vad_model(app: &AppHandle) -> Option<String> {
+    let config = load_config(app);
+    config.get("vad_model")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+/// Load the VAD filter settings from config
+fn load_vad_settings(app: &AppHandle) -> VadSettings {
+    let config = load_config(app);
+    config.get("vad")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Load whether the record hotkey surfaces the hidden main window when no model is loaded
+fn load_background_mode(app: &AppHandle) -> bool {
+    let config = load_config(app);
+    config.get("background_mode")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Save whether the record hotkey surfaces the hidden main window when no model is loaded
+fn save_background_mode(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["background_mode"] = serde_json::json!(enabled);
+    save_config(app, &config)?;
+    println!("[Config] Saved background mode: {}", enabled);
+    Ok(())
+}
+
+/// Load whether downloaded models are checked for corruption at startup
+fn load_model_integrity_scan(app: &AppHandle) -> bool {
+    let config = load_config(app);
+    config.get("model_integrity_scan")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+/// Load whether GPU models get a warmup inference right after loading
+fn load_gpu_warmup(app: &AppHandle) -> bool {
+    let config = load_config(app);
+    config.get("gpu_warmup")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Save whether GPU models get a warmup inference right after loading
+fn save_gpu_warmup(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["gpu_warmup"] = serde_json::json!(enabled);
+    save_config(app, &config)?;
+    println!("[Config] Saved GPU warmup: {}", enabled);
+    Ok(())
+}
+
+/// Save whether downloaded models are checked for corruption at startup
+fn save_model_integrity_scan(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["model_integrity_scan"] = serde_json::json!(enabled);
+    save_config(app, &config)?;
+    println!("[Config] Saved model integrity scan: {}", enabled);
+    Ok(())
+}
+
+/// Load how long a transcription may run before the watchdog aborts it (0 = never)
+fn load_processing_timeout_seconds(app: &AppHandle) -> u64 {
+    let config = load_config(app);
+    config.get("processing_timeout_seconds")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_PROCESSING_TIMEOUT_SECONDS)
+}
+
+/// Save how long a transcription may run before the watchdog aborts it (0 = never)
+fn save_processing_timeout_seconds(app: &AppHandle, seconds: u64) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["processing_timeout_seconds"] = serde_json::json!(seconds);
+    save_config(app, &config)?;
+    println!("[Config] Saved processing timeout: {}s", seconds);
+    Ok(())
+}
+
+/// Load the trailing phrase filter from config
+fn load_trailing_phrase_settings(app: &AppHandle) -> TrailingPhraseSettings {
+    let config = load_config(app);
+    config.get("trailing_phrases")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Save the trailing phrase filter to config
+fn save_trailing_phrase_settings(app: &AppHandle, settings: &TrailingPhraseSettings) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["trailing_phrases"] = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize trailing phrases: {:?}", e))?;
+    save_config(app, &config)?;
+    println!("[Config] Saved trailing phrase filter");
+    Ok(())
+}
+
+/// Load the per-sentence paste settings from config
+fn load_sentence_split_settings(app: &AppHandle) -> SentenceSplitSettings {
+    let config = load_config(app);
+    config.get("sentence_split")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Save the per-sentence paste settings to config
+fn save_sentence_split_settings(app: &AppHandle, settings: &SentenceSplitSettings) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["sentence_split"] = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize sentence split settings: {:?}", e))?;
+    save_config(app, &config)?;
+    println!("[Config] Saved sentence split: {} ({}ms)", settings.enabled, settings.delay_ms);
+    Ok(())
+}
+
+/// Load the noise gate settings from config
+fn load_noise_gate_settings(app: &AppHandle) -> NoiseGateSettings {
+    let config = load_config(app);
+    config.get("noise_gate")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Save the noise gate settings to config
+fn save_noise_gate_settings(app: &AppHandle, settings: &NoiseGateSettings) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["noise_gate"] = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize noise gate settings: {:?}", e))?;
+    save_config(app, &config)?;
+    println!("[Config] Saved noise gate settings");
+    Ok(())
+}
+
+/// Load the automatic gain control settings from config
+fn load_agc_settings(app: &AppHandle) -> AgcSettings {
+    let config = load_config(app);
+    config.get("agc")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Save the automatic gain control settings to config
+fn save_agc_settings(app: &AppHandle, settings: &AgcSettings) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["agc"] = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize AGC settings: {:?}", e))?;
+    save_config(app, &config)?;
+    println!("[Config] Saved AGC settings");
+    Ok(())
+}
+
+/// Load whether recordings are spilled to a WAV file on disk instead of held in memory
+fn load_record_to_disk(app: &AppHandle) -> bool {
+    let config = load_config(app);
+    config.get("record_to_disk")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Save whether recordings are spilled to a WAV file on disk
+fn save_record_to_disk(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["record_to_disk"] = serde_json::json!(enabled);
+    save_config(app, &config)?;
+    println!("[Config] Saved record to disk: {}", enabled);
+    Ok(())
+}
+
+/// Load the high-pass filter cutoff (None = filter off)
+fn load_high_pass_hz(app: &AppHandle) -> Option<f32> {
+    let config = load_config(app);
+    config.get("high_pass_hz")
+        .and_then(|v| v.as_f64())
+        .map(|v| v as f32)
+}
+
+/// Save the high-pass filter cutoff (None = filter off)
+fn save_high_pass_hz(app: &AppHandle, cutoff_hz: Option<f32>) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["high_pass_hz"] = match cutoff_hz {
+        Some(hz) => serde_json::json!(hz),
+        None => serde_json::Value::Null,
+    };
+    save_config(app, &config)?;
+    println!("[Config] Saved high-pass cutoff: {:?}", cutoff_hz);
+    Ok(())
+}
+
+/// Save the VAD filter settings to config
+fn save_vad_settings(app: &AppHandle, settings: &VadSettings) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["vad"] = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize VAD settings: {:?}", e))?;
+    save_config(app, &config)?;
+    println!("[Config] Saved VAD settings");
+    Ok(())
+}
+
+/// Load the find/replace rules from config
+fn load_replacement_rules(app: &AppHandle) -> Vec<ReplacementRule> {
+    let config = load_config(app);
+    config.get("replacement_rules")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Save the find/replace rules to config
+fn save_replacement_rules(app: &AppHandle, rules: &[ReplacementRule]) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["replacement_rules"] = serde_json::to_value(rules)
+        .map_err(|e| format!("Failed to serialize replacement rules: {:?}", e))?;
+    save_config(app, &config)?;
+    println!("[Config] Saved {} replacement rules", rules.len());
+    Ok(())
+}
+
+/// Applies replacement rules in order. Rules that fail (e.g. invalid regex) are
+/// skipped and reported, so one bad rule doesn't block the rest.
+fn apply_replacement_rules(text: &str, rules: &[ReplacementRule]) -> ReplacementPreview {
+    let mut output = text.to_string();
+    let mut errors = Vec::new();
+    
+    for (index, rule) in rules.iter().enumerate() {
+        if rule.find.is_empty() {
+            continue;
+        }
+        if rule.regex {
+            match regex::Regex::new(&rule.find) {
+                Ok(re) => output = re.replace_all(&output, rule.replace.as_str()).into_owned(),
+                Err(e) => errors.push(ReplacementRuleError {
+                    index,
+                    find: rule.find.clone(),
+                    error: e.to_string(),
+                }),
+            }
+        } else {
+            output = output.replace(&rule.find, &rule.replace);
+        }
+    }
+    
+    ReplacementPreview { output, errors }
+}
+
+/// Model recommended for first-run setup: small, fast and good enough for English dictation
+const RECOMMENDED_DEFAULT_MODEL: &str = "base.en-q5_1";
+
+/// Load the model id used by first-run setup
+fn load_default_model(app: &AppHandle) -> String {
+    let config = load_config(app);
+    config.get("default_model")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| RECOMMENDED_DEFAULT_MODEL.to_string())
+}
+
+/// Save the model id used by first-run setup
+fn save_default_model(app: &AppHandle, model_id: &str) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["default_model"] = serde_json::json!(model_id);
+    save_config(app, &config)?;
+    println!("[Config] Saved default model: {}", model_id);
+    Ok(())
+}
+
+/// Load whether first-run setup has been completed. Installs from before the
+/// flag existed count as onboarded once they have a model selected.
+fn load_onboarding_completed(app: &AppHandle) -> bool {
+    let config = load_config(app);
+    config.get("has_completed_onboarding")
+        .and_then(|v| v.as_bool())
+        .unwrap_or_else(|| config.get("selected_model").and_then(|v| v.as_str()).is_some())
+}
+
+/// Save whether first-run setup has been completed
+fn save_onboarding_completed(app: &AppHandle, completed: bool) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["has_completed_onboarding"] = serde_json::json!(completed);
+    save_config(app, &config)?;
+    println!("[Config] Saved onboarding completed: {}", completed);
+    Ok(())
+}
+
+/// Load the GPU backend preference from config
+fn load_gpu_settings(app: &AppHandle) -> GpuSettings {
+    let config = load_config(app);
+    config.get("gpu")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Save the GPU backend preference to config
+fn save_gpu_settings(app: &AppHandle, settings: &GpuSettings) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["gpu"] = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize GPU settings: {:?}", e))?;
+    save_config(app, &config)?;
+    println!("[Config] Saved GPU backend preference: {} (device {})", settings.backend, settings.device);
+    Ok(())
+}
+
+/// Load the number formatting settings from config
+fn load_number_formatting(app: &AppHandle) -> NumberFormatting {
+    let config = load_config(app);
+    config.get("number_formatting")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Save the number formatting settings to config
+fn save_number_formatting(app: &AppHandle, settings: &NumberFormatting) -> Result<(), String> {
+    let _lock = lock_config();
+    let mut config = load_config(app);
+    config["number_formatting"] = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize number formatting: {:?}", e))?;
+    save_config(app, &config)?;
+    println!("[Config] Saved number formatting: {} ({})", settings.mode, settings.locale);
+    Ok(())
+}
+
+/// Applies all text post-processing passes to a raw transcript
+fn post_process_transcript(app: &AppHandle, text: &str) -> String {
+    let trailing = load_trailing_phrase_settings(app);
+    let text = if trailing.enabled {
+        strip_trailing_phrases(text, &trailing.phrases_for(load_selected_model(app).as_deref()))
+    } else {
+        text.to_string()
+    };
+    let preview = apply_replacement_rules(&text, &load_replacement_rules(app));
+    for err in &preview.errors {
+        eprintln!("[Replace] Skipped rule {} ('{}'): {}", err.index, err.find, err.error);
+    }
+    let text = apply_number_formatting(&preview.output, &load_number_formatting(app));
+    text.trim().to_string()
+}
+
+/// Lowercases and drops punctuation, for comparing phrases loosely
+fn normalize_phrase(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Drops a trailing sentence that is exactly one of the phrases Whisper tends to
+/// invent on silence. A transcript that is only that sentence (the typical result
+/// of a silent recording) comes back empty. Conservative otherwise: a transcript
+/// that says the phrase earlier too is left alone.
+fn strip_trailing_phrases(text: &str, phrases: &[String]) -> String {
+    let is_phrase = |sentence: &str| {
+        let sentence = normalize_phrase(sentence);
+        !sentence.is_empty() && phrases.iter().any(|p| normalize_phrase(p) == sentence)
+    };
+    let trimmed = text.trim_end();
+    let body = trimmed.trim_end_matches(['.', '!', '?']);
+    let Some(start) = body.rfind(['.', '!', '?']).map(|i| i + 1) else {
+        return if is_phrase(trimmed) { String::new() } else { text.to_string() };
+    };
+    let (before, last) = trimmed.split_at(start);
+    if !is_phrase(last) || normalize_phrase(before).contains(&normalize_phrase(last)) {
+        return text.to_string();
+    }
+    before.trim_end().to_string()
+}
+
+/// Auto-load the previously selected model on startup
+fn auto_load_model(app: &AppHandle, whisper_state: &SharedWhisper) {
+    let (model_path, auto_selected) = match selected_model_file(app) {
+        Some(path) => (path, None),
+        None => {
+            let Some((preset, path)) = auto_select_model(app) else { return };
+            (path, Some(preset))
+        }
+    };
+    let path_str = model_path.to_string_lossy().to_string();
+    if !model_path.exists() {
+        println!("[Startup] Saved model not found: {}", path_str);
+        return;
+    }
+    
+    println!("[Startup] Auto-loading model from: {}", path_str);
+    match create_whisper_context(app, &path_str) {
+        Ok((ctx, backend)) => {
+            if let Ok(mut ws) = whisper_state.lock() {
+                ws.ctx = Some(ctx);
+                ws.model_path = Some(model_path);
+                ws.backend = Some(backend);
+                println!("[Startup] Model loaded successfully: {}", path_str);
+            }
+            if let Some(preset) = auto_selected {
+                save_auto_selected_model(app, &preset);
+            }
+        }
+        Err(e) => {
+            eprintln!("[Startup] Failed to load model: {}", e);
+        }
+    }
+}
+
+/// With no saved selection, picks a downloaded model by the configured preference
+/// (largest file for "accurate", smallest for "fast"). Nothing is saved until it loads.
+fn auto_select_model(app: &AppHandle) -> Option<(PresetModel, PathBuf)> {
+    let models_dir = get_models_dir(app).ok()?;
+    let preference = load_auto_model_preference(app);
+    let downloaded = get_all_models(app).into_iter().filter_map(|preset| {
+        let path = models_dir.join(&preset.filename);
+        let size = std::fs::metadata(&path).ok()?.len();
+        Some((preset, path, size))
+    });
+    let (preset, path, _) = if preference == "fast" {
+        downloaded.min_by_key(|(_, _, size)| *size)
+    } else {
+        downloaded.max_by_key(|(_, _, size)| *size)
+    }?;
+    
+    println!("[Startup] No model selected, auto-selecting {} ({})", preset.id, preference);
+    Some((preset, path))
+}
+
+/// Saves an auto-selected model once it has loaded and emits `model_auto_selected`.
+/// Onboarding is marked as not done first, so having a selection that the user
+/// never made doesn't count as completing first-run setup.
+fn save_auto_selected_model(app: &AppHandle, preset: &PresetModel) {
+    let onboarding_recorded = load_config(app).get("has_completed_onboarding").is_some();
+    let saved = if onboarding_recorded { Ok(()) } else { save_onboarding_completed(app, false) }
+        .and_then(|_| save_selected_model(app, &preset.id));
+    if let Err(e) = saved {
+        eprintln!("[Startup] Failed to save auto-selected model: {}", e);
+    }
+    let _ = app.emit("model_auto_selected", serde_json::json!({
+        "model_id": preset.id,
+        "name": preset.name,
+        "preference": load_auto_model_preference(app),
+    }));
+}
+
+/// File of the saved model selection: the path itself for models selected by
+/// path, otherwise the preset or imported model's file in the models directory
+fn selected_model_file(app: &AppHandle) -> Option<PathBuf> {
+    if let Some(path) = load_selected_model_path(app) {
+        return Some(path);
+    }
+    let model_id = load_selected_model(app)?;
+    let preset = get_all_models(app).into_iter().find(|p| p.id == model_id)?;
+    Some(get_models_dir(app).ok()?.join(&preset.filename))
+}
+
+/// Tauri command to list all preset models with their status
+#[tauri::command]
+fn list_models(app: AppHandle, whisper_state: tauri::State<SharedWhisper>) -> Result<Vec<ModelInfo>, String> {
+    let models_dir = get_models_dir(&app)?;
+    let presets = get_all_models(&app);
+    
+    let active_path = whisper_state.lock()
+        .ok()
+        .and_then(|ws| ws.model_path.clone());
+    
+    let models: Vec<ModelInfo> = presets.iter().map(|preset| {
+        let model_path = models_dir.join(&preset.filename);
+        let downloaded = model_path.exists();
+        let active = active_path.as_ref().map_or(false, |p| p == &model_path);
+        
+        ModelInfo {
+            id: preset.id.clone(),
+            name: preset.name.clone(),
+            filename: preset.filename.clone(),
+            size: preset.size.clone(),
+            downloaded,
+            active,
+            english_only: is_english_only_model(&preset.filename),
+        }
+    }).collect();
+    
+    Ok(models)
+}
+
+/// Tauri command to list downloaded models, most recently used first. Models that
+/// were never used come last, largest first, as they're the likeliest to delete.
+#[tauri::command]
+fn list_models_by_usage(app: AppHandle, whisper_state: tauri::State<SharedWhisper>) -> Result<Vec<ModelUsage>, String> {
+    let models_dir = get_models_dir(&app)?;
+    let last_used = load_model_last_used(&app);
+    let active_path = whisper_state.lock()
+        .ok()
+        .and_then(|ws| ws.model_path.clone());
+    
+    let mut models: Vec<ModelUsage> = get_all_models(&app).into_iter().filter_map(|preset| {
+        let model_path = models_dir.join(&preset.filename);
+        let size_bytes = std::fs::metadata(&model_path).ok()?.len();
+        Some(ModelUsage {
+            active: active_path.as_ref() == Some(&model_path),
+            last_used: last_used.get(&preset.id).copied(),
+            id: preset.id,
+            name: preset.name,
+            filename: preset.filename,
+            size_bytes,
+        })
+    }).collect();
+    models.sort_by(|a, b| b.last_used.cmp(&a.last_used).then(b.size_bytes.cmp(&a.size_bytes)));
+    Ok(models)
+}
+
+/// Tauri command to download a model
+#[tauri::command]
+async fn download_model(app: AppHandle, model_id: String) -> Result<String, String> {
+    let presets = get_preset_models();
+    let preset = presets.iter()
+        .find(|p| p.id == model_id)
+        .ok_or_else(|| format!("Unknown model: {}", model_id))?
+        .clone();
+    
+    let models_dir = get_models_dir(&app)?;
+    let model_path = models_dir.join(&preset.filename);
+    
+    // Check if already downloaded
+    if model_path.exists() {
+        return Ok(format!("Model already downloaded: {}", preset.filename));
+    }
+    
+    download_preset(&app, &model_id, &preset, &model_path).await?;
+    Ok(format!("Downloaded: {}", preset.filename))
+}
+
+/// Downloads a preset to `model_path`, one download at a time; later requests wait as queued.
+/// Progress and failures are recorded in the `DownloadTracker` for `get_download_states`.
+async fn download_preset(app: &AppHandle, model_id: &str, preset: &PresetModel, model_path: &std::path::Path) -> Result<(), String> {
+    get_writable_models_dir(app)?;
+    let tracker = app.state::<DownloadTracker>();
+    {
+        let mut entries = tracker.entries.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+        if matches!(entries.get(model_id), Some(DownloadState::Queued | DownloadState::Downloading { .. })) {
+            return Err(format!("Model is already downloading: {}", model_id));
+        }
+        entries.insert(model_id.to_string(), DownloadState::Queued);
+    }
+    
+    let result = match tracker.slot.acquire().await {
+        Ok(_permit) => fetch_preset(app, model_id, preset, model_path).await,
+        Err(e) => Err(format!("Download queue closed: {:?}", e)),
+    };
+    
+    if let Ok(mut entries) = tracker.entries.lock() {
+        match &result {
+            Ok(()) => { entries.remove(model_id); }
+            Err(e) => { entries.insert(model_id.to_string(), DownloadState::Failed { error: e.clone() }); }
+        }
+    }
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(model_path.with_extension("tmp")).await;
+    }
+    result
+}
+
+/// Fetches a preset to `model_path` via a temp file, emitting download progress events
+async fn fetch_preset(app: &AppHandle, model_id: &str, preset: &PresetModel, model_path: &std::path::Path) -> Result<(), String> {
+    println!("[Download] Starting download of {} from {}", preset.filename, preset.url);
+    let _ = app.emit("download_started", model_id);
+    
+    // Download the file
+    let client = reqwest::Client::new();
+    let response = client.get(&preset.url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to start download: {:?}", e))?;
+    
+    let total_size = response.content_length().unwrap_or(0);
+    
+    // Create temp file
+    let temp_path = model_path.with_extension("tmp");
+    let mut file = tokio::fs::File::create(&temp_path)
+        .await
+        .map_err(|e| format!("Failed to create temp file: {:?}", e))?;
+    
+    let mut downloaded: u64 = 0;
+    let mut stream = response.bytes_stream();
+    
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Download error: {:?}", e))?;
+        
+        tokio::io::AsyncWriteExt::write_all(&mut file, &chunk)
+            .await
+            .map_err(|e| format!("Failed to write chunk: {:?}", e))?;
+        
+        downloaded += chunk.len() as u64;
+        
+        // Emit progress (throttled to avoid too many events)
+        if total_size > 0 {
+            let progress = (downloaded as f64 / total_size as f64 * 100.0) as u32;
+            if let Ok(mut entries) = app.state::<DownloadTracker>().entries.lock() {
+                entries.insert(model_id.to_string(), DownloadState::Downloading {
+                    progress,
+                    downloaded,
+                    total: total_size,
+                });
+            }
+            let _ = app.emit("download_progress", serde_json::json!({
+                "model_id": model_id,
+                "progress": progress,
+                "downloaded": downloaded,
+                "total": total_size
+            }));
+        }
+    }
+    
+    // Rename temp file to final path
+    tokio::fs::rename(&temp_path, &model_path)
+        .await
+        .map_err(|e| format!("Failed to rename temp file: {:?}", e))?;
+    
+    println!("[Download] Completed: {}", preset.filename);
+    let _ = app.emit("download_complete", model_id);
+    
+    Ok(())
+}
+
+/// State of a model as reported by `get_download_states`
+#[derive(Clone, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum DownloadState {
+    NotDownloaded,
+    Queued,
+    Downloading { progress: u32, downloaded: u64, total: u64 },
+    Downloaded,
+    Corrupt,
+    Failed { error: String },
+}
+
+/// In-flight and failed downloads. Finished downloads are dropped and read back from disk.
+pub struct DownloadTracker {
+    entries: Mutex<std::collections::HashMap<String, DownloadState>>,
+    slot: tokio::sync::Semaphore,  // One download at a time
+}
+
+impl Default for DownloadTracker {
+    fn default() -> Self {
+        Self {
+            entries: Mutex::new(std::collections::HashMap::new()),
+            slot: tokio::sync::Semaphore::new(1),
+        }
+    }
+}
+
+/// Magic number at the start of every ggml model file
+const GGML_MAGIC: u32 = 0x6767_6d6c;
+
+/// Checks that a model file starts with the ggml magic number
+fn has_ggml_magic(path: &std::path::Path) -> bool {
+    let mut magic = [0u8; 4];
+    std::fs::File::open(path)
+        .and_then(|mut f| std::io::Read::read_exact(&mut f, &mut magic))
+        .is_ok_and(|_| u32::from_le_bytes(magic) == GGML_MAGIC)
+}
+
+/// Files smaller than this fraction of their listed size are treated as truncated.
+/// Listed sizes are rounded, so this leaves some slack.
+const TRUNCATED_SIZE_RATIO: f64 = 0.9;
+
+/// Parses a model size label ("78 MB", "1.66 GB") into bytes
+fn parse_size_label(label: &str) -> Option<u64> {
+    let (number, unit) = label.trim().split_once(' ')?;
+    let number: f64 = number.parse().ok()?;
+    let scale = match unit {
+        "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        _ => return None,
+    };
+    Some((number * scale) as u64)
+}
+
+/// What's wrong with a downloaded model file, if anything: a missing ggml header
+/// or a file well short of its listed size
+fn model_file_issue(path: &std::path::Path, size_label: &str) -> Option<&'static str> {
+    if !has_ggml_magic(path) {
+        return Some("bad_header");
+    }
+    let size = std::fs::metadata(path).ok()?.len();
+    let expected = parse_size_label(size_label)?;
+    (size < (expected as f64 * TRUNCATED_SIZE_RATIO) as u64).then_some("truncated")
+}
+
+/// A corrupt model or leftover partial download found by the integrity scan
+#[derive(Serialize)]
+pub struct ModelIntegrityIssue {
+    pub model_id: Option<String>,
+    pub filename: String,
+    pub issue: String,  // "bad_header", "truncated" or "orphaned_temp"
+    pub size_bytes: u64,
+    pub expected_bytes: Option<u64>,
+}
+
+/// Result of checking every downloaded model
+#[derive(Serialize)]
+pub struct ModelIntegrityReport {
+    pub checked: usize,
+    pub issues: Vec<ModelIntegrityIssue>,
+}
+
+/// Checks every downloaded model's header and size, and looks for `.tmp` files
+/// left behind by interrupted downloads that aren't in progress now
+fn scan_model_integrity(app: &AppHandle) -> Result<ModelIntegrityReport, String> {
+    let models_dir = get_models_dir(app)?;
+    let active: Vec<String> = app.state::<DownloadTracker>().entries.lock()
+        .map(|entries| entries.iter()
+            .filter(|(_, state)| matches!(state, DownloadState::Queued | DownloadState::Downloading { .. }))
+            .map(|(id, _)| id.clone())
+            .collect())
+        .unwrap_or_default();
+    
+    let file_size = |path: &std::path::Path| std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    let mut checked = 0;
+    let mut issues = Vec::new();
+    for model in get_all_models(app) {
+        let path = models_dir.join(&model.filename);
+        if path.exists() {
+            checked += 1;
+            if let Some(issue) = model_file_issue(&path, &model.size) {
+                issues.push(ModelIntegrityIssue {
+                    model_id: Some(model.id.clone()),
+                    filename: model.filename.clone(),
+                    issue: issue.to_string(),
+                    size_bytes: file_size(&path),
+                    expected_bytes: parse_size_label(&model.size),
+                });
+            }
+        }
+        let temp_path = path.with_extension("tmp");
+        if temp_path.exists() && !active.contains(&model.id) {
+            issues.push(ModelIntegrityIssue {
+                model_id: Some(model.id.clone()),
+                filename: temp_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+                issue: "orphaned_temp".to_string(),
+                size_bytes: file_size(&temp_path),
+                expected_bytes: parse_size_label(&model.size),
+            });
+        }
+    }
+    
+    for issue in &issues {
+        println!("[Models] Integrity issue: {} ({})", issue.filename, issue.issue);
+    }
+    println!("[Models] Integrity scan checked {} models, {} issues", checked, issues.len());
+    Ok(ModelIntegrityReport { checked, issues })
+}
+
+/// Tauri command to check downloaded models for corruption on demand; the same
+/// report is emitted as `model_integrity_report` by the startup scan
+#[tauri::command]
+fn check_model_integrity(app: AppHandle) -> Result<ModelIntegrityReport, String> {
+    scan_model_integrity(&app)
+}
+
+/// Tauri command to get the state of every preset model, recomputed from the
+/// in-flight downloads and the files on disk
+#[tauri::command]
+fn get_download_states(
+    app: AppHandle,
+    tracker: tauri::State<DownloadTracker>,
+) -> Result<std::collections::HashMap<String, DownloadState>, String> {
+    let models_dir = get_models_dir(&app)?;
+    let entries = tracker.entries.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+    
+    Ok(get_preset_models().into_iter().map(|preset| {
+        let path = models_dir.join(&preset.filename);
+        let tracked = entries.get(&preset.id);
+        let state = match tracked {
+            Some(state @ (DownloadState::Queued | DownloadState::Downloading { .. })) => state.clone(),
+            _ if path.exists() && model_file_issue(&path, &preset.size).is_none() => DownloadState::Downloaded,
+            _ if path.exists() => DownloadState::Corrupt,
+            Some(state) => state.clone(),
+            None => DownloadState::NotDownloaded,
+        };
+        (preset.id, state)
+    }).collect())
+}
+
+/// VAD model info returned to frontend
+#[derive(Serialize)]
+pub struct VadModelInfo {
+    pub id: String,
+    pub name: String,
+    pub filename: String,
+    pub size: String,
+    pub downloaded: bool,
+    pub active: bool,
+}
+
+/// Loads a downloaded VAD model into the VAD slot
+fn load_vad_model_into(app: &AppHandle, model_id: &str, vad_state: &SharedVadModel) -> Result<(), String> {
+    let preset = get_vad_presets()
+        .into_iter()
+        .find(|p| p.id == model_id)
+        .ok_or_else(|| format!("Unknown VAD model: {}", model_id))?;
+    let model_path = get_models_dir(app)?.join(&preset.filename);
+    
+    let size = std::fs::metadata(&model_path)
+        .map_err(|_| format!("VAD model not downloaded: {}", preset.filename))?
+        .len();
+    if size == 0 {
+        return Err(format!("VAD model file is empty: {}", preset.filename));
+    }
+    
+    let mut slot = vad_state.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+    slot.model_id = Some(preset.id.clone());
+    slot.model_path = Some(model_path);
+    println!("[VAD] Loaded model: {}", preset.name);
+    Ok(())
+}
+
+/// Tauri command to list the VAD model presets with their status
+#[tauri::command]
+fn list_vad_status(app: AppHandle, vad_state: tauri::State<SharedVadModel>) -> Result<Vec<VadModelInfo>, String> {
+    let models_dir = get_models_dir(&app)?;
+    let active_id = vad_state.lock().ok().and_then(|s| s.model_id.clone());
+    
+    Ok(get_vad_presets().into_iter().map(|preset| VadModelInfo {
+        downloaded: models_dir.join(&preset.filename).exists(),
+        active: active_id.as_deref() == Some(preset.id.as_str()),
+        id: preset.id,
+        name: preset.name,
+        filename: preset.filename,
+        size: preset.size,
+    }).collect())
+}
+
+/// Tauri command to download a VAD model and load it into the VAD slot
+#[tauri::command]
+async fn download_vad_model(
+    app: AppHandle,
+    model_id: String,
+    vad_state: tauri::State<'_, SharedVadModel>,
+) -> Result<String, String> {
+    let preset = get_vad_presets()
+        .into_iter()
+        .find(|p| p.id == model_id)
+        .ok_or_else(|| format!("Unknown VAD model: {}", model_id))?;
+    let model_path = get_models_dir(&app)?.join(&preset.filename);
+    
+    if !model_path.exists() {
+        download_preset(&app, &model_id, &preset, &model_path).await?;
+    }
+    
+    load_vad_model_into(&app, &model_id, &vad_state)?;
+    save_vad_model(&app, Some(&model_id))?;
+    Ok(format!("VAD model ready: {}", preset.filename))
+}
+
+/// Tauri command to select a downloaded VAD model (None unloads it)
+#[tauri::command]
+fn set_vad_model(
+    app: AppHandle,
+    model_id: Option<String>,
+    vad_state: tauri::State<SharedVadModel>,
+) -> Result<(), String> {
+    match model_id.as_deref() {
+        Some(id) => load_vad_model_into(&app, id, &vad_state)?,
+        None => {
+            let mut slot = vad_state.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+            slot.model_id = None;
+            slot.model_path = None;
+        }
+    }
+    save_vad_model(&app, model_id.as_deref())
+}
+
+/// Tauri command to re-read config after it was changed outside the app (or restored
+/// from a backup). Per-transcription settings are read fresh from config anyway; this
+/// refreshes what is held in memory: hotkey bindings, the VAD model and the Whisper model.
+#[tauri::command]
+fn reload_config(
+    app: AppHandle,
+    whisper_state: tauri::State<SharedWhisper>,
+    vad_state: tauri::State<SharedVadModel>,
+    bindings: tauri::State<SharedHotkeyBindings>,
+) -> Result<(), String> {
+    *bindings.lock().map_err(|e| format!("Lock error: {:?}", e))? = HotkeyBindings::load(&app);
+    
+    let vad_model = load_vad_model(&app);
+    let current_vad = vad_state.lock().ok().and_then(|s| s.model_id.clone());
+    if vad_model != current_vad {
+        match vad_model.as_deref() {
+            Some(id) => load_vad_model_into(&app, id, &vad_state)?,
+            None => {
+                let mut slot = vad_state.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+                slot.model_id = None;
+                slot.model_path = None;
+            }
+        }
+    }
+    
+    let loaded_path = whisper_state.lock().ok().and_then(|ws| ws.model_path.clone());
+    if let Some(model_path) = selected_model_file(&app).filter(|p| Some(p) != loaded_path.as_ref()) {
+        if !model_path.exists() {
+            return Err(format!("Selected model not found: {}", model_path.display()));
+        }
+        let (ctx, backend) = create_whisper_context(&app, &model_path.to_string_lossy())?;
+        let mut ws = whisper_state.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+        ws.ctx = Some(ctx);
+        ws.model_path = Some(model_path);
+        ws.backend = Some(backend);
+    }
+    
+    println!("[Config] Reloaded config");
+    let _ = app.emit("config_reloaded", ());
+    Ok(())
+}
+
+/// Tauri command to load a model by ID
+#[tauri::command]
+fn load_model(app: AppHandle, model_id: String, state: tauri::State<SharedWhisper>) -> Result<String, String> {
+    let presets = get_all_models(&app);
+    let preset = presets.iter()
+        .find(|p| p.id == model_id)
+        .ok_or_else(|| format!("Unknown model: {}", model_id))?;
+    
+    let models_dir = get_models_dir(&app)?;
+    let model_path = models_dir.join(&preset.filename);
+    
+    if !model_path.exists() {
+        return Err(format!("Model not downloaded: {}", preset.filename));
+    }
+    
+    let path_str = model_path.to_string_lossy().to_string();
+    println!("[Whisper] Loading model from: {}", path_str);
+    
+    // Load the Whisper context
+    let (ctx, backend) = create_whisper_context(&app, &path_str)?;
+    
+    // Store in state
+    let mut ws = state.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+    ws.ctx = Some(ctx);
+    ws.model_path = Some(model_path);
+    ws.backend = Some(backend);
+    
+    // Save the selection to config
+    let _ = save_selected_model(&app, &model_id);
+    record_model_use(&app, &model_id);
+    drop(ws);
+    
+    println!("[Whisper] Model loaded successfully: {}", preset.name);
+    warm_up_gpu(&app, &state);
+    
+    Ok(format!("Loaded: {}", preset.name))
+}
+
+/// Tauri command to get the model first-run setup will install
+#[tauri::command]
+fn get_default_model(app: AppHandle) -> String {
+    load_default_model(&app)
+}
+
+/// Tauri command to change the model first-run setup will install
+#[tauri::command]
+fn set_default_model(app: AppHandle, model_id: String) -> Result<(), String> {
+    if !get_preset_models().iter().any(|p| p.id == model_id) {
+        return Err(format!("Unknown model: {}", model_id));
+    }
+    save_default_model(&app, &model_id)
+}
+
+/// Tauri command for first-run setup: downloads (with the usual progress events)
+/// and loads the default model so dictation works right away
+#[tauri::command]
+async fn setup_default_model(app: AppHandle, state: tauri::State<'_, SharedWhisper>) -> Result<String, String> {
+    let model_id = load_default_model(&app);
+    println!("[Setup] Installing default model: {}", model_id);
+    
+    download_model(app.clone(), model_id.clone()).await?;
+    let message = load_model(app.clone(), model_id.clone(), state)?;
+    
+    let _ = app.emit("default_model_ready", &model_id);
+    Ok(message)
+}
+
+/// Tauri command to get first-run state, so the setup wizard shows exactly once
+/// and can skip steps that are already done
+#[tauri::command]
+fn get_onboarding_state(app: AppHandle, hotkey_status: tauri::State<SharedHotkeyStatus>) -> serde_json::Value {
+    let hotkey_failure = hotkey_status.lock().ok().and_then(|s| s.clone());
+    serde_json::json!({
+        "completed": load_onboarding_completed(&app),
+        "hotkey_listener_running": hotkey_failure.is_none(),
+        "hotkey_remediation": hotkey_failure.map(|_| hotkey_listener_remediation()),
+        "default_model": load_default_model(&app),
+        "selected_model": load_selected_model(&app),
+        "noise_calibrated": load_noise_calibration(&app).is_some(),
+    })
+}
+
+/// Tauri command to mark first-run setup as finished
+#[tauri::command]
+fn complete_onboarding(app: AppHandle) -> Result<(), String> {
+    save_onboarding_completed(&app, true)?;
+    let _ = app.emit("onboarding_completed", ());
+    Ok(())
+}
+
+/// Tauri command to import a custom model file. The file is only registered if
+/// Whisper can actually load it, so broken files fail here rather than at record time.
+#[tauri::command]
+fn import_custom_model(app: AppHandle, path: String, name: Option<String>) -> Result<ModelInfo, String> {
+    let source = PathBuf::from(&path);
+    if !source.is_file() {
+        return Err(format!("Model file not found: {}", path));
+    }
+    let mut filename = source.file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .ok_or_else(|| format!("Invalid model path: {}", path))?;
+    if get_preset_models().iter().any(|p| p.filename == filename) {
+        return Err(format!("{} conflicts with a preset model filename", filename));
+    }
+    
+    // Validate by creating a context, then drop it immediately to free the memory
+    println!("[Import] Validating custom model: {}", path);
+    let (ctx, _) = create_whisper_context(&app, &path)
+        .map_err(|e| format!("Model could not be loaded, not imported: {}", e))?;
+    drop(ctx);
+    
+    // Copy into the models directory so it's managed like a downloaded preset,
+    // numbering the copy rather than overwriting a different model of the same name
+    let models_dir = get_writable_models_dir(&app)?;
+    let mut dest = models_dir.join(&filename);
+    if dest != source {
+        let stem = source.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let extension = source.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+        let mut copy_number = 1;
+        while dest.exists() {
+            copy_number += 1;
+            filename = format!("{}-{}{}", stem, copy_number, extension);
+            dest = models_dir.join(&filename);
+        }
+        std::fs::copy(&source, &dest)
+            .map_err(|e| format!("Failed to copy model: {:?}", e))?;
+    }
+    
+    let size_bytes = std::fs::metadata(&dest).map(|m| m.len()).unwrap_or(0);
+    let model = CustomModel {
+        id: model_id_for_path(&dest),
+        name: name.unwrap_or_else(|| model_id_for_path(&dest)),
+        filename,
+        size: format!("{:.0} MB", size_bytes as f64 / 1_000_000.0),
+    };
+    
+    update_custom_models(&app, |models| {
+        models.retain(|m| m.id != model.id);
+        models.push(model.clone());
+    })?;
+    
+    println!("[Import] Registered custom model: {}", model.id);
+    Ok(ModelInfo {
+        id: model.id,
+        name: model.name,
+        filename: model.filename,
+        size: model.size,
+        downloaded: true,
+        active: false,
+        english_only: is_english_only_model(&model.filename),
+    })
+}
+
+/// Tauri command to check whether hotkey presses during processing are queued
+#[tauri::command]
+fn get_queue_during_processing(app: AppHandle) -> bool {
+    load_queue_during_processing(&app)
+}
+
+/// Tauri command to queue (true) or drop (false) hotkey presses during processing
+#[tauri::command]
+fn set_queue_during_processing(app: AppHandle, enabled: bool) -> Result<(), String> {
+    save_queue_during_processing(&app, enabled)
+}
+
+/// Tauri command to get the Whisper decoding settings
+#[tauri::command]
+fn get_decoding_options(app: AppHandle) -> DecodingOptions {
+    load_decoding_options(&app)
+}
+
+/// Tauri command to set segmentation: split on word boundaries and cap segment
+/// length in characters (0 = no limit)
+#[tauri::command]
+fn set_max_segment_length(app: AppHandle, max_len: u32, split_on_word: bool) -> Result<(), String> {
+    update_decoding_options(&app, |options| {
+        options.max_len = max_len;
+        options.split_on_word = split_on_word;
+    })
+}
+
+/// Tauri command to get the transcription performance mode
+#[tauri::command]
+fn get_performance_mode(app: AppHandle) -> PerformanceMode {
+    load_decoding_options(&app).performance_mode
+}
+
+/// Tauri command to set the transcription performance mode (eco, balanced or max)
+#[tauri::command]
+fn set_performance_mode(app: AppHandle, mode: PerformanceMode) -> Result<(), String> {
+    update_decoding_options(&app, |options| {
+        options.performance_mode = mode;
+        // Picking a mode replaces an explicit thread count
+        options.n_threads = None;
+    })?;
+    println!("[Whisper] Performance mode: {} threads", mode.n_threads());
+    Ok(())
+}
+
+/// Longest initial prompt accepted. Whisper keeps at most 224 prompt tokens and
+/// drops the oldest beyond that, so longer prompts would lose their start.
+const MAX_INITIAL_PROMPT_CHARS: usize = 800;
+
+/// Tauri command to get the initial prompt
+#[tauri::command]
+fn get_initial_prompt(app: AppHandle) -> String {
+    load_decoding_options(&app).initial_prompt
+}
+
+/// Tauri command to set the initial prompt: names and terms (e.g. "Kubernetes,
+/// winsper") Whisper should favor. An empty prompt turns it off.
+#[tauri::command]
+fn set_initial_prompt(app: AppHandle, text: String) -> Result<(), String> {
+    let text = text.trim().to_string();
+    if text.chars().count() > MAX_INITIAL_PROMPT_CHARS {
+        return Err(format!("Initial prompt must be at most {} characters", MAX_INITIAL_PROMPT_CHARS));
+    }
+    update_decoding_options(&app, |options| options.initial_prompt = text)
+}
+
+/// Largest best_of or beam size accepted; beyond this decoding gets very slow
+const MAX_SAMPLING_CANDIDATES: u32 = 10;
+
+/// Tauri command to get the decoder sampling strategy with its best_of and beam size
+#[tauri::command]
+fn get_sampling_params(app: AppHandle) -> SamplingSettings {
+    load_decoding_options(&app).sampling
+}
+
+/// Tauri command to set the decoder sampling strategy ("greedy" or "beam") with
+/// its best_of and beam size. Greedy with best_of 1 stays the fastest option.
+#[tauri::command]
+fn set_sampling_params(app: AppHandle, settings: SamplingSettings) -> Result<(), String> {
+    if !(1..=MAX_SAMPLING_CANDIDATES).contains(&settings.best_of) {
+        return Err(format!("best_of must be between 1 and {}", MAX_SAMPLING_CANDIDATES));
+    }
+    if !(1..=MAX_SAMPLING_CANDIDATES).contains(&settings.beam_size) {
+        return Err(format!("Beam size must be between 1 and {}", MAX_SAMPLING_CANDIDATES));
+    }
+    update_decoding_options(&app, |options| options.sampling = settings)
+}
+
+/// Tauri command to get the number of threads inference runs on
+#[tauri::command]
+fn get_thread_count(app: AppHandle) -> u32 {
+    load_decoding_options(&app).thread_count() as u32
+}
+
+/// Tauri command to set an explicit inference thread count, overriding the
+/// performance mode (None = go back to the mode's count)
+#[tauri::command]
+fn set_thread_count(app: AppHandle, threads: Option<u32>) -> Result<(), String> {
+    if let Some(n) = threads {
+        let cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4) as u32;
+        if !(1..=cores).contains(&n) {
+            return Err(format!("Thread count must be between 1 and {} (the number of cores)", cores));
+        }
+    }
+    let thread_count = update_decoding_options(&app, |options| {
+        options.n_threads = threads;
+        options.thread_count()
+    })?;
+    println!("[Whisper] Thread count: {}", thread_count);
+    Ok(())
+}
+
+/// Model file name of the loaded model if it is English-only
+fn loaded_english_only_model(whisper_state: &SharedWhisper) -> Option<String> {
+    whisper_state.lock()
+        .ok()
+        .and_then(|ws| ws.model_path.as_ref()?.file_name().map(|f| f.to_string_lossy().to_string()))
+        .filter(|name| is_english_only_model(name))
+}
+
+/// Normalizes a language setting, accepting "auto" or an ISO 639-1 code Whisper knows
+fn parse_language_code(code: &str) -> Result<String, String> {
+    let code = code.trim().to_lowercase();
+    if code != "auto" && whisper_rs::get_lang_id(&code).is_none() {
+        return Err(format!("Unknown language code: {}", code));
+    }
+    Ok(code)
+}
+
+/// Tauri command to check whether the loaded model can transcribe a language:
+/// multilingual models handle every language (and "auto"), English-only models
+/// only "en". With no model loaded nothing is ruled out.
+#[tauri::command]
+fn can_transcribe_language(code: String, whisper_state: tauri::State<SharedWhisper>) -> Result<bool, String> {
+    let code = parse_language_code(&code)?;
+    Ok(loaded_english_only_model(&whisper_state).is_none() || code == "en")
+}
+
+/// Tauri command to check whether speech is translated into English
+#[tauri::command]
+fn get_translate_mode(app: AppHandle) -> bool {
+    load_decoding_options(&app).translate
+}
+
+/// Tauri command to translate speech in any language into English text instead of
+/// transcribing it. Needs a multilingual model.
+#[tauri::command]
+fn set_translate_mode(app: AppHandle, enabled: bool, whisper_state: tauri::State<SharedWhisper>) -> Result<(), String> {
+    if enabled {
+        if let Some(name) = loaded_english_only_model(&whisper_state) {
+            return Err(format!("{} is an English-only model; load a multilingual model to translate", name));
+        }
+    }
+    update_decoding_options(&app, |options| options.translate = enabled)
+}
+
+/// Tauri command to get the transcription language (ISO 639-1 code or "auto")
+#[tauri::command]
+fn get_language(app: AppHandle) -> String {
+    load_decoding_options(&app).language
+}
+
+/// Tauri command to set the transcription language: an ISO 639-1 code Whisper
+/// knows, or "auto" to detect it (reported as `language_detected`). English-only
+/// models can't transcribe anything else, so other languages are rejected while
+/// one is loaded.
+#[tauri::command]
+fn set_language(app: AppHandle, language: String, whisper_state: tauri::State<SharedWhisper>) -> Result<(), String> {
+    let language = parse_language_code(&language)?;
+    if let Some(name) = loaded_english_only_model(&whisper_state) {
+        if language != "en" {
+            return Err(format!("{} is an English-only model; load a multilingual model to use {}", name, language));
+        }
+    }
+    
+    update_decoding_options(&app, |options| options.language = language)
+}
+
+/// Tauri command to set what goes between segments: "space" (only where needed)
+/// or "newline" (one segment per line)
+#[tauri::command]
+fn set_segment_separator(app: AppHandle, separator: SegmentSeparator) -> Result<(), String> {
+    update_decoding_options(&app, |options| options.segment_separator = separator)
+}
+
+/// Tauri command to turn experimental per-segment language detection on or off
+#[tauri::command]
+fn set_segment_language_detection(app: AppHandle, enabled: bool) -> Result<(), String> {
+    update_decoding_options(&app, |options| options.detect_segment_languages = enabled)
+}
+
+/// Tauri command to get the VAD filter settings
+#[tauri::command]
+fn get_vad_settings(app: AppHandle) -> VadSettings {
+    load_vad_settings(&app)
+}
+
+/// Tauri command to configure removal of non-speech gaps before transcription
+#[tauri::command]
+fn set_vad_settings(app: AppHandle, settings: VadSettings) -> Result<(), String> {
+    if settings.aggressiveness > 3 {
+        return Err("Aggressiveness must be between 0 and 3".to_string());
+    }
+    if settings.threshold.is_some_and(|t| !(t > 0.0 && t < 1.0)) {
+        return Err("Threshold must be between 0 and 1".to_string());
+    }
+    save_vad_settings(&app, &settings)
+}
+
+/// Tauri command to check whether the record hotkey surfaces the main window when no model is loaded
+#[tauri::command]
+fn get_background_mode(app: AppHandle) -> bool {
+    load_background_mode(&app)
+}
+
+/// Tauri command to have the record hotkey show the main window, instead of the
+/// overlay's "no model" message, when it is pressed without a model loaded
+#[tauri::command]
+fn set_background_mode(app: AppHandle, enabled: bool) -> Result<(), String> {
+    save_background_mode(&app, enabled)
+}
+
+/// Tauri command to check whether downloaded models are scanned for corruption at startup
+#[tauri::command]
+fn get_model_integrity_scan(app: AppHandle) -> bool {
+    load_model_integrity_scan(&app)
+}
+
+/// Tauri command to turn the startup model integrity scan on or off
+#[tauri::command]
+fn set_model_integrity_scan(app: AppHandle, enabled: bool) -> Result<(), String> {
+    save_model_integrity_scan(&app, enabled)
+}
+
+/// Tauri command to check whether GPU models are warmed up after loading
+#[tauri::command]
+fn get_gpu_warmup(app: AppHandle) -> bool {
+    load_gpu_warmup(&app)
+}
+
+/// Tauri command to turn the post-load GPU warmup on or off
+#[tauri::command]
+fn set_gpu_warmup(app: AppHandle, enabled: bool) -> Result<(), String> {
+    save_gpu_warmup(&app, enabled)
+}
+
+/// Tauri command to get how long a transcription may run before it is aborted (0 = never)
+#[tauri::command]
+fn get_processing_timeout(app: AppHandle) -> u64 {
+    load_processing_timeout_seconds(&app)
+}
+
+/// Tauri command to set how long a transcription may run before it is aborted (0 = never)
+#[tauri::command]
+fn set_processing_timeout(app: AppHandle, seconds: u64) -> Result<(), String> {
+    save_processing_timeout_seconds(&app, seconds)
+}
+
+/// Tauri command to get the trailing phrase filter
+#[tauri::command]
+fn get_trailing_phrases(app: AppHandle) -> TrailingPhraseSettings {
+    load_trailing_phrase_settings(&app)
+}
+
+/// Tauri command to configure phrases stripped from the end of transcripts
+/// ("Thank you.", "Bye.") when Whisper hallucinates them on silence
+#[tauri::command]
+fn set_trailing_phrases(app: AppHandle, settings: TrailingPhraseSettings) -> Result<(), String> {
+    let mut all_phrases = settings.phrases.iter().chain(settings.model_phrases.values().flatten());
+    if all_phrases.any(|p| normalize_phrase(p).is_empty()) {
+        return Err("Phrases must contain at least one word".to_string());
+    }
+    save_trailing_phrase_settings(&app, &settings)
+}
+
+/// Tauri command to get the per-sentence paste settings
+#[tauri::command]
+fn get_sentence_split(app: AppHandle) -> SentenceSplitSettings {
+    load_sentence_split_settings(&app)
+}
+
+/// Tauri command to paste each sentence separately with Enter between, for sending
+/// a dictation as several chat messages
+#[tauri::command]
+fn set_sentence_split(app: AppHandle, settings: SentenceSplitSettings) -> Result<(), String> {
+    if settings.delay_ms > MAX_SENTENCE_SPLIT_DELAY_MS {
+        return Err(format!("Delay must be at most {}ms", MAX_SENTENCE_SPLIT_DELAY_MS));
+    }
+    save_sentence_split_settings(&app, &settings)
+}
+
+/// Tauri command to get the live noise gate settings
+#[tauri::command]
+fn get_noise_gate(app: AppHandle) -> NoiseGateSettings {
+    load_noise_gate_settings(&app)
+}
+
+/// Tauri command to configure the live noise gate, applied from the next recording
+#[tauri::command]
+fn set_noise_gate(app: AppHandle, settings: NoiseGateSettings) -> Result<(), String> {
+    if !(settings.threshold > 0.0 && settings.threshold < 1.0) {
+        return Err("Threshold must be between 0 and 1".to_string());
+    }
+    if settings.attack_ms > 1000 || settings.hold_ms > 5000 || settings.release_ms > 5000 {
+        return Err("Gate times are limited to 1s attack and 5s hold/release".to_string());
+    }
+    save_noise_gate_settings(&app, &settings)
+}
+
+/// Tauri command to get the automatic gain control settings
+#[tauri::command]
+fn get_agc(app: AppHandle) -> AgcSettings {
+    load_agc_settings(&app)
+}
+
+/// Tauri command to configure automatic gain control, applied to each recording
+/// before transcription
+#[tauri::command]
+fn set_agc(app: AppHandle, settings: AgcSettings) -> Result<(), String> {
+    if !(settings.target_level > 0.0 && settings.target_level <= 1.0) {
+        return Err("Target level must be between 0 and 1".to_string());
+    }
+    if !(1.0..=MAX_AGC_GAIN).contains(&settings.max_gain) {
+        return Err(format!("Max gain must be between 1 and {}", MAX_AGC_GAIN));
+    }
+    if settings.attack_ms > 5000 || settings.release_ms > 10000 {
+        return Err("AGC times are limited to 5s attack and 10s release".to_string());
+    }
+    save_agc_settings(&app, &settings)
+}
+
+/// Tauri command to check whether long-recording mode (audio spilled to disk) is on
+#[tauri::command]
+fn get_record_to_disk(app: AppHandle) -> bool {
+    load_record_to_disk(&app)
+}
+
+/// Tauri command to turn long-recording mode on or off. Audio is written to a temp
+/// WAV file as it is captured and transcribed from it in chunks, bounding memory
+/// for sessions of any length. Takes effect from the next recording.
+#[tauri::command]
+fn set_record_to_disk(app: AppHandle, enabled: bool) -> Result<(), String> {
+    save_record_to_disk(&app, enabled)
+}
+
+/// Tauri command to get the high-pass filter cutoff in Hz (None = off)
+#[tauri::command]
+fn get_high_pass_filter(app: AppHandle) -> Option<f32> {
+    load_high_pass_hz(&app)
+}
+
+/// Tauri command to set the high-pass filter cutoff in Hz (None = off). The limit
+/// is Nyquist at 16kHz, the lowest rate audio is captured at.
+#[tauri::command]
+fn set_high_pass_filter(app: AppHandle, cutoff_hz: Option<f32>) -> Result<(), String> {
+    let nyquist = WHISPER_SAMPLE_RATE as f32 / 2.0;
+    if cutoff_hz.is_some_and(|hz| !(hz > 0.0 && hz < nyquist)) {
+        return Err(format!("Cutoff must be between 0 and {} Hz", nyquist));
+    }
+    save_high_pass_hz(&app, cutoff_hz)
+}
+
+/// Tauri command to list the key names that can be bound to hotkeys
+#[tauri::command]
+fn list_bindable_hotkeys() -> Vec<String> {
+    BINDABLE_HOTKEYS.iter().map(|(name, _)| name.to_string()).collect()
+}
+
+/// Tauri command to check a key during hotkey capture, before binding it.
+/// Returns the system action it's known to trigger, if any.
+#[tauri::command]
+fn check_hotkey_conflict(hotkey: String) -> Result<Option<String>, String> {
+    if parse_hotkey(&hotkey).is_none() {
+        return Err(format!("Unsupported hotkey: {}", hotkey));
+    }
+    Ok(hotkey_conflict(&hotkey).map(str::to_string))
+}
+
+/// Tauri command to get the hotkey that opens the settings window
+#[tauri::command]
+fn get_settings_hotkey(app: AppHandle) -> Option<String> {
+    load_settings_hotkey(&app)
+}
+
+/// Tauri command to bind the hotkey that shows and focuses the settings window (None unbinds it)
+/// The record key (Right Ctrl) isn't bindable, so the two can't conflict.
+#[tauri::command]
+fn set_settings_hotkey(
+    app: AppHandle,
+    hotkey: Option<String>,
+    bindings: tauri::State<SharedHotkeyBindings>,
+) -> Result<(), String> {
+    let mut bindings = bindings.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+    let key = parse_hotkey_binding(&bindings, hotkey.as_deref(), "settings")?;
+    save_settings_hotkey(&app, hotkey.as_deref())?;
+    bindings.settings = key;
+    warn_hotkey_conflict(&app, hotkey.as_deref(), "settings");
+    Ok(())
+}
+
+/// Tauri command to get the voice command settings
+#[tauri::command]
+fn get_voice_commands(app: AppHandle) -> VoiceCommandSettings {
+    load_voice_commands(&app)
+}
+
+/// Tauri command to enable voice commands and set the phrase -> action map
+#[tauri::command]
+fn set_voice_commands(app: AppHandle, settings: VoiceCommandSettings) -> Result<(), String> {
+    if settings.phrases.keys().any(|p| p.split_whitespace().map(normalize_command_word).all(|w| w.is_empty())) {
+        return Err("Voice command phrases must contain at least one word".to_string());
+    }
+    save_voice_commands(&app, &settings)
+}
+
+/// Tauri command to list the per-day history archives, newest first
+#[tauri::command]
+fn list_history_archives(app: AppHandle) -> Result<Vec<HistoryArchive>, String> {
+    let history_dir = get_history_dir(&app)?;
+    Ok(list_history_files(&history_dir)
+        .into_iter()
+        .rev()
+        .map(|(date, path)| HistoryArchive {
+            entries: read_history_file(&path).len(),
+            size_bytes: std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+            date,
+        })
+        .collect())
+}
+
+/// Tauri command to get the dictations from one day (`YYYY-MM-DD`, UTC), oldest first
+#[tauri::command]
+fn get_history_for_date(app: AppHandle, date: String) -> Result<Vec<HistoryEntry>, String> {
+    if !is_history_date(&date) {
+        return Err(format!("Invalid date, expected YYYY-MM-DD: {}", date));
+    }
+    Ok(read_history_file(&get_history_dir(&app)?.join(format!("{}.jsonl", date))))
+}
+
+/// Tauri command to check whether dictations are kept in the history
+#[tauri::command]
+fn get_history_enabled(app: AppHandle) -> bool {
+    load_history_enabled(&app)
+}
+
+/// Tauri command to turn the transcription history on or off. Turning it off
+/// stops recording new dictations; existing archives are left in place.
+#[tauri::command]
+fn set_history_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    save_history_enabled(&app, enabled)
+}
+
+/// Tauri command to get how many days of history are kept
+#[tauri::command]
+fn get_history_retention_days(app: AppHandle) -> Option<u32> {
+    load_history_retention_days(&app)
+}
+
+/// Tauri command to set how many days of history are kept (None = forever).
+/// Older archives are pruned right away and after each new dictation.
+#[tauri::command]
+fn set_history_retention_days(app: AppHandle, days: Option<u32>) -> Result<(), String> {
+    if days == Some(0) {
+        return Err("Retention must be at least one day".to_string());
+    }
+    save_history_retention_days(&app, days)?;
+    prune_history(&app);
+    Ok(())
+}
+
+/// Tauri command to check whether pastes are verified with a typing fallback
+#[tauri::command]
+fn get_verify_paste(app: AppHandle) -> bool {
+    load_verify_paste(&app)
+}
+
+/// Tauri command to turn on paste verification: detectable paste failures fall back
+/// to typing the transcript character by character
+#[tauri::command]
+fn set_verify_paste(app: AppHandle, enabled: bool) -> Result<(), String> {
+    save_verify_paste(&app, enabled)
+}
+
+/// Tauri command to get the paste focus check mode
+#[tauri::command]
+fn get_paste_focus_check(app: AppHandle) -> String {
+    load_paste_focus_check(&app)
+}
+
+/// Tauri command to check the window focused at recording stop still has focus when
+/// pasting: "off", "abort" (leave the transcript on the clipboard) or "refocus"
+#[tauri::command]
+fn set_paste_focus_check(app: AppHandle, mode: String) -> Result<(), String> {
+    if !["off", "abort", "refocus"].contains(&mode.as_str()) {
+        return Err(format!("Unknown focus check mode: {}", mode));
+    }
+    if mode != "off" && foreground_window().is_none() {
+        return Err("The focus check is not supported on this platform".to_string());
+    }
+    save_paste_focus_check(&app, &mode)
+}
+
+/// Tauri command to get where pastes go ("active" or "remembered")
+#[tauri::command]
+fn get_paste_target(app: AppHandle) -> String {
+    load_paste_target(&app)
+}
+
+/// Tauri command to paste into the active window ("active") or always into the
+/// window focused when recording started, refocusing it first ("remembered")
+#[tauri::command]
+fn set_paste_target(app: AppHandle, target: String) -> Result<(), String> {
+    if target != "active" && target != "remembered" {
+        return Err(format!("Unknown paste target: {}", target));
+    }
+    if target == "remembered" && foreground_window().is_none() {
+        return Err("Remembering the paste window is not supported on this platform".to_string());
+    }
+    save_paste_target(&app, &target)
+}
+
+/// Tauri command to get which downloaded model is auto-loaded when none is selected
+#[tauri::command]
+fn get_auto_model_preference(app: AppHandle) -> String {
+    load_auto_model_preference(&app)
+}
+
+/// Tauri command to set the auto-selection preference: "accurate" picks the largest
+/// downloaded model, "fast" the smallest
+#[tauri::command]
+fn set_auto_model_preference(app: AppHandle, preference: String) -> Result<(), String> {
+    if preference != "accurate" && preference != "fast" {
+        return Err(format!("Unknown model preference: {}", preference));
+    }
+    save_auto_model_preference(&app, &preference)
+}
+
+/// Tauri command to check whether `recording_complete` stats are emitted
+#[tauri::command]
+fn get_emit_recording_stats(app: AppHandle) -> bool {
+    load_emit_recording_stats(&app)
+}
+
+/// Tauri command to turn the `recording_complete` stats event (sample counts, peak level,
+/// clipping and detected speech) on or off
+#[tauri::command]
+fn set_emit_recording_stats(app: AppHandle, enabled: bool) -> Result<(), String> {
+    save_emit_recording_stats(&app, enabled)
+}
+
+/// Tauri command to get how transcripts are output ("paste", "copy_only" or "type")
+#[tauri::command]
+fn get_output_mode(app: AppHandle) -> String {
+    load_output_mode(&app)
+}
+
+/// Tauri command to set how transcripts are output: "paste" copies and simulates
+/// the paste shortcut, "copy_only" leaves the transcript on the clipboard for
+/// fields that reject programmatic paste, "type" types it without the clipboard
+#[tauri::command]
+fn set_output_mode(app: AppHandle, mode: String) -> Result<(), String> {
+    if !["paste", "copy_only", "type"].contains(&mode.as_str()) {
+        return Err(format!("Unknown output mode: {}", mode));
+    }
+    save_output_mode(&app, &mode)
+}
+
+/// Tauri command to get the wait between typed keystrokes in milliseconds
+#[tauri::command]
+fn get_typing_delay(app: AppHandle) -> u64 {
+    load_typing_delay_ms(&app)
+}
+
+/// Tauri command to set the wait between typed keystrokes. Raise it for terminals
+/// and remote-desktop windows that drop characters typed too quickly.
+#[tauri::command]
+fn set_typing_delay(app: AppHandle, ms: u64) -> Result<(), String> {
+    if ms > MAX_TYPING_KEY_DELAY_MS {
+        return Err(format!("Typing delay must be at most {}ms", MAX_TYPING_KEY_DELAY_MS));
+    }
+    save_typing_delay_ms(&app, ms)
+}
+
+/// Tauri command to get how the record key works ("toggle" or "push_to_talk")
+#[tauri::command]
+fn get_hotkey_mode(app: AppHandle) -> String {
+    load_hotkey_mode(&app)
+}
+
+/// Tauri command to switch the record key between toggle and push-to-talk.
+/// In push-to-talk, holds shorter than `PUSH_TO_TALK_MIN_HOLD_MS` are discarded.
+#[tauri::command]
+fn set_hotkey_mode(
+    app: AppHandle,
+    mode: String,
+    bindings: tauri::State<SharedHotkeyBindings>,
+) -> Result<(), String> {
+    if mode != "toggle" && mode != "push_to_talk" {
+        return Err(format!("Unknown hotkey mode: {}", mode));
+    }
+    let mut bindings = bindings.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+    save_hotkey_mode(&app, &mode)?;
+    bindings.push_to_talk = mode == "push_to_talk";
+    Ok(())
+}
+
+/// Tauri command to get the action bound to tapping Alt
+#[tauri::command]
+fn get_alt_action(app: AppHandle) -> String {
+    load_alt_action(&app)
+}
+
+/// Tauri command to bind an action to tapping Alt on its own: "none", "cancel"
+/// (discard the current recording), "cycle_model" or "record" (a second record key)
+#[tauri::command]
+fn set_alt_action(
+    app: AppHandle,
+    action: String,
+    bindings: tauri::State<SharedHotkeyBindings>,
+) -> Result<(), String> {
+    if !["none", "cancel", "cycle_model", "record"].contains(&action.as_str()) {
+        return Err(format!("Unknown Alt action: {}", action));
+    }
+    let mut bindings = bindings.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+    save_alt_action(&app, &action)?;
+    bindings.alt_action = action;
+    Ok(())
+}
+
+/// Tauri command to get the hotkey that undoes the last paste
+#[tauri::command]
+fn get_undo_paste_hotkey(app: AppHandle) -> Option<String> {
+    load_undo_paste_hotkey(&app)
+}
+
+/// Tauri command to bind a hotkey that retracts winsper's last paste with the
+/// platform undo shortcut (None unbinds it)
+#[tauri::command]
+fn set_undo_paste_hotkey(
+    app: AppHandle,
+    hotkey: Option<String>,
+    bindings: tauri::State<SharedHotkeyBindings>,
+) -> Result<(), String> {
+    let mut bindings = bindings.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+    let key = parse_hotkey_binding(&bindings, hotkey.as_deref(), "undo_paste")?;
+    save_undo_paste_hotkey(&app, hotkey.as_deref())?;
+    bindings.undo_paste = key;
+    warn_hotkey_conflict(&app, hotkey.as_deref(), "undo_paste");
+    Ok(())
+}
+
+/// Tauri command to get the fallback model used when transcription fails
+#[tauri::command]
+fn get_fallback_model(app: AppHandle) -> Option<String> {
+    load_fallback_model(&app)
+}
+
+/// Tauri command to set the model to retry with once when transcription fails (None disables retry)
+#[tauri::command]
+fn set_fallback_model(app: AppHandle, model_id: Option<String>) -> Result<(), String> {
+    if let Some(id) = model_id.as_deref() {
+        if !get_all_models(&app).iter().any(|p| p.id == id) {
+            return Err(format!("Unknown model: {}", id));
+        }
+    }
+    save_fallback_model(&app, model_id.as_deref())
+}
+
+/// Tauri command to check whether the clipboard is restored after pasting
+#[tauri::command]
+fn get_preserve_clipboard(app: AppHandle) -> bool {
+    load_preserve_clipboard(&app)
+}
+
+/// Tauri command to set whether the clipboard is restored after pasting
+#[tauri::command]
+fn set_preserve_clipboard(app: AppHandle, enabled: bool) -> Result<(), String> {
+    save_preserve_clipboard(&app, enabled)
+}
+
+/// Tauri command to get the clipboard restore delay in milliseconds
+#[tauri::command]
+fn get_clipboard_restore_delay(app: AppHandle) -> u64 {
+    load_clipboard_restore_delay_ms(&app)
+}
+
+/// Tauri command to set how long to wait after pasting before restoring the original clipboard.
+/// Too short and slow apps paste the old contents instead of the transcript; too long and
+/// the transcript is visibly left on the clipboard. Electron and remote-desktop apps need more.
+#[tauri::command]
+fn set_clipboard_restore_delay(app: AppHandle, ms: u64) -> Result<(), String> {
+    if ms > MAX_CLIPBOARD_RESTORE_DELAY_MS {
+        return Err(format!("Restore delay must be at most {}ms", MAX_CLIPBOARD_RESTORE_DELAY_MS));
+    }
+    save_clipboard_restore_delay_ms(&app, ms)
+}
+
+/// Tauri command to get the pause before inference starts, in milliseconds
+#[tauri::command]
+fn get_pre_transcription_delay(app: AppHandle) -> u64 {
+    load_pre_transcription_delay_ms(&app)
+}
+
+/// Tauri command to set the pause before inference starts, so the overlay's
+/// transcribing state is visible even on fast models (0 = start immediately)
+#[tauri::command]
+fn set_pre_transcription_delay(app: AppHandle, ms: u64) -> Result<(), String> {
+    if ms > MAX_PRE_TRANSCRIPTION_DELAY_MS {
+        return Err(format!("Delay must be at most {}ms", MAX_PRE_TRANSCRIPTION_DELAY_MS));
+    }
+    save_pre_transcription_delay_ms(&app, ms)
+}
+
+/// Tauri command to get the defer-under-load settings
+#[tauri::command]
+fn get_defer_under_load(app: AppHandle) -> LoadDeferSettings {
+    load_load_defer_settings(&app)
+}
+
+/// Tauri command to configure deferring transcription while the CPU is busy
+#[tauri::command]
+fn set_defer_under_load(app: AppHandle, settings: LoadDeferSettings) -> Result<(), String> {
+    if !(1..=100).contains(&settings.cpu_threshold_percent) {
+        return Err("CPU threshold must be between 1 and 100 percent".to_string());
+    }
+    if settings.max_defer_seconds > MAX_LOAD_DEFER_SECONDS {
+        return Err(format!("Max defer time must be at most {}s", MAX_LOAD_DEFER_SECONDS));
+    }
+    save_load_defer_settings(&app, &settings)
+}
+
+/// Tauri command to get the longest a recording may run in seconds (0 = no limit)
+#[tauri::command]
+fn get_max_recording_seconds(app: AppHandle) -> u64 {
+    load_max_recording_seconds(&app).unwrap_or(0)
+}
+
+/// Tauri command to set the longest a recording may run before it is stopped and
+/// transcribed (0 = no limit)
+#[tauri::command]
+fn set_max_recording_seconds(app: AppHandle, seconds: u64) -> Result<(), String> {
+    save_max_recording_seconds(&app, seconds)
+}
+
+/// Tauri command to get how far above the running level a meter spike is held back (0 = off)
+#[tauri::command]
+fn get_transient_rejection(app: AppHandle) -> f32 {
+    load_transient_rejection_ratio(&app)
+}
+
+/// Tauri command to set the level meter's transient rejection ratio. Lower values
+/// hold back smaller spikes; 0 shows every spike.
+#[tauri::command]
+fn set_transient_rejection(app: AppHandle, ratio: f32) -> Result<(), String> {
+    if ratio != 0.0 && !(1.5..=20.0).contains(&ratio) {
+        return Err("Transient rejection ratio must be 0 (off) or between 1.5 and 20".to_string());
+    }
+    save_transient_rejection_ratio(&app, ratio)
+}
+
+/// Tauri command to get the journal output settings
+#[tauri::command]
+fn get_journal_settings(app: AppHandle) -> JournalSettings {
+    load_journal_settings(&app)
+}
+
+/// Tauri command to configure the journal file transcripts are appended to
+/// (empty path disables it)
+#[tauri::command]
+fn set_journal_settings(app: AppHandle, mut settings: JournalSettings) -> Result<(), String> {
+    settings.path = settings.path.trim().to_string();
+    if settings.is_enabled() {
+        let path = std::path::Path::new(&settings.path);
+        if path.is_dir() {
+            return Err("Journal path must be a file, not a directory".to_string());
+        }
+        if !path.parent().is_some_and(|dir| dir.as_os_str().is_empty() || dir.is_dir()) {
+            return Err("Journal file's folder does not exist".to_string());
+        }
+    }
+    save_journal_settings(&app, &settings)
+}
+
+/// Tauri command to get the webhook output settings
+#[tauri::command]
+fn get_webhook_settings(app: AppHandle) -> WebhookSettings {
+    load_webhook_settings(&app)
+}
+
+/// Tauri command to configure webhook output (empty URL disables it)
+#[tauri::command]
+fn set_webhook_settings(app: AppHandle, mut settings: WebhookSettings) -> Result<(), String> {
+    settings.url = settings.url.trim().to_string();
+    if settings.is_enabled() && !(settings.url.starts_with("http://") || settings.url.starts_with("https://")) {
+        return Err("Webhook URL must start with http:// or https://".to_string());
+    }
+    if settings.timeout_ms == 0 {
+        return Err("Webhook timeout must be greater than zero".to_string());
+    }
+    save_webhook_settings(&app, &settings)
+}
+
+/// Tauri command to check whether punctuation-only transcripts are skipped
+#[tauri::command]
+fn get_skip_punctuation_only(app: AppHandle) -> bool {
+    load_skip_punctuation_only(&app)
+}
+
+/// Tauri command to set whether punctuation-only transcripts are skipped.
+/// Disable this to dictate standalone punctuation.
+#[tauri::command]
+fn set_skip_punctuation_only(app: AppHandle, enabled: bool) -> Result<(), String> {
+    save_skip_punctuation_only(&app, enabled)
+}
+
+/// Tauri command to check whether blank results are retried at a higher temperature
+#[tauri::command]
+fn get_blank_retry(app: AppHandle) -> bool {
+    load_blank_retry(&app)
+}
+
+/// Tauri command to set whether a blank result on audio that has speech is retried
+/// once at a higher temperature (adds one extra decode in that case only)
+#[tauri::command]
+fn set_blank_retry(app: AppHandle, enabled: bool) -> Result<(), String> {
+    save_blank_retry(&app, enabled)
+}
+
+/// Tauri command to save the current settings as a named profile (overwrites same name)
+#[tauri::command]
+fn save_profile(app: AppHandle, name: String) -> Result<Profile, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+    
+    let config = load_config(&app);
+    let settings = PROFILE_SETTING_KEYS.iter()
+        .filter_map(|key| config.get(*key).map(|v| (key.to_string(), v.clone())))
+        .collect();
+    let profile = Profile { name, settings };
+    
+    update_profiles(&app, |profiles| {
+        profiles.retain(|p| p.name != profile.name);
+        profiles.push(profile.clone());
+        Ok(())
+    })?;
+    Ok(profile)
+}
+
+/// Tauri command to list saved profiles
+#[tauri::command]
+fn list_profiles(app: AppHandle) -> Vec<Profile> {
+    load_profiles(&app)
+}
+
+/// Tauri command to apply all settings from a profile, reloading the model if it changed
+#[tauri::command]
+fn apply_profile(
+    app: AppHandle,
+    name: String,
+    state: tauri::State<SharedWhisper>,
+    bindings: tauri::State<SharedHotkeyBindings>,
+) -> Result<String, String> {
+    let profile = load_profiles(&app)
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("Unknown profile: {}", name))?;
+    
+    // Load the model first, so a profile whose model can't load changes nothing
+    let profile_model = profile.settings.get("selected_model").and_then(|v| v.as_str());
+    if let Some(model_id) = profile_model {
+        if load_selected_model(&app).as_deref() != Some(model_id) {
+            load_model(app.clone(), model_id.to_string(), state)?;
+        }
+    }
+    
+    update_config(&app, |config| {
+        for (key, value) in &profile.settings {
+            if PROFILE_SETTING_KEYS.contains(&key.as_str()) {
+                config[key.as_str()] = value.clone();
+            }
+        }
+        Ok(())
+    })?;
+    // The hotkey mode is held in memory by the listener
+    let push_to_talk = load_hotkey_mode(&app) == "push_to_talk";
+    bindings.lock().map_err(|e| format!("Lock error: {:?}", e))?.push_to_talk = push_to_talk;
+    println!("[Profile] Applied profile: {}", profile.name);
+    
+    let _ = app.emit("profile_applied", &profile.name);
+    Ok(format!("Applied profile: {}", profile.name))
+}
+
+/// Tauri command to delete a saved profile
+#[tauri::command]
+fn delete_profile(app: AppHandle, name: String) -> Result<(), String> {
+    update_profiles(&app, |profiles| {
+        let before = profiles.len();
+        profiles.retain(|p| p.name != name);
+        if profiles.len() == before {
+            return Err(format!("Unknown profile: {}", name));
+        }
+        Ok(())
+    })
+}
+
+/// Tauri command to export the portable subset of the current settings as a
+/// named preset file others can import. Personal data (paths, devices, hotkeys,
+/// history, webhooks) is never included.
+#[tauri::command]
+fn export_shareable_preset(
+    app: AppHandle,
+    name: String,
+    description: Option<String>,
+    path: String,
+) -> Result<ShareablePreset, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Preset name cannot be empty".to_string());
+    }
+    
+    let config = load_config(&app);
+    let settings = SHAREABLE_SETTING_KEYS.iter()
+        .filter_map(|key| config.get(*key).filter(|v| !v.is_null()).map(|v| (key.to_string(), v.clone())))
+        .collect();
+    // Only built-in presets are meaningful on another machine
+    let recommended_model = load_selected_model(&app)
+        .filter(|id| get_preset_models().iter().any(|p| &p.id == id));
+    let preset = ShareablePreset {
+        format_version: SHAREABLE_PRESET_VERSION,
+        name,
+        description: description.unwrap_or_default(),
+        recommended_model,
+        settings,
+    };
+    
+    let json = serde_json::to_string_pretty(&preset)
+        .map_err(|e| format!("Failed to serialize preset: {:?}", e))?;
+    std::fs::write(&path, json)
+        .map_err(|e| format!("Failed to write preset: {:?}", e))?;
+    println!("[Preset] Exported \"{}\" ({} settings) to {}", preset.name, preset.settings.len(), path);
+    Ok(preset)
+}
+
+/// Tauri command to import a shared preset file. Every setting is validated before
+/// any is applied; the recommended model is loaded if it's already downloaded.
+#[tauri::command]
+fn import_shareable_preset(app: AppHandle, path: String, state: tauri::State<SharedWhisper>) -> Result<ShareablePreset, String> {
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read preset: {:?}", e))?;
+    let preset: ShareablePreset = serde_json::from_str(&contents)
+        .map_err(|e| format!("Not a valid preset file: {}", e))?;
+    if preset.format_version > SHAREABLE_PRESET_VERSION {
+        return Err(format!("Preset format {} is newer than this version of winsper supports", preset.format_version));
+    }
+    for (key, value) in &preset.settings {
+        validate_shareable_setting(key, value)?;
+    }
+    if let Some(model_id) = preset.recommended_model.as_deref() {
+        if !get_preset_models().iter().any(|p| p.id == model_id) {
+            return Err(format!("Preset recommends an unknown model: {}", model_id));
+        }
+    }
+    
+    update_config(&app, |config| {
+        for (key, value) in &preset.settings {
+            config[key.as_str()] = value.clone();
+        }
+        Ok(())
+    })?;
+    println!("[Preset] Imported \"{}\" ({} settings)", preset.name, preset.settings.len());
+    
+    if let Some(model_id) = preset.recommended_model.as_deref() {
+        let downloaded = get_models_dir(&app)
+            .ok()
+            .zip(get_preset_models().into_iter().find(|p| p.id == model_id))
+            .is_some_and(|(dir, p)| dir.join(p.filename).exists());
+        if downloaded && load_selected_model(&app).as_deref() != Some(model_id) {
+            load_model(app.clone(), model_id.to_string(), state)?;
+        }
+    }
+    
+    let _ = app.emit("shareable_preset_imported", &preset);
+    Ok(preset)
+}
+
+/// Text pasted by the paste test
+const PASTE_TEST_STRING: &str = "Winsper paste test 123";
+
+/// Label of the scratch window the paste test pastes into
+const PASTE_TEST_WINDOW: &str = "paste_test";
+
+/// Latest contents of the paste test scratch field, reported by its window
+pub type SharedPasteTest = Arc<Mutex<Option<String>>>;
+
+/// Opens (or reuses) the scratch window and gives it focus
+fn open_paste_test_window(app: &AppHandle) -> Result<tauri::WebviewWindow, String> {
+    let window = match app.get_webview_window(PASTE_TEST_WINDOW) {
+        Some(w) => w,
+        None => tauri::WebviewWindowBuilder::new(app, PASTE_TEST_WINDOW, tauri::WebviewUrl::App("index.html".into()))
+            .title("Winsper Paste Test")
+            .inner_size(420.0, 180.0)
+            .always_on_top(true)
+            .build()
+            .map_err(|e| format!("Failed to open paste test window: {:?}", e))?,
+    };
+    let _ = window.show();
+    let _ = window.set_focus();
+    Ok(window)
+}
+
+/// Pastes text into the focused scratch window through the normal clipboard + paste
+/// path and waits for the window to report it back
+fn run_paste_probe(app: &AppHandle, text: &str) -> Result<PasteTestResult, String> {
+    let received = app.state::<SharedPasteTest>().inner().clone();
+    if let Ok(mut r) = received.lock() {
+        *r = None;
+    }
+    // Clear the scratch field so a previous probe's text isn't pasted onto
+    let _ = app.emit("paste_test_reset", ());
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    
+    let start = std::time::Instant::now();
+    copy_to_clipboard_and_paste(app, text, None)?;
+    
+    // Wait up to 2s for the scratch field to report the pasted text
+    let mut last = None;
+    while start.elapsed() < std::time::Duration::from_secs(2) {
+        last = received.lock().ok().and_then(|r| r.clone());
+        if last.as_deref() == Some(text) {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    
+    Ok(PasteTestResult {
+        success: last.as_deref() == Some(text),
+        expected: text.to_string(),
+        received: last,
+        latency_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+/// Tauri command the scratch window uses to report its field contents
+#[tauri::command]
+fn report_paste_test(text: String, state: tauri::State<SharedPasteTest>) {
+    if let Ok(mut r) = state.lock() {
+        *r = Some(text);
+    }
+}
+
+/// Tauri command to verify paste simulation works: focuses an app-owned scratch window,
+/// pastes a known string into it and checks that it arrived
+#[tauri::command]
+async fn test_paste(app: AppHandle) -> Result<PasteTestResult, String> {
+    let window = open_paste_test_window(&app)?;
+    
+    let app_clone = app.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        // Give the page time to mount and focus its field
+        std::thread::sleep(std::time::Duration::from_millis(800));
+        run_paste_probe(&app_clone, PASTE_TEST_STRING)
+    })
+    .await
+    .map_err(|e| format!("Paste test task failed: {:?}", e))?;
+    
+    let _ = window.destroy();
+    let result = result?;
+    println!("[Paste] Test paste {}: {:?}", if result.success { "succeeded" } else { "failed" }, result.received);
+    Ok(result)
+}
+
+/// Default and maximum number of round-trips for `test_paste_reliability`
+const PASTE_RELIABILITY_ATTEMPTS: usize = 5;
+const MAX_PASTE_RELIABILITY_ATTEMPTS: usize = 20;
+
+/// Success rate below which copy-only or type mode is suggested
+const PASTE_RELIABILITY_THRESHOLD: f32 = 0.9;
+
+/// Tauri command to measure how reliably clipboard + paste works on this system:
+/// runs several round-trips into the scratch window and reports the success rate
+/// and average latency
+#[tauri::command]
+async fn test_paste_reliability(app: AppHandle, attempts: Option<usize>) -> Result<PasteReliabilityResult, String> {
+    let attempts = attempts.unwrap_or(PASTE_RELIABILITY_ATTEMPTS).clamp(1, MAX_PASTE_RELIABILITY_ATTEMPTS);
+    let window = open_paste_test_window(&app)?;
+    
+    let app_clone = app.clone();
+    let results = tauri::async_runtime::spawn_blocking(move || {
+        std::thread::sleep(std::time::Duration::from_millis(800));
+        (1..=attempts)
+            .map(|attempt| {
+                // Distinct text per attempt so a stale report can't count as success
+                let text = format!("{} #{}", PASTE_TEST_STRING, attempt);
+                let result = run_paste_probe(&app_clone, &text);
+                std::thread::sleep(std::time::Duration::from_millis(200));
+                result
+            })
+            .collect::<Result<Vec<_>, String>>()
+    })
+    .await
+    .map_err(|e| format!("Paste test task failed: {:?}", e))?;
+    
+    let _ = window.destroy();
+    let results = results?;
+    let successes = results.iter().filter(|r| r.success).count();
+    let success_rate = successes as f32 / attempts as f32;
+    let succeeded: Vec<u64> = results.iter().filter(|r| r.success).map(|r| r.latency_ms).collect();
+    let average_latency_ms = if succeeded.is_empty() { 0 } else { succeeded.iter().sum::<u64>() / succeeded.len() as u64 };
+    let suggestion = (success_rate < PASTE_RELIABILITY_THRESHOLD).then(|| {
+        "Auto-paste is unreliable on this system. Consider copy-only or type mode instead.".to_string()
+    });
+    println!("[Paste] Reliability test: {}/{} succeeded, average {}ms", successes, attempts, average_latency_ms);
+    
+    Ok(PasteReliabilityResult {
+        attempts,
+        successes,
+        success_rate,
+        average_latency_ms,
+        suggestion,
+        results,
+    })
+}
+
+/// whisper-rs 0.13 has no mmap option in `WhisperContextParameters`, and whisper.cpp
+/// copies the weights into its backend buffers while loading, so mapping the file
+/// ourselves wouldn't save time or memory. Flip this once the binding exposes it.
+const MMAP_MODEL_LOADING: bool = false;
+
+/// Tauri command to report which optional features this binary supports
+#[tauri::command]
+fn get_build_info() -> BuildInfo {
+    BuildInfo {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        // Read from Cargo.lock by build.rs
+        whisper_rs_version: env!("WHISPER_RS_VERSION").to_string(),
+        gpu_backends: compiled_gpu_backends().into_iter().map(String::from).collect(),
+        audio_hosts: cpal::available_hosts().iter().map(|h| h.name().to_string()).collect(),
+        default_audio_host: cpal::default_host().id().name().to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        debug_build: cfg!(debug_assertions),
+        whisper_system_info: whisper_rs::print_system_info().to_string(),
+        mmap_model_loading: MMAP_MODEL_LOADING,
+    }
+}
+
+/// Tauri command to check if autostart is enabled
+#[tauri::command]
+fn get_autostart_enabled(app: AppHandle) -> Result<bool, String> {
+    use tauri_plugin_autostart::ManagerExt;
+    app.autolaunch()
+        .is_enabled()
+        .map_err(|e| format!("Failed to check autostart: {:?}", e))
+}
+
+/// Tauri command to set autostart enabled/disabled
+#[tauri::command]
+fn set_autostart_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    use tauri_plugin_autostart::ManagerExt;
+    let autostart = app.autolaunch();
+    
+    if enabled {
+        autostart.enable().map_err(|e| format!("Failed to enable autostart: {:?}", e))
+    } else {
+        autostart.disable().map_err(|e| format!("Failed to disable autostart: {:?}", e))
+    }
+}
+
+/// Tauri command to list available audio input devices
+#[tauri::command]
+fn list_audio_devices(app: AppHandle) -> Result<Vec<AudioDeviceInfo>, String> {
+    let host = cpal::default_host();
+    let default_device = host.default_input_device();
+    let default_name = default_device.as_ref().and_then(|d| d.name().ok());
+    
+    // Get saved selection
+    let selected_mic = load_selected_microphone(&app);
+    
+    // Only the first device with the default's name is the default
+    let mut default_marked = false;
+    let devices: Vec<AudioDeviceInfo> = enumerate_input_devices()?
+        .into_iter()
+        .map(|(id, device)| {
+            let name = device.name().unwrap_or_default();
+            let is_default = !default_marked && default_name.as_ref() == Some(&name);
+            default_marked |= is_default;
+            AudioDeviceInfo {
+                name: id.clone(),
+                id,
+                is_default,
+            }
+        })
+        .collect();
+    
+    println!("[Audio] Found {} input devices, selected: {:?}", devices.len(), selected_mic);
+    Ok(devices)
+}
+
+/// Tauri command to get the requested capture buffer size in frames
+#[tauri::command]
+fn get_audio_buffer_size(app: AppHandle) -> Option<u32> {
+    load_audio_buffer_frames(&app)
+}
+
+/// Tauri command to request a capture buffer size in frames (None = device default).
+/// Smaller buffers make the level meter more responsive; unsupported sizes fall back to default.
+#[tauri::command]
+fn set_audio_buffer_size(app: AppHandle, frames: Option<u32>) -> Result<(), String> {
+    if frames == Some(0) {
+        return Err("Buffer size must be greater than zero".to_string());
+    }
+    save_audio_buffer_frames(&app, frames)
+}
+
+/// Tauri command to check whether shared-mode capture is on
+#[tauri::command]
+fn get_shared_capture(app: AppHandle) -> bool {
+    load_shared_capture(&app)
+}
+
+/// Tauri command to turn shared-mode capture on or off. When on, the mic is opened
+/// with the device's default format and buffer size instead of a native 16kHz
+/// format or a custom buffer, so the driver isn't reconfigured under other apps'
+/// streams (music, calls). The tradeoff is slightly higher latency: audio must be
+/// resampled and the level meter updates at the default buffer's pace.
+///
+/// cpal always opens WASAPI devices in shared mode, so exclusive access is never
+/// requested either way; ducking set by Windows' communications settings is
+/// outside the app's control.
+#[tauri::command]
+fn set_shared_capture(app: AppHandle, enabled: bool) -> Result<(), String> {
+    save_shared_capture(&app, enabled)
+}
+
+/// Tauri command to get the per-channel downmix weights
+#[tauri::command]
+fn get_channel_weights(app: AppHandle) -> Option<Vec<f32>> {
+    load_channel_weights(&app)
+}
+
+/// Tauri command to set per-channel downmix weights (None = average all channels).
+/// Weights are relative and normalized when applied; there must be one per channel
+/// of the selected microphone.
+#[tauri::command]
+fn set_channel_weights(app: AppHandle, weights: Option<Vec<f32>>) -> Result<(), String> {
+    if let Some(w) = weights.as_deref() {
+        if w.iter().any(|x| !x.is_finite() || *x < 0.0) {
+            return Err("Channel weights must be non-negative numbers".to_string());
+        }
+        if w.iter().sum::<f32>() <= 0.0 {
+            return Err("At least one channel weight must be positive".to_string());
+        }
+        let device = find_input_device(load_selected_microphone(&app).as_deref())
+            .ok_or("No input device available")?;
+        let channels = device.default_input_config()
+            .map_err(|e| format!("Failed to get input config: {:?}", e))?
+            .channels() as usize;
+        if w.len() != channels {
+            return Err(format!("Expected {} channel weights for this microphone, got {}", channels, w.len()));
+        }
+    }
+    save_channel_weights(&app, weights.as_deref())
+}
+
+/// Samples analyzed for the spectral rolloff estimate
+const ROLLOFF_WINDOW: usize = 2048;
+
+/// Share of spectral energy below the rolloff frequency
+const ROLLOFF_ENERGY: f32 = 0.85;
+
+/// Estimates the spectral rolloff of the loudest window of audio with a Hann-windowed DFT.
+/// A plain DFT is fine at this size, and keeps an FFT dependency out for a debug command.
+fn spectral_rolloff_hz(samples: &[f32], sample_rate: u32) -> f32 {
+    let len = ROLLOFF_WINDOW.min(samples.len());
+    if len < 2 {
+        return 0.0;
+    }
+    // Use the loudest window so silence at the start doesn't dominate
+    let start = (0..=samples.len() - len)
+        .step_by(len / 2)
+        .max_by(|&a, &b| compute_rms(&samples[a..a + len], len).total_cmp(&compute_rms(&samples[b..b + len], len)))
+        .unwrap_or(0);
+    let window: Vec<f32> = samples[start..start + len].iter().enumerate()
+        .map(|(n, s)| s * 0.5 * (1.0 - (2.0 * std::f32::consts::PI * n as f32 / (len - 1) as f32).cos()))
+        .collect();
+    
+    let power: Vec<f32> = (0..len / 2).map(|k| {
+        let (re, im) = window.iter().enumerate().fold((0.0f32, 0.0f32), |(re, im), (n, s)| {
+            let angle = -2.0 * std::f32::consts::PI * (k * n % len) as f32 / len as f32;
+            (re + s * angle.cos(), im + s * angle.sin())
+        });
+        re * re + im * im
+    }).collect();
+    
+    let total: f32 = power.iter().sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+    let mut cumulative = 0.0;
+    for (k, p) in power.iter().enumerate() {
+        cumulative += p;
+        if cumulative >= total * ROLLOFF_ENERGY {
+            return k as f32 * sample_rate as f32 / len as f32;
+        }
+    }
+    sample_rate as f32 / 2.0
+}
+
+/// Length of the synthetic clip benchmarked when no recording is available
+const BENCHMARK_CLIP_SECONDS: u32 = 10;
+
+/// Audio for the full benchmark: a WAV file if given, else the retained last
+/// recording, else a synthetic clip at 48kHz so the resampler is exercised too
+fn benchmark_clip(app: &AppHandle, path: Option<&str>) -> Result<(String, Vec<f32>, u32), String> {
+    if let Some(path) = path {
+        let (samples, sample_rate) = read_wav_mono(std::path::Path::new(path))?;
+        return Ok((path.to_string(), samples, sample_rate));
+    }
+    let retained = app.state::<SharedRetainedAudio>().lock()
+        .ok()
+        .and_then(|mut slot| {
+            drop_stale_retained_audio(&mut slot);
+            slot.as_ref().map(|r| (r.samples.clone(), r.sample_rate))
+        });
+    if let Some((samples, sample_rate)) = retained {
+        return Ok(("last recording".to_string(), samples, sample_rate));
+    }
+    
+    // A quiet 220Hz tone over low noise, deterministic so runs are comparable
+    let sample_rate = 48000;
+    let mut seed = 0x2545F491u32;
+    let samples = (0..sample_rate * BENCHMARK_CLIP_SECONDS)
+        .map(|i| {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            let noise = (seed as f32 / u32::MAX as f32 - 0.5) * 0.02;
+            let tone = (2.0 * std::f32::consts::PI * 220.0 * i as f32 / sample_rate as f32).sin() * 0.1;
+            tone + noise
+        })
+        .collect();
+    Ok(("synthetic".to_string(), samples, sample_rate))
+}
+
+/// Tauri command to benchmark every downloaded model on the same clip, reporting
+/// load, resample and inference times per model. Emits `benchmark_progress`.
+#[tauri::command]
+async fn run_full_benchmark(app: AppHandle, clip_path: Option<String>) -> Result<FullBenchmark, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let (clip, samples, sample_rate) = benchmark_clip(&app, clip_path.as_deref())?;
+        let clip_seconds = samples.len() as f32 / sample_rate as f32;
+        if samples.is_empty() {
+            return Err("Benchmark clip is empty".to_string());
+        }
+        
+        let models_dir = get_models_dir(&app)?;
+        let downloaded: Vec<PresetModel> = get_all_models(&app)
+            .into_iter()
+            .filter(|m| models_dir.join(&m.filename).exists())
+            .collect();
+        if downloaded.is_empty() {
+            return Err("No downloaded models to benchmark".to_string());
+        }
+        
+        let options = load_decoding_options(&app);
+        let mut models = Vec::new();
+        for (index, model) in downloaded.iter().enumerate() {
+            let _ = app.emit("benchmark_progress", serde_json::json!({
+                "model_id": model.id,
+                "index": index,
+                "total": downloaded.len(),
+            }));
+            println!("[Benchmark] Running {} ({}/{})", model.id, index + 1, downloaded.len());
+            
+            let model_path = models_dir.join(&model.filename);
+            let load_start = std::time::Instant::now();
+            let result = create_whisper_context(&app, &model_path.to_string_lossy()).and_then(|(ctx, backend)| {
+                let load_ms = load_start.elapsed().as_millis() as u64;
+                let state: SharedWhisper = Arc::new(Mutex::new(WhisperState {
+                    ctx: Some(ctx),
+                    model_path: Some(model_path.clone()),
+                    backend: Some(backend),
+                }));
+                run_whisper_on_buffer(&samples, sample_rate, &state, &options, &AbortToken::default()).map(|t| (load_ms, t))
+            });
+            
+            models.push(match result {
+                Ok((load_ms, transcription)) => ModelBenchmark {
+                    model_id: model.id.clone(),
+                    name: model.name.clone(),
+                    load_ms,
+                    resample_ms: transcription.resample_ms,
+                    inference_ms: transcription.inference_ms,
+                    realtime_factor: (transcription.resample_ms + transcription.inference_ms) as f32 / 1000.0 / clip_seconds,
+                    error: None,
+                },
+                Err(e) => {
+                    eprintln!("[Benchmark] {} failed: {}", model.id, e);
+                    ModelBenchmark {
+                        model_id: model.id.clone(),
+                        name: model.name.clone(),
+                        load_ms: 0,
+                        resample_ms: 0,
+                        inference_ms: 0,
+                        realtime_factor: 0.0,
+                        error: Some(e),
                     }
-                    _ => {}
                 }
+            });
+        }
+        
+        let _ = app.emit("benchmark_progress", serde_json::json!({
+            "index": downloaded.len(),
+            "total": downloaded.len(),
+        }));
+        Ok(FullBenchmark { clip, clip_seconds, sample_rate, models })
+    })
+    .await
+    .map_err(|e| format!("Benchmark task failed: {:?}", e))?
+}
+
+/// Tauri command for diagnosing quality issues: records a few seconds from the selected
+/// microphone at its native rate, resamples it to 16kHz and compares the signal before and after
+#[tauri::command]
+async fn preview_resampling(app: AppHandle, seconds: Option<f32>) -> Result<ResamplePreview, String> {
+    let seconds = seconds.unwrap_or(3.0).clamp(1.0, 10.0);
+    let selected_mic = load_selected_microphone(&app);
+    
+    tauri::async_runtime::spawn_blocking(move || {
+        let device = find_input_device(selected_mic.as_deref())
+            .ok_or("No input device available")?;
+        let config = device.default_input_config()
+            .map_err(|e| format!("Failed to get input config: {:?}", e))?;
+        let source_rate = config.sample_rate().0;
+        
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let stream = build_probe_stream(&device, &config, samples.clone())?;
+        stream.play().map_err(|e| format!("Failed to start stream: {:?}", e))?;
+        std::thread::sleep(std::time::Duration::from_secs_f32(seconds));
+        drop(stream);
+        let input = samples.lock().map_err(|e| format!("Lock error: {:?}", e))?.clone();
+        
+        let resample_start = std::time::Instant::now();
+        let output = resample_to_16khz(&input, source_rate)?;
+        let resample_ms = resample_start.elapsed().as_millis() as u64;
+        
+        let peak = |buf: &[f32]| buf.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        let clipped = |buf: &[f32]| buf.iter().filter(|s| s.abs() >= 1.0).count();
+        let preview = ResamplePreview {
+            source_rate,
+            input_samples: input.len(),
+            output_samples: output.len(),
+            expected_output_samples: (input.len() as u64 * WHISPER_SAMPLE_RATE as u64 / source_rate as u64) as usize,
+            input_peak: peak(&input),
+            output_peak: peak(&output[..]),
+            input_clipped: clipped(&input),
+            output_clipped: clipped(&output[..]),
+            input_rolloff_hz: spectral_rolloff_hz(&input, source_rate),
+            output_rolloff_hz: spectral_rolloff_hz(&output, WHISPER_SAMPLE_RATE),
+            resample_ms,
+        };
+        println!("[Audio] Resample preview: {}Hz -> 16kHz, {} -> {} samples, rolloff {:.0}Hz -> {:.0}Hz",
+                 source_rate, preview.input_samples, preview.output_samples,
+                 preview.input_rolloff_hz, preview.output_rolloff_hz);
+        Ok(preview)
+    })
+    .await
+    .map_err(|e| format!("Resample preview task failed: {:?}", e))?
+}
+
+/// Tauri command for onboarding: listens to the selected microphone for a few seconds,
+/// emitting live `mic_test_level` events, and returns level stats with a pass/fail verdict
+#[tauri::command]
+async fn test_mic_level(app: AppHandle, seconds: Option<f32>) -> Result<MicTestResult, String> {
+    let seconds = seconds.unwrap_or(3.0).clamp(1.0, 10.0);
+    let selected_mic = load_selected_microphone(&app);
+    
+    tauri::async_runtime::spawn_blocking(move || {
+        let device = find_input_device(selected_mic.as_deref())
+            .ok_or("No input device available")?;
+        let config = device.default_input_config()
+            .map_err(|e| format!("Failed to get input config: {:?}", e))?;
+        let sample_rate = config.sample_rate().0;
+        
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let stream = build_probe_stream(&device, &config, samples.clone())?;
+        stream.play().map_err(|e| format!("Failed to start stream: {:?}", e))?;
+        
+        let start = std::time::Instant::now();
+        while start.elapsed().as_secs_f32() < seconds {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            let level = samples.lock()
+                .map(|buf| compute_rms(&buf, sample_rate as usize / 10))
+                .unwrap_or(0.0);
+            let _ = app.emit("mic_test_level", (level * 10.0).min(1.0));
+        }
+        drop(stream);
+        
+        let buf = samples.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+        let peak = buf.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        let average_rms = compute_rms(&buf, buf.len());
+        let clipped = buf.iter().filter(|s| s.abs() >= 0.99).count();
+        let clipping_ratio = if buf.is_empty() { 0.0 } else { clipped as f32 / buf.len() as f32 };
+        
+        let verdict = if peak < 0.001 {
+            "no_signal"
+        } else if clipping_ratio > 0.01 {
+            "clipping"
+        } else if average_rms < 0.003 {
+            "too_quiet"
+        } else {
+            "pass"
+        };
+        
+        println!("[Audio] Mic test: peak {:.3}, rms {:.4}, clipping {:.2}% -> {}",
+                 peak, average_rms, clipping_ratio * 100.0, verdict);
+        Ok(MicTestResult {
+            device: device.name().unwrap_or_default(),
+            duration_seconds: buf.len() as f32 / sample_rate as f32,
+            peak,
+            average_rms,
+            clipping_ratio,
+            verdict: verdict.to_string(),
+        })
+    })
+    .await
+    .map_err(|e| format!("Mic test task failed: {:?}", e))?
+}
+
+/// How long to listen to the room when calibrating the noise floor
+const NOISE_CALIBRATION_SECS: f32 = 2.0;
+
+/// Speech threshold as a multiple of the measured noise floor
+const NOISE_FLOOR_MARGIN: f32 = 2.5;
+
+/// Records a couple of seconds of (assumed) silence from the selected microphone and
+/// stores a speech threshold a margin above its noise floor
+fn run_noise_calibration(app: &AppHandle) -> Result<NoiseCalibration, String> {
+    let device = find_input_device(load_selected_microphone(app).as_deref())
+        .ok_or("No input device available")?;
+    let config = device.default_input_config()
+        .map_err(|e| format!("Failed to get input config: {:?}", e))?;
+    let sample_rate = config.sample_rate().0;
+    
+    let samples = Arc::new(Mutex::new(Vec::new()));
+    let stream = build_probe_stream(&device, &config, samples.clone())?;
+    stream.play().map_err(|e| format!("Failed to start stream: {:?}", e))?;
+    std::thread::sleep(std::time::Duration::from_secs_f32(NOISE_CALIBRATION_SECS));
+    drop(stream);
+    
+    // Median frame level, so a single click or cough doesn't skew the floor
+    let buf = samples.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+    let frame_len = (sample_rate as usize * VAD_FRAME_MS / 1000).max(1);
+    let mut levels: Vec<f32> = buf.chunks(frame_len).map(|f| compute_rms(f, f.len())).collect();
+    if levels.is_empty() {
+        return Err("No audio captured from the microphone".to_string());
+    }
+    levels.sort_by(|a, b| a.total_cmp(b));
+    let noise_floor = levels[levels.len() / 2];
+    let threshold = (noise_floor * NOISE_FLOOR_MARGIN).max(VAD_MIN_THRESHOLD);
+    
+    let calibration = NoiseCalibration {
+        device: device.name().unwrap_or_default(),
+        noise_floor,
+        threshold,
+    };
+    update_setting(app, "vad", |vad: &mut VadSettings| {
+        vad.threshold = Some(threshold);
+        Ok(())
+    })?;
+    save_noise_calibration(app, &calibration)?;
+    
+    println!("[Audio] Noise floor {:.4} on {}, speech threshold set to {:.4}",
+             noise_floor, calibration.device, threshold);
+    let _ = app.emit("noise_floor_calibrated", &calibration);
+    Ok(calibration)
+}
+
+/// Tauri command to measure the microphone's noise floor (stay quiet while it runs)
+/// and set the VAD/endpointing speech threshold from it
+#[tauri::command]
+async fn calibrate_noise_floor(app: AppHandle) -> Result<NoiseCalibration, String> {
+    tauri::async_runtime::spawn_blocking(move || run_noise_calibration(&app))
+        .await
+        .map_err(|e| format!("Calibration task failed: {:?}", e))?
+}
+
+/// Tauri command to get the last noise floor calibration, if any
+#[tauri::command]
+fn get_noise_calibration(app: AppHandle) -> Option<NoiseCalibration> {
+    load_noise_calibration(&app)
+}
+
+/// Reads a WAV file as mono f32 samples, returning them with the file's sample rate
+fn read_wav_mono(path: &std::path::Path) -> Result<(Vec<f32>, u32), String> {
+    let mut reader = hound::WavReader::open(path)
+        .map_err(|e| format!("Failed to open WAV file: {:?}", e))?;
+    let spec = reader.spec();
+    let channels = spec.channels.max(1) as usize;
+    
+    let interleaved: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>()
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("Failed to read WAV samples: {:?}", e))?,
+        hound::SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader.samples::<i32>()
+                .map(|s| s.map(|v| v as f32 / scale))
+                .collect::<Result<_, _>>()
+                .map_err(|e| format!("Failed to read WAV samples: {:?}", e))?
+        }
+    };
+    
+    let mut mono = Vec::with_capacity(interleaved.len() / channels);
+    push_mono_samples(&interleaved, channels, &mut mono);
+    Ok((mono, spec.sample_rate))
+}
+
+/// Format of a WAV file being read while it's still written
+#[derive(Clone, Copy, Debug)]
+struct WavStreamFormat {
+    channels: u16,
+    sample_rate: u32,
+    bits_per_sample: u16,
+    float: bool,
+    data_offset: usize,  // Where sample data starts
+}
+
+impl WavStreamFormat {
+    /// Bytes per frame (one sample for every channel)
+    fn block_align(&self) -> usize {
+        self.channels as usize * (self.bits_per_sample as usize / 8)
+    }
+}
+
+/// Parses a WAV header from the start of a file. Returns `Ok(None)` until the header
+/// up to the data chunk has been written, and an error for a file that isn't a WAV
+/// or has an unsupported format. The data chunk's declared size is ignored since it
+/// isn't final while the file grows.
+fn parse_wav_stream_header(bytes: &[u8]) -> Result<Option<WavStreamFormat>, String> {
+    let u16_at = |i: usize| bytes.get(i..i + 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+    let u32_at = |i: usize| bytes.get(i..i + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+    match (bytes.get(0..4), bytes.get(8..12)) {
+        (Some(b"RIFF"), Some(b"WAVE")) => {}
+        (Some(_), Some(_)) => return Err("Not a WAV file".to_string()),
+        _ => return Ok(None),
+    }
+    let mut format = None;
+    let mut pos = 12;
+    loop {
+        let (Some(id), Some(size)) = (bytes.get(pos..pos + 4), u32_at(pos + 4)) else { return Ok(None) };
+        let size = size as usize;
+        let body = pos + 8;
+        if id == b"data" {
+            let (channels, sample_rate, bits_per_sample, float) = format
+                .ok_or("WAV data chunk comes before its format chunk")?;
+            return Ok(Some(WavStreamFormat { channels, sample_rate, bits_per_sample, float, data_offset: body }));
+        }
+        if id == b"fmt " {
+            let Some(mut tag) = u16_at(body) else { return Ok(None) };
+            if tag == 0xFFFE {
+                // WAVE_FORMAT_EXTENSIBLE: the real tag starts the subformat GUID
+                let Some(subformat) = u16_at(body + 24) else { return Ok(None) };
+                tag = subformat;
+            }
+            let (Some(channels), Some(sample_rate), Some(bits)) = (u16_at(body + 2), u32_at(body + 4), u16_at(body + 14)) else {
+                return Ok(None);
+            };
+            if !matches!((tag, bits), (1, 16) | (1, 24) | (1, 32) | (3, 32)) {
+                return Err(format!("Unsupported WAV format (tag {}, {}-bit)", tag, bits));
+            }
+            if channels == 0 || sample_rate == 0 {
+                return Err("Invalid WAV header".to_string());
+            }
+            format = Some((channels, sample_rate, bits, tag == 3));
+        }
+        // Chunks are padded to an even size
+        pos = body + size + (size & 1);
+    }
+}
+
+/// Decodes whole frames into mono samples, averaging channels like the file decoder
+fn decode_wav_frames(bytes: &[u8], format: &WavStreamFormat) -> Vec<f32> {
+    let bytes_per_sample = format.bits_per_sample as usize / 8;
+    let decode = |b: &[u8]| -> f32 {
+        match (format.float, bytes_per_sample) {
+            (true, _) => f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+            (false, 2) => i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0,
+            (false, 3) => (i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 8) as f32 / 8_388_608.0,
+            _ => i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2_147_483_648.0,
+        }
+    };
+    let whole = bytes.len() / format.block_align() * format.block_align();
+    let interleaved: Vec<f32> = bytes[..whole].chunks_exact(bytes_per_sample).map(decode).collect();
+    let mut mono = Vec::with_capacity(interleaved.len() / format.channels as usize);
+    push_mono_samples(&interleaved, format.channels as usize, &mut mono);
+    mono
+}
+
+/// Poll interval while tailing a growing WAV file
+const TAIL_POLL_MS: u64 = 250;
+
+/// Seconds of appended audio transcribed at a time in tail mode
+const TAIL_CHUNK_SECONDS: usize = 5;
+
+/// Seconds of the previous chunk decoded again at the start of the next
+const TAIL_OVERLAP_SECONDS: usize = 1;
+
+/// Most bytes read from the tailed file per poll, so a large file that already
+/// exists is worked through a piece at a time instead of loaded whole
+const TAIL_READ_MAX_BYTES: u64 = 1 << 20;
+
+/// Bytes the WAV header may take before the data chunk; anything longer is treated
+/// as a file that isn't going to become a readable WAV
+const TAIL_MAX_HEADER_BYTES: usize = 64 * 1024;
+
+/// Once the file stops growing for this long, a shorter chunk is transcribed
+/// rather than waiting for a full one
+const TAIL_IDLE_FLUSH_MS: u64 = 1500;
+
+/// Stop flag of the running tail transcription, if any
+pub type SharedTail = Arc<Mutex<Option<Arc<AtomicBool>>>>;
+
+/// Appends bytes written to the file since `pos` to `pending`, at most `TAIL_READ_MAX_BYTES`,
+/// returning how many were read
+fn read_appended(path: &std::path::Path, pos: &mut u64, pending: &mut Vec<u8>) -> Result<usize, String> {
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| format!("Failed to open tailed file: {:?}", e))?;
+    let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+    if len < *pos {
+        return Err("Tailed file was truncated".to_string());
+    }
+    std::io::Seek::seek(&mut file, std::io::SeekFrom::Start(*pos))
+        .and_then(|_| std::io::Read::read_to_end(&mut std::io::Read::take(file, TAIL_READ_MAX_BYTES), pending))
+        .map(|read| {
+            *pos += read as u64;
+            read
+        })
+        .map_err(|e| format!("Failed to read tailed file: {:?}", e))
+}
+
+/// Transcribes one chunk of tailed audio and emits it as `tail_transcription`. The
+/// chunk starts with audio the previous one ended with, so words `previous_text`
+/// already ended with are left out of the event. Returns the chunk's full transcript.
+fn transcribe_tail_chunk(
+    app: &AppHandle,
+    path: &std::path::Path,
+    chunk: &mut [f32],
+    sample_rate: u32,
+    offset_ms: u64,
+    previous_text: &str,
+    whisper_state: &SharedWhisper,
+) -> String {
+    condition_audio(app, chunk, sample_rate);
+    let filtered = apply_vad_filter(app, chunk, sample_rate);
+    let decoding = load_decoding_options(app);
+    match run_whisper_on_buffer(filtered.as_deref().unwrap_or(chunk), sample_rate, whisper_state, &decoding, &AbortToken::default()) {
+        Ok(transcription) => {
+            let full = post_process_transcript(app, &transcription.text);
+            let text = match find_text_overlap(previous_text, &full) {
+                Some(cut) => {
+                    let repeated = previous_text[cut..].split_whitespace().count();
+                    full.split_whitespace()
+                        .nth(repeated)
+                        .map_or("", |w| &full[w.as_ptr() as usize - full.as_ptr() as usize..])
+                }
+                None => full.as_str(),
+            };
+            if !text.is_empty() {
+                println!("[Tail] {}ms: \"{}\"", offset_ms, text);
+                let _ = app.emit("tail_transcription", serde_json::json!({
+                    "path": path.to_string_lossy(),
+                    "text": text,
+                    "offset_ms": offset_ms,
+                }));
+            }
+            full
+        }
+        Err(e) => {
+            eprintln!("[Tail] Transcription failed: {}", e);
+            let _ = app.emit("tail_transcription_error", e);
+            String::new()
+        }
+    }
+}
+
+/// Watches a WAV file another program is writing and transcribes newly appended
+/// audio in chunks until stopped. Only whole frames are decoded; a partial frame
+/// at the end of a read waits for the rest to be written. A file that turns out not
+/// to be a supported WAV stops the tail with a `tail_transcription_error`.
+fn tail_wav_file(app: AppHandle, path: PathBuf, stop: Arc<AtomicBool>, whisper_state: SharedWhisper) {
+    let mut pos = 0u64;
+    let mut pending = Vec::new();
+    let mut format: Option<WavStreamFormat> = None;
+    let mut audio: Vec<f32> = Vec::new();
+    let mut previous_tail: Vec<f32> = Vec::new();
+    let mut previous_text = String::new();
+    let mut transcribed_samples = 0usize;
+    let mut last_growth = std::time::Instant::now();
+    
+    loop {
+        let stopping = stop.load(Ordering::SeqCst);
+        let read = match read_appended(&path, &mut pos, &mut pending) {
+            Ok(read) => read,
+            Err(e) => {
+                eprintln!("[Tail] {}", e);
+                let _ = app.emit("tail_transcription_error", e);
+                break;
+            }
+        };
+        if read > 0 {
+            last_growth = std::time::Instant::now();
+        }
+        if format.is_none() {
+            let parsed = parse_wav_stream_header(&pending).and_then(|parsed| match parsed {
+                None if pending.len() > TAIL_MAX_HEADER_BYTES => Err("No WAV data found".to_string()),
+                parsed => Ok(parsed),
+            });
+            match parsed {
+                Ok(parsed) => format = parsed,
+                Err(e) => {
+                    eprintln!("[Tail] {}: {}", path.display(), e);
+                    let _ = app.emit("tail_transcription_error", e);
+                    break;
+                }
+            }
+            if let Some(f) = &format {
+                println!("[Tail] {} is {}Hz, {} channels, {}-bit", path.display(), f.sample_rate, f.channels, f.bits_per_sample);
+                pending.drain(..f.data_offset);
+            }
+        }
+        
+        if let Some(f) = &format {
+            let whole = pending.len() / f.block_align() * f.block_align();
+            audio.extend(decode_wav_frames(&pending[..whole], f));
+            pending.drain(..whole);
+            
+            let rate = f.sample_rate as usize;
+            let chunk_len = TAIL_CHUNK_SECONDS * rate;
+            let idle = last_growth.elapsed() >= std::time::Duration::from_millis(TAIL_IDLE_FLUSH_MS);
+            while audio.len() >= chunk_len || (idle && audio.len() >= rate) || (stopping && !audio.is_empty()) {
+                let take = audio.len().min(chunk_len);
+                // Lead in with the end of the previous chunk so boundary words are heard whole
+                let lead_in = previous_tail.len();
+                let mut chunk = std::mem::take(&mut previous_tail);
+                chunk.extend(audio.drain(..take));
+                previous_tail = chunk[chunk.len().saturating_sub(TAIL_OVERLAP_SECONDS * rate)..].to_vec();
+                let offset_ms = (transcribed_samples.saturating_sub(lead_in) as u64 * 1000) / rate as u64;
+                previous_text = transcribe_tail_chunk(&app, &path, &mut chunk, f.sample_rate, offset_ms, &previous_text, &whisper_state);
+                transcribed_samples += take;
             }
-        };
-
-        if let Err(err) = listen(callback) {
-            eprintln!("Error listening to keyboard: {:?}", err);
         }
-    });
-}
-
-// Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
-#[tauri::command]
-fn greet(name: &str) -> String {
-    format!("Hello, {}! You've been greeted from Rust!", name)
+        
+        if stopping {
+            break;
+        }
+        // Keep reading right away while a large backlog is being worked through
+        if read < TAIL_READ_MAX_BYTES as usize {
+            std::thread::sleep(std::time::Duration::from_millis(TAIL_POLL_MS));
+        }
+    }
+    println!("[Tail] Stopped tailing {}", path.display());
+    let _ = app.emit("tail_transcription_stopped", path.to_string_lossy());
 }
 
-/// Tauri command to set the active Whisper model
+/// Tauri command to start transcribing a WAV file as another program appends to
+/// it, emitting `tail_transcription` per chunk. Replaces any running tail.
 #[tauri::command]
-fn set_active_model(path: String, state: tauri::State<SharedWhisper>) -> Result<String, String> {
-    println!("[Whisper] Loading model from: {}", path);
-    
-    let model_path = PathBuf::from(&path);
-    
-    if !model_path.exists() {
-        return Err(format!("Model file not found: {}", path));
+fn start_tail_transcription(
+    app: AppHandle,
+    path: String,
+    whisper_state: tauri::State<SharedWhisper>,
+    tail: tauri::State<SharedTail>,
+) -> Result<(), String> {
+    let path = PathBuf::from(path);
+    if !path.exists() {
+        return Err(format!("File not found: {}", path.display()));
+    }
+    let model_loaded = whisper_state.lock().map(|ws| ws.ctx.is_some()).unwrap_or(false);
+    if !model_loaded {
+        return Err("No model loaded".to_string());
     }
     
-    // Load the Whisper context
-    let ctx = WhisperContext::new_with_params(&path, WhisperContextParameters::default())
-        .map_err(|e| format!("Failed to load Whisper model: {:?}", e))?;
-    
-    // Store in state
-    let mut ws = state.lock().map_err(|e| format!("Lock error: {:?}", e))?;
-    ws.ctx = Some(ctx);
-    ws.model_path = Some(model_path);
-    
-    println!("[Whisper] Model loaded successfully");
+    let mut tail = tail.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+    if let Some(previous) = tail.take() {
+        previous.store(true, Ordering::SeqCst);
+    }
+    let stop = Arc::new(AtomicBool::new(false));
+    *tail = Some(stop.clone());
     
-    Ok(format!("Model loaded: {}", path))
+    println!("[Tail] Tailing {}", path.display());
+    let whisper_state = whisper_state.inner().clone();
+    std::thread::spawn(move || tail_wav_file(app, path, stop, whisper_state));
+    Ok(())
 }
 
-/// Tauri command to get current model path
+/// Tauri command to stop tail transcription; audio already appended is still transcribed
 #[tauri::command]
-fn get_active_model(state: tauri::State<SharedWhisper>) -> Option<String> {
-    let ws = state.lock().ok()?;
-    ws.model_path.as_ref().map(|p| p.to_string_lossy().to_string())
+fn stop_tail_transcription(tail: tauri::State<SharedTail>) -> Result<(), String> {
+    let mut tail = tail.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+    match tail.take() {
+        Some(stop) => {
+            stop.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+        None => Err("Tail transcription is not running".to_string()),
+    }
 }
 
-/// Get the models directory path
-fn get_models_dir(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app.path().app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {:?}", e))?;
-    let models_dir = app_data_dir.join("models");
-    
-    // Create directory if it doesn't exist
-    if !models_dir.exists() {
-        std::fs::create_dir_all(&models_dir)
-            .map_err(|e| format!("Failed to create models directory: {:?}", e))?;
-    }
-    
-    Ok(models_dir)
+/// Formats a timestamp for subtitles: `HH:MM:SS,mmm` for SRT, `HH:MM:SS.mmm` for WebVTT
+fn format_subtitle_time(ms: u64, separator: char) -> String {
+    format!("{:02}:{:02}:{:02}{}{:03}",
+            ms / 3_600_000, ms / 60_000 % 60, ms / 1000 % 60, separator, ms % 1000)
 }
 
-/// Get the config file path
-fn get_config_path(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app.path().app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {:?}", e))?;
+/// Renders transcript segments as an SRT or WebVTT document
+fn format_subtitles(segments: &[TranscriptSegment], format: &str) -> Result<String, String> {
+    let (header, separator) = match format {
+        "srt" => ("", ','),
+        "vtt" => ("WEBVTT\n\n", '.'),
+        other => return Err(format!("Unsupported subtitle format: {}", other)),
+    };
     
-    // Create directory if it doesn't exist
-    if !app_data_dir.exists() {
-        std::fs::create_dir_all(&app_data_dir)
-            .map_err(|e| format!("Failed to create app data directory: {:?}", e))?;
+    let mut out = String::from(header);
+    for (i, segment) in segments.iter().enumerate() {
+        // SRT cues are numbered from 1; the identifier is optional in WebVTT but harmless
+        out.push_str(&format!("{}\n{} --> {}\n{}\n\n",
+                              i + 1,
+                              format_subtitle_time(segment.start_ms, separator),
+                              format_subtitle_time(segment.end_ms, separator),
+                              segment.text));
     }
-    
-    Ok(app_data_dir.join("config.json"))
+    Ok(out)
 }
 
-/// Load the full config
-fn load_config(app: &AppHandle) -> serde_json::Value {
-    let config_path = match get_config_path(app) {
-        Ok(p) => p,
-        Err(_) => return serde_json::json!({}),
-    };
-    
-    if !config_path.exists() {
-        return serde_json::json!({});
+/// Tauri command to transcribe a WAV file into an SRT or WebVTT subtitle file.
+/// Cues follow Whisper's segments, so a max segment length set with
+/// `set_max_segment_length` is what keeps long cues short.
+#[tauri::command]
+async fn transcribe_file_to_subtitles(
+    app: AppHandle,
+    input_path: String,
+    format: String,
+    output_path: String,
+    whisper_state: tauri::State<'_, SharedWhisper>,
+) -> Result<usize, String> {
+    let format = format.to_lowercase();
+    if format != "srt" && format != "vtt" {
+        return Err(format!("Unsupported subtitle format: {}", format));
     }
+    let whisper_state = whisper_state.inner().clone();
     
-    std::fs::read_to_string(&config_path)
-        .ok()
-        .and_then(|contents| serde_json::from_str(&contents).ok())
-        .unwrap_or(serde_json::json!({}))
+    tauri::async_runtime::spawn_blocking(move || {
+        let (samples, sample_rate) = read_wav_mono(std::path::Path::new(&input_path))?;
+        println!("[Subtitles] Transcribing {} ({:.1}s)", input_path, samples.len() as f32 / sample_rate as f32);
+        let transcription = run_whisper_on_buffer(&samples, sample_rate, &whisper_state, &load_decoding_options(&app), &AbortToken::default())?;
+        
+        let segments: Vec<TranscriptSegment> = transcription.segments.into_iter()
+            .map(|segment| TranscriptSegment {
+                text: post_process_transcript(&app, &segment.text),
+                ..segment
+            })
+            .filter(|segment| !segment.text.is_empty())
+            .collect();
+        
+        std::fs::write(&output_path, format_subtitles(&segments, &format)?)
+            .map_err(|e| format!("Failed to write subtitles: {:?}", e))?;
+        println!("[Subtitles] Wrote {} cues to {}", segments.len(), output_path);
+        Ok(segments.len())
+    })
+    .await
+    .map_err(|e| format!("Subtitle task failed: {:?}", e))?
 }
 
-/// Save the full config
-fn save_config(app: &AppHandle, config: &serde_json::Value) -> Result<(), String> {
-    let config_path = get_config_path(app)?;
-    std::fs::write(&config_path, serde_json::to_string_pretty(config).unwrap())
-        .map_err(|e| format!("Failed to save config: {:?}", e))?;
-    Ok(())
+/// Tauri command to get the currently selected microphone
+#[tauri::command]
+fn get_selected_microphone(app: AppHandle) -> Option<String> {
+    load_selected_microphone(&app)
 }
 
-/// Save the selected model ID to config
-fn save_selected_model(app: &AppHandle, model_id: &str) -> Result<(), String> {
-    let mut config = load_config(app);
-    config["selected_model"] = serde_json::json!(model_id);
-    save_config(app, &config)?;
-    println!("[Config] Saved selected model: {}", model_id);
-    Ok(())
+/// Tauri command to list audio output devices cues can play on
+#[tauri::command]
+fn list_output_devices() -> Result<Vec<AudioDeviceInfo>, String> {
+    let default_name = cpal::default_host().default_output_device().and_then(|d| d.name().ok());
+    let mut default_marked = false;
+    let devices = enumerate_output_devices()?
+        .into_iter()
+        .map(|(id, device)| {
+            let name = device.name().unwrap_or_default();
+            let is_default = !default_marked && default_name.as_ref() == Some(&name);
+            default_marked |= is_default;
+            AudioDeviceInfo {
+                name: id.clone(),
+                id,
+                is_default,
+            }
+        })
+        .collect();
+    Ok(devices)
 }
 
-/// Load the selected model ID from config
-fn load_selected_model(app: &AppHandle) -> Option<String> {
-    let config = load_config(app);
-    config.get("selected_model")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
+/// Tauri command to get the output device cues play on (None = default output)
+#[tauri::command]
+fn get_cue_output_device(app: AppHandle) -> Option<String> {
+    load_cue_output_device(&app)
 }
 
-/// Save the selected microphone to config
-fn save_selected_microphone(app: &AppHandle, device_name: Option<&str>) -> Result<(), String> {
-    let mut config = load_config(app);
-    config["selected_microphone"] = match device_name {
-        Some(name) => serde_json::json!(name),
-        None => serde_json::Value::Null,
-    };
-    save_config(app, &config)?;
-    println!("[Config] Saved selected microphone: {:?}", device_name);
-    Ok(())
+/// Tauri command to choose the output device cues play on (None = default output)
+#[tauri::command]
+fn set_cue_output_device(app: AppHandle, device_id: Option<String>) -> Result<(), String> {
+    if let Some(id) = device_id.as_deref() {
+        if !enumerate_output_devices()?.iter().any(|(device_id, _)| device_id == id) {
+            return Err(format!("Output device not found: {}", id));
+        }
+    }
+    save_cue_output_device(&app, device_id.as_deref())
 }
 
-/// Load the selected microphone from config
-fn load_selected_microphone(app: &AppHandle) -> Option<String> {
-    let config = load_config(app);
-    config.get("selected_microphone")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
+/// Tauri command to check whether start/stop cue tones are played
+#[tauri::command]
+fn get_audio_cues(app: AppHandle) -> bool {
+    load_audio_cues(&app)
 }
 
-/// Auto-load the previously selected model on startup
-fn auto_load_model(app: &AppHandle, whisper_state: &SharedWhisper) {
-    if let Some(model_id) = load_selected_model(app) {
-        println!("[Startup] Found saved model: {}", model_id);
-        
-        let presets = get_preset_models();
-        if let Some(preset) = presets.iter().find(|p| p.id == model_id) {
-            if let Ok(models_dir) = get_models_dir(app) {
-                let model_path = models_dir.join(&preset.filename);
-                
-                if model_path.exists() {
-                    let path_str = model_path.to_string_lossy().to_string();
-                    println!("[Startup] Auto-loading model from: {}", path_str);
-                    
-                    match WhisperContext::new_with_params(&path_str, WhisperContextParameters::default()) {
-                        Ok(ctx) => {
-                            if let Ok(mut ws) = whisper_state.lock() {
-                                ws.ctx = Some(ctx);
-                                ws.model_path = Some(model_path);
-                                println!("[Startup] Model loaded successfully: {}", preset.name);
-                            }
-                        }
-                        Err(e) => {
-                            eprintln!("[Startup] Failed to load model: {:?}", e);
-                        }
-                    }
-                } else {
-                    println!("[Startup] Saved model not downloaded: {}", preset.filename);
-                }
+/// Tauri command to turn start/stop cue tones on or off
+#[tauri::command]
+fn set_audio_cues(app: AppHandle, enabled: bool) -> Result<(), String> {
+    save_audio_cues(&app, enabled)
+}
+
+/// Tauri command to set the selected microphone
+#[tauri::command]
+fn set_selected_microphone(app: AppHandle, device_name: Option<String>) -> Result<(), String> {
+    let changed = load_selected_microphone(&app) != device_name;
+    save_selected_microphone(&app, device_name.as_deref())?;
+    
+    // A calibrated threshold belongs to the old mic, so measure the new one
+    if changed && load_noise_calibration(&app).is_some() {
+        std::thread::spawn(move || {
+            if let Err(e) = run_noise_calibration(&app) {
+                eprintln!("[Audio] Recalibration after device change failed: {}", e);
             }
-        }
+        });
     }
+    Ok(())
 }
 
-/// Tauri command to list all preset models with their status
+/// Tauri command to list connected monitors
 #[tauri::command]
-fn list_models(app: AppHandle, whisper_state: tauri::State<SharedWhisper>) -> Result<Vec<ModelInfo>, String> {
-    let models_dir = get_models_dir(&app)?;
-    let presets = get_preset_models();
-    
-    let active_path = whisper_state.lock()
+fn list_monitors(app: AppHandle) -> Result<Vec<MonitorInfo>, String> {
+    let primary_name = app.primary_monitor()
         .ok()
-        .and_then(|ws| ws.model_path.clone());
+        .flatten()
+        .and_then(|m| m.name().cloned());
     
-    let models: Vec<ModelInfo> = presets.iter().map(|preset| {
-        let model_path = models_dir.join(&preset.filename);
-        let downloaded = model_path.exists();
-        let active = active_path.as_ref().map_or(false, |p| p == &model_path);
-        
-        ModelInfo {
-            id: preset.id.clone(),
-            name: preset.name.clone(),
-            filename: preset.filename.clone(),
-            size: preset.size.clone(),
-            downloaded,
-            active,
-        }
-    }).collect();
+    let monitors = app.available_monitors()
+        .map_err(|e| format!("Failed to enumerate monitors: {:?}", e))?
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            let name = m.name().cloned().unwrap_or_else(|| format!("Monitor {}", i + 1));
+            MonitorInfo {
+                id: monitor_id(m, i),
+                is_primary: primary_name.as_ref() == m.name(),
+                name,
+                x: m.position().x,
+                y: m.position().y,
+                width: m.size().width,
+                height: m.size().height,
+                scale_factor: m.scale_factor(),
+            }
+        })
+        .collect();
     
-    Ok(models)
+    Ok(monitors)
 }
 
-/// Tauri command to download a model
+/// Tauri command to get the monitor the overlay is pinned to
 #[tauri::command]
-async fn download_model(app: AppHandle, model_id: String) -> Result<String, String> {
-    let presets = get_preset_models();
-    let preset = presets.iter()
-        .find(|p| p.id == model_id)
-        .ok_or_else(|| format!("Unknown model: {}", model_id))?
-        .clone();
-    
-    let models_dir = get_models_dir(&app)?;
-    let model_path = models_dir.join(&preset.filename);
-    
-    // Check if already downloaded
-    if model_path.exists() {
-        return Ok(format!("Model already downloaded: {}", preset.filename));
+fn get_overlay_monitor(app: AppHandle) -> Option<String> {
+    load_overlay_monitor(&app)
+}
+
+/// Tauri command to pin the overlay to a monitor (None = primary)
+#[tauri::command]
+fn set_overlay_monitor(app: AppHandle, monitor_id: Option<String>) -> Result<(), String> {
+    save_overlay_monitor(&app, monitor_id.as_deref())
+}
+
+/// Tauri command to get the overlay fade animation duration in milliseconds
+#[tauri::command]
+fn get_overlay_animation_ms(app: AppHandle) -> u64 {
+    load_overlay_animation_ms(&app)
+}
+
+/// Tauri command to set the overlay fade animation duration (0 disables the fade)
+#[tauri::command]
+fn set_overlay_animation_ms(app: AppHandle, ms: u64) -> Result<(), String> {
+    if ms > MAX_OVERLAY_ANIMATION_MS {
+        return Err(format!("Animation duration must be at most {}ms", MAX_OVERLAY_ANIMATION_MS));
     }
+    save_overlay_animation_ms(&app, ms)
+}
+
+/// Tauri command to get the overlay size and opacity
+#[tauri::command]
+fn get_overlay_appearance(app: AppHandle) -> OverlayAppearance {
+    load_overlay_appearance(&app)
+}
+
+/// Tauri command to set the overlay size and opacity (clamped to sane ranges).
+/// Returns the values actually applied.
+#[tauri::command]
+fn set_overlay_appearance(app: AppHandle, appearance: OverlayAppearance) -> Result<OverlayAppearance, String> {
+    let appearance = appearance.clamped();
+    save_overlay_appearance(&app, &appearance)?;
     
-    println!("[Download] Starting download of {} from {}", preset.filename, preset.url);
-    let _ = app.emit("download_started", &model_id);
-    
-    // Download the file
-    let client = reqwest::Client::new();
-    let response = client.get(&preset.url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to start download: {:?}", e))?;
-    
-    let total_size = response.content_length().unwrap_or(0);
-    
-    // Create temp file
-    let temp_path = model_path.with_extension("tmp");
-    let mut file = tokio::fs::File::create(&temp_path)
-        .await
-        .map_err(|e| format!("Failed to create temp file: {:?}", e))?;
-    
-    let mut downloaded: u64 = 0;
-    let mut stream = response.bytes_stream();
-    
-    while let Some(chunk) = stream.next().await {
-        let chunk = chunk.map_err(|e| format!("Download error: {:?}", e))?;
-        
-        tokio::io::AsyncWriteExt::write_all(&mut file, &chunk)
-            .await
-            .map_err(|e| format!("Failed to write chunk: {:?}", e))?;
-        
-        downloaded += chunk.len() as u64;
-        
-        // Emit progress (throttled to avoid too many events)
-        if total_size > 0 {
-            let progress = (downloaded as f64 / total_size as f64 * 100.0) as u32;
-            let _ = app.emit("download_progress", serde_json::json!({
-                "model_id": model_id,
-                "progress": progress,
-                "downloaded": downloaded,
-                "total": total_size
-            }));
+    // Apply right away if the overlay is on screen, so changes can be previewed
+    if let Some(overlay) = overlay_window(&app) {
+        if overlay.is_visible().unwrap_or(false) {
+            let _ = overlay.set_size(tauri::LogicalSize::new(appearance.width, appearance.height));
+            let _ = app.emit("overlay_appearance", &appearance);
         }
     }
-    
-    // Rename temp file to final path
-    tokio::fs::rename(&temp_path, &model_path)
+    Ok(appearance)
+}
+
+/// Tauri command to get the find/replace rules
+#[tauri::command]
+fn get_replacement_rules(app: AppHandle) -> Vec<ReplacementRule> {
+    load_replacement_rules(&app)
+}
+
+/// Tauri command to set the find/replace rules
+#[tauri::command]
+fn set_replacement_rules(app: AppHandle, rules: Vec<ReplacementRule>) -> Result<(), String> {
+    save_replacement_rules(&app, &rules)
+}
+
+/// Tauri command to preview how the current replacement rules transform sample text
+#[tauri::command]
+fn preview_replacements(app: AppHandle, text: String) -> ReplacementPreview {
+    apply_replacement_rules(&text, &load_replacement_rules(&app))
+}
+
+/// Tauri command to get the number formatting settings
+#[tauri::command]
+fn get_number_formatting(app: AppHandle) -> NumberFormatting {
+    load_number_formatting(&app)
+}
+
+/// Tauri command to set number formatting ("off", "digits" or "words")
+#[tauri::command]
+fn set_number_formatting(app: AppHandle, mode: String, locale: Option<String>) -> Result<(), String> {
+    if !["off", "digits", "words"].contains(&mode.as_str()) {
+        return Err(format!("Unknown number formatting mode: {}", mode));
+    }
+    let settings = NumberFormatting {
+        mode,
+        locale: locale.unwrap_or_else(default_number_locale),
+    };
+    save_number_formatting(&app, &settings)
+}
+
+/// Tauri command to get the preferred and actually-selected GPU backend
+#[tauri::command]
+fn get_gpu_backend(app: AppHandle, state: tauri::State<SharedWhisper>) -> GpuBackendInfo {
+    let selected = state.lock().ok().and_then(|ws| ws.backend.clone());
+    GpuBackendInfo {
+        preferred: load_gpu_settings(&app).backend,
+        selected,
+        available: compiled_gpu_backends().into_iter().map(String::from).collect(),
+    }
+}
+
+/// Tauri command to set the GPU backend preference. Applies on the next model load.
+#[tauri::command]
+fn set_gpu_backend(app: AppHandle, backend: String, device: Option<i32>) -> Result<(), String> {
+    if !["auto", "cpu", "cuda", "vulkan", "metal"].contains(&backend.as_str()) {
+        return Err(format!("Unknown GPU backend: {}", backend));
+    }
+    let settings = GpuSettings {
+        backend,
+        device: device.unwrap_or(0).max(0),
+    };
+    save_gpu_settings(&app, &settings)
+}
+
+/// Tauri command to check whether models load on a GPU backend (any preference but "cpu")
+#[tauri::command]
+fn get_gpu_enabled(app: AppHandle) -> bool {
+    load_gpu_settings(&app).backend != "cpu"
+}
+
+/// Tauri command to turn GPU acceleration on or off. Turning it on picks the best
+/// compiled backend unless one was already chosen. Applies on the next model load.
+#[tauri::command]
+fn set_gpu_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let backend = update_setting(&app, "gpu", |settings: &mut GpuSettings| {
+        match (enabled, settings.backend.as_str()) {
+            (false, _) => settings.backend = "cpu".to_string(),
+            (true, "cpu") => settings.backend = "auto".to_string(),
+            (true, _) => {}
+        }
+        Ok(settings.backend.clone())
+    })?;
+    println!("[Config] Saved GPU backend preference: {}", backend);
+    Ok(())
+}
+
+/// Tauri command to get the keyboard listener failure, if any
+#[tauri::command]
+fn get_hotkey_listener_status(state: tauri::State<SharedHotkeyStatus>) -> serde_json::Value {
+    let failure = state.lock().ok().and_then(|s| s.clone());
+    serde_json::json!({
+        "running": failure.is_none(),
+        "reason": failure,
+        "remediation": hotkey_listener_remediation(),
+    })
+}
+
+/// Tauri command to re-run the GPU validation inference
+#[tauri::command]
+async fn validate_gpu(app: AppHandle, state: tauri::State<'_, SharedWhisper>) -> Result<serde_json::Value, String> {
+    let whisper_state = state.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || run_gpu_validation(&app, &whisper_state))
         .await
-        .map_err(|e| format!("Failed to rename temp file: {:?}", e))?;
-    
-    println!("[Download] Completed: {}", preset.filename);
-    let _ = app.emit("download_complete", &model_id);
-    
-    Ok(format!("Downloaded: {}", preset.filename))
+        .map_err(|e| format!("GPU validation task failed: {:?}", e))
 }
 
-/// Tauri command to load a model by ID
+/// Tauri command to get recent latency breakdowns, oldest first
 #[tauri::command]
-fn load_model(app: AppHandle, model_id: String, state: tauri::State<SharedWhisper>) -> Result<String, String> {
-    let presets = get_preset_models();
-    let preset = presets.iter()
-        .find(|p| p.id == model_id)
-        .ok_or_else(|| format!("Unknown model: {}", model_id))?;
-    
-    let models_dir = get_models_dir(&app)?;
-    let model_path = models_dir.join(&preset.filename);
-    
-    if !model_path.exists() {
-        return Err(format!("Model not downloaded: {}", preset.filename));
+fn get_latency_history(state: tauri::State<SharedLatencyHistory>) -> Vec<LatencyBreakdown> {
+    state.lock().map(|h| h.iter().cloned().collect()).unwrap_or_default()
+}
+
+/// Tauri command the overlay calls when its listeners are mounted and whenever it
+/// is shown. Levels emitted before it was listening were missed, so it gets the
+/// current recording's levels so far, oldest first (empty when not recording).
+#[tauri::command]
+fn overlay_ready(
+    recording_state: tauri::State<Arc<RecordingState>>,
+    levels: tauri::State<SharedLevelHistory>,
+) -> Vec<f32> {
+    if !recording_state.is_recording.load(Ordering::SeqCst) {
+        return Vec::new();
+    }
+    levels.lock().map(|l| l.iter().copied().collect()).unwrap_or_default()
+}
+
+/// Tauri command to pause the current recording without transcribing.
+/// The audio stream is closed but captured audio is kept for `resume_recording`.
+#[tauri::command]
+fn pause_recording(
+    app: AppHandle,
+    recording_state: tauri::State<Arc<RecordingState>>,
+    audio_ctx: tauri::State<SharedAudio>,
+) -> Result<(), String> {
+    if !recording_state.is_recording.load(Ordering::SeqCst) {
+        return Err("Not recording".to_string());
+    }
+    if recording_state.is_paused.swap(true, Ordering::SeqCst) {
+        return Ok(());
     }
     
-    let path_str = model_path.to_string_lossy().to_string();
-    println!("[Whisper] Loading model from: {}", path_str);
-    
-    // Load the Whisper context
-    let ctx = WhisperContext::new_with_params(&path_str, WhisperContextParameters::default())
-        .map_err(|e| format!("Failed to load Whisper model: {:?}", e))?;
-    
-    // Store in state
-    let mut ws = state.lock().map_err(|e| format!("Lock error: {:?}", e))?;
-    ws.ctx = Some(ctx);
-    ws.model_path = Some(model_path);
-    
-    // Save the selection to config
-    let _ = save_selected_model(&app, &model_id);
+    if let Ok(ctx) = audio_ctx.lock() {
+        ctx.stop_signal.store(true, Ordering::SeqCst);
+    }
+    // Wait for the stream thread to exit, so a quick resume doesn't race it
+    wait_for_stream_stopped(&audio_ctx);
     
-    println!("[Whisper] Model loaded successfully: {}", preset.name);
+    println!("[Audio] Recording paused");
+    let _ = app.emit("recording_paused", ());
+    Ok(())
+}
+
+/// Tauri command to resume a paused recording, appending to the same buffer
+#[tauri::command]
+fn resume_recording(
+    app: AppHandle,
+    recording_state: tauri::State<Arc<RecordingState>>,
+    audio_ctx: tauri::State<SharedAudio>,
+) -> Result<(), String> {
+    if !recording_state.is_recording.load(Ordering::SeqCst) {
+        return Err("Not recording".to_string());
+    }
+    if !recording_state.is_paused.swap(false, Ordering::SeqCst) {
+        return Ok(());
+    }
     
-    Ok(format!("Loaded: {}", preset.name))
+    println!("[Audio] Recording resumed");
+    let _ = app.emit("recording_resumed", ());
+    start_audio_recording(app.clone(), audio_ctx.inner().clone(), true);
+    Ok(())
 }
 
-/// Tauri command to check if autostart is enabled
+/// Tauri command to get the retained-recording bounds
 #[tauri::command]
-fn get_autostart_enabled(app: AppHandle) -> Result<bool, String> {
-    use tauri_plugin_autostart::ManagerExt;
-    app.autolaunch()
-        .is_enabled()
-        .map_err(|e| format!("Failed to check autostart: {:?}", e))
+fn get_retention_settings(app: AppHandle) -> RetentionSettings {
+    load_retention_settings(&app)
 }
 
-/// Tauri command to set autostart enabled/disabled
+/// Tauri command to set the retained-recording bounds. Applies from the next recording.
 #[tauri::command]
-fn set_autostart_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
-    use tauri_plugin_autostart::ManagerExt;
-    let autostart = app.autolaunch();
-    
-    if enabled {
-        autostart.enable().map_err(|e| format!("Failed to enable autostart: {:?}", e))
-    } else {
-        autostart.disable().map_err(|e| format!("Failed to disable autostart: {:?}", e))
+fn set_retention_settings(app: AppHandle, max_seconds: u64, timeout_seconds: u64) -> Result<(), String> {
+    save_retention_settings(&app, &RetentionSettings { max_seconds, timeout_seconds })
+}
+
+/// Tauri command to drop the retained recording and free its memory
+#[tauri::command]
+fn clear_retained_audio(retained: tauri::State<SharedRetainedAudio>) {
+    if let Ok(mut slot) = retained.lock() {
+        *slot = None;
     }
 }
 
-/// Tauri command to list available audio input devices
+/// Tauri command to re-run transcription on the retained last recording (no paste)
 #[tauri::command]
-fn list_audio_devices(app: AppHandle) -> Result<Vec<AudioDeviceInfo>, String> {
-    let host = cpal::default_host();
-    let default_device = host.default_input_device();
-    let default_name = default_device.as_ref().and_then(|d| d.name().ok());
-    
-    // Get saved selection
-    let selected_mic = load_selected_microphone(&app);
+async fn retranscribe_last(
+    app: AppHandle,
+    retained: tauri::State<'_, SharedRetainedAudio>,
+    whisper_state: tauri::State<'_, SharedWhisper>,
+) -> Result<String, String> {
+    let (mut samples, sample_rate) = {
+        let mut slot = retained.lock().map_err(|e| format!("Lock error: {:?}", e))?;
+        drop_stale_retained_audio(&mut slot);
+        slot.as_ref()
+            .map(|r| (r.samples.clone(), r.sample_rate))
+            .ok_or("No retained recording")?
+    };
+    let whisper_state = whisper_state.inner().clone();
     
-    let devices: Vec<AudioDeviceInfo> = host
-        .input_devices()
-        .map_err(|e| format!("Failed to enumerate devices: {:?}", e))?
-        .filter_map(|device| {
-            let name = device.name().ok()?;
-            let is_default = default_name.as_ref().map_or(false, |d| d == &name);
-            Some(AudioDeviceInfo {
-                id: name.clone(),
-                name,
-                is_default,
-            })
-        })
-        .collect();
+    tauri::async_runtime::spawn_blocking(move || {
+        let options = load_decoding_options(&app);
+        condition_audio(&app, &mut samples, sample_rate);
+        let filtered = apply_vad_filter(&app, &samples, sample_rate);
+        let audio = filtered.as_deref().unwrap_or(&samples);
+        run_whisper_on_buffer(audio, sample_rate, &whisper_state, &options, &AbortToken::default())
+            .map(|t| post_process_transcript(&app, &t.text))
+    })
+    .await
+    .map_err(|e| format!("Transcription task failed: {:?}", e))?
+}
+
+/// File extensions accepted by file transcription (WAV is the only decoder bundled)
+const TRANSCRIBABLE_EXTENSIONS: &[&str] = &["wav", "wave"];
+
+/// Transcribes an audio file with the same conditioning, VAD and post-processing
+/// as a recording
+fn transcribe_audio_file(app: &AppHandle, path: &std::path::Path, whisper_state: &SharedWhisper) -> Result<String, String> {
+    let (mut samples, sample_rate) = read_wav_mono(path)?;
+    println!("[File] Transcribing {} ({:.1}s)", path.display(), samples.len() as f32 / sample_rate as f32);
+    condition_audio(app, &mut samples, sample_rate);
+    let filtered = apply_vad_filter(app, &samples, sample_rate);
+    let audio = filtered.as_deref().unwrap_or(&samples);
+    run_whisper_on_buffer(audio, sample_rate, whisper_state, &load_decoding_options(app), &AbortToken::default())
+        .map(|t| post_process_transcript(app, &t.text))
+}
+
+/// Tauri command to transcribe an audio file and return the transcript (no paste)
+#[tauri::command]
+async fn transcribe_file(
+    app: AppHandle,
+    path: String,
+    whisper_state: tauri::State<'_, SharedWhisper>,
+) -> Result<String, String> {
+    let whisper_state = whisper_state.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || transcribe_audio_file(&app, std::path::Path::new(&path), &whisper_state))
+        .await
+        .map_err(|e| format!("Transcription task failed: {:?}", e))?
+}
+
+/// Transcribes files dropped onto the main window one after another. Each transcript
+/// is copied to the clipboard (the main window has focus, so there's nothing to paste
+/// into) and emitted as `file_transcription_result`; files that aren't audio are
+/// reported with `file_drop_rejected`.
+fn handle_dropped_files(app: &AppHandle, paths: &[PathBuf]) {
+    let (audio, rejected): (Vec<PathBuf>, Vec<PathBuf>) = paths.iter().cloned().partition(|path| {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| TRANSCRIBABLE_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+    });
+    for path in &rejected {
+        println!("[File] Ignoring dropped file that isn't WAV audio: {}", path.display());
+        let _ = app.emit("file_drop_rejected", path.to_string_lossy().to_string());
+    }
+    if audio.is_empty() {
+        return;
+    }
     
-    println!("[Audio] Found {} input devices, selected: {:?}", devices.len(), selected_mic);
-    Ok(devices)
+    let app = app.clone();
+    let whisper_state = app.state::<SharedWhisper>().inner().clone();
+    std::thread::spawn(move || {
+        for path in audio {
+            let name = path.to_string_lossy().to_string();
+            let _ = app.emit("file_transcription_started", &name);
+            match transcribe_audio_file(&app, &path, &whisper_state) {
+                Ok(text) => {
+                    if !text.is_empty() {
+                        if let Err(e) = copy_to_clipboard(&text) {
+                            eprintln!("[File] {}", e);
+                        }
+                    }
+                    let _ = app.emit("file_transcription_result", serde_json::json!({
+                        "path": name,
+                        "text": text,
+                    }));
+                }
+                Err(e) => {
+                    eprintln!("[File] Failed to transcribe {}: {}", name, e);
+                    let _ = app.emit("file_transcription_error", serde_json::json!({
+                        "path": name,
+                        "error": e,
+                    }));
+                }
+            }
+        }
+    });
 }
 
-/// Tauri command to get the currently selected microphone
+/// Tauri command to check whether continuous dictation is on
 #[tauri::command]
-fn get_selected_microphone(app: AppHandle) -> Option<String> {
-    load_selected_microphone(&app)
+fn get_continuous_dictation(recording_state: tauri::State<Arc<RecordingState>>) -> bool {
+    recording_state.continuous.load(Ordering::SeqCst)
 }
 
-/// Tauri command to set the selected microphone
+/// Tauri command to toggle continuous dictation. Turning it on starts listening
+/// right away; turning it off lets the current utterance finish normally.
 #[tauri::command]
-fn set_selected_microphone(app: AppHandle, device_name: Option<String>) -> Result<(), String> {
-    save_selected_microphone(&app, device_name.as_deref())
+fn set_continuous_dictation(
+    app: AppHandle,
+    enabled: bool,
+    recording_state: tauri::State<Arc<RecordingState>>,
+    audio_ctx: tauri::State<SharedAudio>,
+    whisper_state: tauri::State<SharedWhisper>,
+) -> Result<(), String> {
+    let was_enabled = recording_state.continuous.swap(enabled, Ordering::SeqCst);
+    println!("[Continuous] Continuous dictation {}", if enabled { "enabled" } else { "disabled" });
+    
+    if !enabled {
+        if was_enabled {
+            let _ = app.emit("continuous_dictation_ended", "disabled");
+        }
+        return Ok(());
+    }
+    
+    let idle = !recording_state.is_recording.load(Ordering::SeqCst)
+        && !recording_state.is_processing.load(Ordering::SeqCst);
+    if idle && !begin_recording(&app, recording_state.inner(), audio_ctx.inner(), whisper_state.inner()) {
+        recording_state.continuous.store(false, Ordering::SeqCst);
+        return Err("No Whisper model loaded. Please set a model first.".to_string());
+    }
+    Ok(())
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -1243,12 +9781,196 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_autostart::init(MacosLauncher::LaunchAgent, Some(vec!["--minimized"])))
-        .invoke_handler(tauri::generate_handler![greet, set_active_model, get_active_model, list_models, download_model, load_model, get_autostart_enabled, set_autostart_enabled, list_audio_devices, get_selected_microphone, set_selected_microphone])
+        .invoke_handler(tauri::generate_handler![
+            set_active_model,
+            get_active_model,
+            list_models,
+            download_model,
+            load_model,
+            get_autostart_enabled,
+            set_autostart_enabled,
+            list_audio_devices,
+            get_selected_microphone,
+            set_selected_microphone,
+            get_replacement_rules,
+            set_replacement_rules,
+            preview_replacements,
+            list_monitors,
+            get_overlay_monitor,
+            set_overlay_monitor,
+            get_number_formatting,
+            set_number_formatting,
+            get_gpu_backend,
+            set_gpu_backend,
+            get_hotkey_listener_status,
+            validate_gpu,
+            get_default_model,
+            set_default_model,
+            setup_default_model,
+            get_latency_history,
+            get_continuous_dictation,
+            set_continuous_dictation,
+            import_custom_model,
+            get_audio_buffer_size,
+            set_audio_buffer_size,
+            save_profile,
+            list_profiles,
+            apply_profile,
+            delete_profile,
+            get_skip_punctuation_only,
+            set_skip_punctuation_only,
+            test_mic_level,
+            pause_recording,
+            resume_recording,
+            get_decoding_options,
+            set_max_segment_length,
+            get_queue_during_processing,
+            set_queue_during_processing,
+            get_build_info,
+            get_retention_settings,
+            set_retention_settings,
+            clear_retained_audio,
+            retranscribe_last,
+            report_paste_test,
+            test_paste,
+            get_overlay_animation_ms,
+            set_overlay_animation_ms,
+            get_vad_settings,
+            set_vad_settings,
+            list_vad_status,
+            download_vad_model,
+            set_vad_model,
+            get_webhook_settings,
+            set_webhook_settings,
+            get_preserve_clipboard,
+            set_preserve_clipboard,
+            get_clipboard_restore_delay,
+            set_clipboard_restore_delay,
+            get_download_states,
+            get_fallback_model,
+            set_fallback_model,
+            list_bindable_hotkeys,
+            get_settings_hotkey,
+            set_settings_hotkey,
+            calibrate_noise_floor,
+            get_noise_calibration,
+            transcribe_file_to_subtitles,
+            get_overlay_appearance,
+            set_overlay_appearance,
+            get_channel_weights,
+            set_channel_weights,
+            preview_resampling,
+            get_undo_paste_hotkey,
+            set_undo_paste_hotkey,
+            get_emit_recording_stats,
+            set_emit_recording_stats,
+            get_verify_paste,
+            set_verify_paste,
+            list_history_archives,
+            get_history_for_date,
+            get_history_enabled,
+            set_history_enabled,
+            get_history_retention_days,
+            set_history_retention_days,
+            get_voice_commands,
+            set_voice_commands,
+            get_onboarding_state,
+            complete_onboarding,
+            get_performance_mode,
+            set_performance_mode,
+            get_high_pass_filter,
+            set_high_pass_filter,
+            get_processing_timeout,
+            set_processing_timeout,
+            get_background_mode,
+            set_background_mode,
+            set_segment_language_detection,
+            run_full_benchmark,
+            get_paste_focus_check,
+            set_paste_focus_check,
+            list_output_devices,
+            get_cue_output_device,
+            set_cue_output_device,
+            get_audio_cues,
+            set_audio_cues,
+            get_noise_gate,
+            set_noise_gate,
+            get_paste_target,
+            set_paste_target,
+            get_trailing_phrases,
+            set_trailing_phrases,
+            get_pre_transcription_delay,
+            set_pre_transcription_delay,
+            reload_config,
+            get_record_to_disk,
+            set_record_to_disk,
+            check_hotkey_conflict,
+            get_auto_model_preference,
+            set_auto_model_preference,
+            test_paste_reliability,
+            get_sentence_split,
+            set_sentence_split,
+            check_model_integrity,
+            get_model_integrity_scan,
+            set_model_integrity_scan,
+            get_alt_action,
+            set_alt_action,
+            set_segment_separator,
+            export_shareable_preset,
+            import_shareable_preset,
+            start_tail_transcription,
+            stop_tail_transcription,
+            get_hotkey_mode,
+            set_hotkey_mode,
+            get_language,
+            set_language,
+            get_agc,
+            set_agc,
+            get_translate_mode,
+            set_translate_mode,
+            get_output_mode,
+            set_output_mode,
+            can_transcribe_language,
+            get_typing_delay,
+            set_typing_delay,
+            get_defer_under_load,
+            set_defer_under_load,
+            overlay_ready,
+            get_gpu_warmup,
+            set_gpu_warmup,
+            transcribe_file,
+            get_transient_rejection,
+            set_transient_rejection,
+            get_max_recording_seconds,
+            set_max_recording_seconds,
+            list_models_by_usage,
+            get_gpu_enabled,
+            set_gpu_enabled,
+            get_thread_count,
+            set_thread_count,
+            get_journal_settings,
+            set_journal_settings,
+            get_shared_capture,
+            set_shared_capture,
+            get_sampling_params,
+            set_sampling_params,
+            get_blank_retry,
+            set_blank_retry,
+            get_initial_prompt,
+            set_initial_prompt,
+        ])
         .setup(|app| {
             // Initialize recording state
             let recording_state = Arc::new(RecordingState {
                 is_recording: AtomicBool::new(false),
                 is_processing: AtomicBool::new(false),
+                session_id: AtomicU64::new(0),
+                pasted_session: AtomicU64::new(0),
+                continuous: AtomicBool::new(false),
+                is_paused: AtomicBool::new(false),
+                start_queued: AtomicBool::new(false),
+                start_window: AtomicIsize::new(0),
+                processing_abort: Mutex::new(AbortToken::default()),
             });
             
             // Initialize audio context
@@ -1256,22 +9978,98 @@ pub fn run() {
                 buffer: Vec::new(),
                 sample_rate: 44100, // Default, will be updated when recording starts
                 stop_signal: Arc::new(AtomicBool::new(false)),
+                stream_stopped: Arc::new(AtomicBool::new(true)),
+                disk: None,
             }));
             
             // Initialize Whisper state (model loaded via set_active_model command)
             let whisper_state: SharedWhisper = Arc::new(Mutex::new(WhisperState {
                 ctx: None,
                 model_path: None,
+                backend: None,
             }));
             
-            // Manage whisper state so it can be accessed by commands
+            // Manage shared state so it can be accessed by commands
             app.manage(whisper_state.clone());
+            app.manage(recording_state.clone());
+            app.manage(audio_ctx.clone());
+            
+            let hotkey_status: SharedHotkeyStatus = Arc::new(Mutex::new(None));
+            app.manage(hotkey_status);
+            
+            let latency_history: SharedLatencyHistory = Arc::new(Mutex::new(std::collections::VecDeque::new()));
+            app.manage(latency_history);
             
-            // Auto-load previously selected model
+            let level_history: SharedLevelHistory = Arc::new(Mutex::new(std::collections::VecDeque::new()));
+            app.manage(level_history);
+            
+            let retained_audio: SharedRetainedAudio = Arc::new(Mutex::new(None));
+            app.manage(retained_audio);
+            
+            let paste_test: SharedPasteTest = Arc::new(Mutex::new(None));
+            app.manage(paste_test);
+            
+            let tail: SharedTail = Arc::new(Mutex::new(None));
+            app.manage(tail);
+            app.manage(OverlayVisibility::default());
+            app.manage(DownloadTracker::default());
+            
+            let hotkey_bindings: SharedHotkeyBindings = Arc::new(Mutex::new(HotkeyBindings::load(app.handle())));
+            app.manage(hotkey_bindings);
+            
+            let last_paste: SharedLastPaste = Arc::new(Mutex::new(None));
+            app.manage(last_paste);
+            app.manage(JournalQueue::start(app.handle().clone()));
+            
+            // Restore the selected VAD model into its own slot
+            let vad_model: SharedVadModel = Arc::new(Mutex::new(VadModelState {
+                model_id: None,
+                model_path: None,
+            }));
+            if let Some(model_id) = load_vad_model(app.handle()) {
+                if let Err(e) = load_vad_model_into(app.handle(), &model_id, &vad_model) {
+                    eprintln!("[Startup] Failed to load VAD model: {}", e);
+                }
+            }
+            app.manage(vad_model);
+            
+            // Catch models corrupted by an interrupted download before record time
+            if load_model_integrity_scan(app.handle()) {
+                let app_handle = app.handle().clone();
+                std::thread::spawn(move || {
+                    match scan_model_integrity(&app_handle) {
+                        Ok(report) => { let _ = app_handle.emit("model_integrity_report", &report); }
+                        Err(e) => eprintln!("[Models] Integrity scan failed: {}", e),
+                    }
+                });
+            }
+            
+            // Auto-load previously selected model, then check the GPU path in the background;
+            // the validation inference doubles as the warmup
             auto_load_model(app.handle(), &whisper_state);
+            if load_gpu_settings(app.handle()).backend != "cpu" {
+                let app_handle = app.handle().clone();
+                let whisper_clone = whisper_state.clone();
+                std::thread::spawn(move || {
+                    let report = run_gpu_validation(&app_handle, &whisper_clone);
+                    if load_gpu_warmup(&app_handle) {
+                        emit_gpu_warmup_result(&app_handle, &report);
+                    }
+                });
+            }
             
             // Start hotkey listener with audio context and whisper state
             start_hotkey_listener(app.handle().clone(), recording_state, audio_ctx, whisper_state);
+            
+            // Dropping audio files onto the main window transcribes them
+            if let Some(main) = app.get_webview_window("main") {
+                let app_handle = app.handle().clone();
+                main.on_window_event(move |event| {
+                    if let WindowEvent::DragDrop(DragDropEvent::Drop { paths, .. }) = event {
+                        handle_dropped_files(&app_handle, paths);
+                    }
+                });
+            }
 
             // Build the tray menu
             let show_hide = MenuItem::with_id(app, "show_hide", "Show/Hide", true, None::<&str>)?;
@@ -1295,7 +10093,8 @@ pub fn run() {
                         }
                     }
                     "quit" => {
-                        std::process::exit(0);
+                        let app = app.clone();
+                        std::thread::spawn(move || shutdown(&app));
                     }
                     _ => {}
                 })
@@ -1328,3 +10127,392 @@ pub fn run() {
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tauri::Listener;
+    
+    /// Counts how many times `event` reaches a global listener
+    fn count_events<R: tauri::Runtime>(app: &tauri::AppHandle<R>, event: &str) -> Arc<AtomicU64> {
+        let count = Arc::new(AtomicU64::new(0));
+        let counter = count.clone();
+        app.listen_any(event, move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        count
+    }
+    
+    #[test]
+    fn overlay_helpers_are_no_ops_without_the_window() {
+        let app = tauri::test::mock_app();
+        let handle = app.handle();
+        assert!(overlay_window(handle).is_none());
+        
+        let received = count_events(handle, "transcription_started");
+        emit_to_overlay(handle, "transcription_started", ());
+        assert_eq!(received.load(Ordering::SeqCst), 0);
+    }
+    
+    #[test]
+    fn overlay_helpers_reach_the_window_when_it_exists() {
+        let app = tauri::test::mock_app();
+        tauri::WebviewWindowBuilder::new(&app, OVERLAY_WINDOW, tauri::WebviewUrl::default())
+            .build()
+            .expect("mock overlay window");
+        let handle = app.handle();
+        assert!(overlay_window(handle).is_some());
+        
+        let received = count_events(handle, "transcription_started");
+        emit_to_overlay(handle, "transcription_started", ());
+        assert_eq!(received.load(Ordering::SeqCst), 1);
+    }
+    
+    #[test]
+    fn english_words_to_digits_converts_numbers() {
+        assert_eq!(english_words_to_digits("twenty four"), "24");
+        assert_eq!(english_words_to_digits("one hundred and five"), "105");
+        assert_eq!(english_words_to_digits("three thousand two hundred"), "3200");
+        assert_eq!(english_words_to_digits("it costs twenty-four dollars"), "it costs 24 dollars");
+        assert_eq!(english_words_to_digits("Twenty Four."), "24.");
+    }
+    
+    #[test]
+    fn english_words_to_digits_reads_years_and_digit_strings() {
+        assert_eq!(english_words_to_digits("twenty twenty four"), "2024");
+        assert_eq!(english_words_to_digits("call five five five"), "call 555");
+    }
+    
+    #[test]
+    fn english_words_to_digits_leaves_lone_small_words_and_other_text() {
+        assert_eq!(english_words_to_digits("I have three cats"), "I have three cats");
+        assert_eq!(english_words_to_digits("a hundred times"), "a hundred times");
+        assert_eq!(english_words_to_digits("one and two"), "one and two");
+        assert_eq!(english_words_to_digits("line one\ntwenty one "), "line one\n21 ");
+    }
+    
+    #[test]
+    fn apply_voice_commands_inserts_line_breaks() {
+        let settings = VoiceCommandSettings::default();
+        assert_eq!(apply_voice_commands(&settings, "Hello new line world").text, "Hello\nworld");
+        assert_eq!(apply_voice_commands(&settings, "First. New paragraph. Second.").text, "First.\n\nSecond.");
+    }
+    
+    #[test]
+    fn apply_voice_commands_deletes_what_came_before() {
+        let settings = VoiceCommandSettings::default();
+        let result = apply_voice_commands(&settings, "some words. Delete that. Hello");
+        assert_eq!(result.text, "Hello");
+        assert!(!result.delete_previous);
+        
+        let result = apply_voice_commands(&settings, "Delete that.");
+        assert_eq!(result.text, "");
+        assert!(result.delete_previous);
+    }
+    
+    #[test]
+    fn apply_voice_commands_uppercases_up_to_punctuation() {
+        let settings = VoiceCommandSettings::default();
+        assert_eq!(apply_voice_commands(&settings, "all caps hello world. bye").text, "HELLO WORLD. bye");
+        assert_eq!(apply_voice_commands(&settings, "say all caps hi").text, "say HI");
+    }
+    
+    #[test]
+    fn split_sentences_splits_on_sentence_ends() {
+        assert_eq!(
+            split_sentences("Hello there. How are you? Fine!"),
+            ["Hello there.", "How are you?", "Fine!"],
+        );
+        assert_eq!(split_sentences("He said \"Stop.\" Then left."), ["He said \"Stop.\"", "Then left."]);
+    }
+    
+    #[test]
+    fn split_sentences_keeps_decimals_abbreviations_and_initials() {
+        assert_eq!(split_sentences("Pi is 3.14 today. Yes."), ["Pi is 3.14 today.", "Yes."]);
+        assert_eq!(split_sentences("Dr. Smith met J. Doe. Done."), ["Dr. Smith met J. Doe.", "Done."]);
+        assert_eq!(split_sentences("wait... and then"), ["wait... and then"]);
+        assert!(split_sentences("   ").is_empty());
+    }
+    
+    #[test]
+    fn strip_trailing_phrases_drops_a_final_phrase() {
+        let phrases = vec!["Thank you.".to_string(), "Bye.".to_string()];
+        assert_eq!(strip_trailing_phrases("See you tomorrow. Thank you.", &phrases), "See you tomorrow.");
+        assert_eq!(strip_trailing_phrases("See you tomorrow! bye", &phrases), "See you tomorrow!");
+        assert_eq!(strip_trailing_phrases(" Thank you. ", &phrases), "");
+    }
+    
+    #[test]
+    fn strip_trailing_phrases_leaves_other_text_alone() {
+        let phrases = vec!["Thank you.".to_string()];
+        assert_eq!(strip_trailing_phrases("See you tomorrow.", &phrases), "See you tomorrow.");
+        assert_eq!(strip_trailing_phrases("Thank you. I mean it. Thank you.", &phrases), "Thank you. I mean it. Thank you.");
+        assert_eq!(strip_trailing_phrases("Thank you so much.", &phrases), "Thank you so much.");
+        assert_eq!(strip_trailing_phrases("", &phrases), "");
+    }
+    
+    #[test]
+    fn utc_date_handles_epoch_and_leap_days() {
+        assert_eq!(utc_date(0), "1970-01-01");
+        assert_eq!(utc_date(951_782_400), "2000-02-29");
+        assert_eq!(utc_date(1_709_164_800), "2024-02-29");
+        assert_eq!(utc_date(1_735_689_599), "2024-12-31");
+        assert_eq!(format_utc_timestamp(1_735_689_599), "2024-12-31 23:59:59");
+    }
+    
+    #[test]
+    fn format_subtitle_time_pads_each_field() {
+        assert_eq!(format_subtitle_time(0, ','), "00:00:00,000");
+        assert_eq!(format_subtitle_time(3_723_004, ','), "01:02:03,004");
+        assert_eq!(format_subtitle_time(59_999, '.'), "00:00:59.999");
+    }
+    
+    /// A WAV header up to the start of an empty data chunk
+    fn wav_header(tag: u16, channels: u16, sample_rate: u32, bits: u16) -> Vec<u8> {
+        let block_align = channels * bits / 8;
+        let mut header = Vec::new();
+        header.extend_from_slice(b"RIFF");
+        header.extend_from_slice(&0u32.to_le_bytes());
+        header.extend_from_slice(b"WAVEfmt ");
+        header.extend_from_slice(&16u32.to_le_bytes());
+        header.extend_from_slice(&tag.to_le_bytes());
+        header.extend_from_slice(&channels.to_le_bytes());
+        header.extend_from_slice(&sample_rate.to_le_bytes());
+        header.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+        header.extend_from_slice(&block_align.to_le_bytes());
+        header.extend_from_slice(&bits.to_le_bytes());
+        header.extend_from_slice(b"data");
+        header.extend_from_slice(&0u32.to_le_bytes());
+        header
+    }
+    
+    #[test]
+    fn parse_wav_stream_header_reads_the_format() {
+        let format = parse_wav_stream_header(&wav_header(1, 2, 44_100, 16)).unwrap().unwrap();
+        assert_eq!((format.channels, format.sample_rate, format.bits_per_sample), (2, 44_100, 16));
+        assert!(!format.float);
+        assert_eq!(format.data_offset, 44);
+        assert_eq!(format.block_align(), 4);
+        
+        let format = parse_wav_stream_header(&wav_header(3, 1, 16_000, 32)).unwrap().unwrap();
+        assert!(format.float);
+    }
+    
+    #[test]
+    fn parse_wav_stream_header_skips_padded_chunks() {
+        let mut bytes = wav_header(1, 1, 16_000, 16);
+        let data = bytes.split_off(36);
+        bytes.extend_from_slice(b"LIST");
+        bytes.extend_from_slice(&3u32.to_le_bytes());
+        bytes.extend_from_slice(&[0, 0, 0, 0]);  // 3 bytes plus one of padding
+        bytes.extend_from_slice(&data);
+        let format = parse_wav_stream_header(&bytes).unwrap().unwrap();
+        assert_eq!(format.data_offset, 56);
+    }
+    
+    #[test]
+    fn parse_wav_stream_header_waits_for_a_partial_header() {
+        let header = wav_header(1, 1, 16_000, 16);
+        assert!(parse_wav_stream_header(&header[..6]).unwrap().is_none());
+        assert!(parse_wav_stream_header(&header[..30]).unwrap().is_none());
+        assert!(parse_wav_stream_header(&header[..40]).unwrap().is_none());
+    }
+    
+    #[test]
+    fn parse_wav_stream_header_rejects_other_files() {
+        assert!(parse_wav_stream_header(b"RIFF\0\0\0\0AVI LIST").is_err());
+        assert!(parse_wav_stream_header(&wav_header(1, 1, 16_000, 8)).is_err());
+        assert!(parse_wav_stream_header(&wav_header(1, 0, 16_000, 16)).is_err());
+    }
+    
+    fn stream_format(channels: u16, bits_per_sample: u16, float: bool) -> WavStreamFormat {
+        WavStreamFormat { channels, sample_rate: 16_000, bits_per_sample, float, data_offset: 44 }
+    }
+    
+    #[test]
+    fn decode_wav_frames_averages_channels_and_drops_partial_frames() {
+        let bytes: Vec<u8> = [16_384i16, -16_384, 16_384, 16_384]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .chain([0x01])
+            .collect();
+        assert_eq!(decode_wav_frames(&bytes, &stream_format(2, 16, false)), [0.0, 0.5]);
+    }
+    
+    #[test]
+    fn decode_wav_frames_reads_24_bit_and_float_samples() {
+        assert_eq!(decode_wav_frames(&[0x00, 0x00, 0x40, 0x00, 0x00, 0xC0], &stream_format(1, 24, false)), [0.5, -0.5]);
+        assert_eq!(decode_wav_frames(&0.25f32.to_le_bytes(), &stream_format(1, 32, true)), [0.25]);
+    }
+    
+    #[test]
+    fn level_meter_holds_back_short_spikes() {
+        let mut meter = LevelMeter::new(4.0);
+        assert_eq!(meter.level(0.1), 0.1);
+        assert_eq!(meter.level(1.0), 0.1);
+        assert_eq!(meter.level(1.0), 0.1);
+        // A rise lasting longer than a transient is shown
+        assert_eq!(meter.level(1.0), 1.0);
+        assert_eq!(meter.level(0.1), 0.1);
+    }
+    
+    #[test]
+    fn level_meter_passes_everything_through_without_a_ratio() {
+        let mut meter = LevelMeter::new(0.0);
+        for rms in [0.01, 1.0, 0.5, 0.0] {
+            assert_eq!(meter.level(rms), rms);
+        }
+    }
+    
+    fn segments(texts: &[&str]) -> Vec<String> {
+        texts.iter().map(|t| t.to_string()).collect()
+    }
+    
+    #[test]
+    fn space_separator_adds_a_space_only_where_missing() {
+        let join = |texts: &[&str]| SegmentSeparator::Space.join(&segments(texts));
+        assert_eq!(join(&["Hello", "world."]), "Hello world.");
+        assert_eq!(join(&[" Hello", " world."]), "Hello world.");
+        assert_eq!(join(&["Hello ", "world."]), "Hello world.");
+        assert_eq!(join(&[" Hello", "", " ", " world. "]), "Hello world.");
+        assert_eq!(join(&[]), "");
+    }
+    
+    #[test]
+    fn newline_separator_puts_each_segment_on_its_own_line() {
+        let join = |texts: &[&str]| SegmentSeparator::Newline.join(&segments(texts));
+        assert_eq!(join(&[" Hello.", " How are you?"]), "Hello.\nHow are you?");
+        assert_eq!(join(&["First. ", "  ", "\tSecond."]), "First.\nSecond.");
+        assert_eq!(join(&[" Only one."]), "Only one.");
+    }
+    
+    fn subtitle_segments() -> Vec<TranscriptSegment> {
+        [(0, 1_500, "Hello there."), (1_500, 3_723_004, "General Kenobi.")]
+            .into_iter()
+            .map(|(start_ms, end_ms, text)| TranscriptSegment {
+                start_ms,
+                end_ms,
+                text: text.to_string(),
+                language: None,
+            })
+            .collect()
+    }
+    
+    #[test]
+    fn format_subtitles_writes_srt_cues() {
+        assert_eq!(
+            format_subtitles(&subtitle_segments(), "srt").unwrap(),
+            "1\n00:00:00,000 --> 00:00:01,500\nHello there.\n\n\
+             2\n00:00:01,500 --> 01:02:03,004\nGeneral Kenobi.\n\n",
+        );
+    }
+    
+    #[test]
+    fn format_subtitles_writes_a_webvtt_document() {
+        assert_eq!(
+            format_subtitles(&subtitle_segments(), "vtt").unwrap(),
+            "WEBVTT\n\n\
+             1\n00:00:00.000 --> 00:00:01.500\nHello there.\n\n\
+             2\n00:00:01.500 --> 01:02:03.004\nGeneral Kenobi.\n\n",
+        );
+        assert_eq!(format_subtitles(&[], "vtt").unwrap(), "WEBVTT\n\n");
+    }
+    
+    #[test]
+    fn format_subtitles_rejects_unknown_formats() {
+        assert!(format_subtitles(&subtitle_segments(), "ass").is_err());
+    }
+    
+    #[test]
+    fn expired_retained_audio_is_dropped_on_access() {
+        let mut slot = Some(RetainedAudio {
+            samples: vec![0.0; 16],
+            sample_rate: 16_000,
+            expires_at: Some(std::time::Instant::now()),
+        });
+        drop_stale_retained_audio(&mut slot);
+        assert!(slot.is_none());
+        
+        let mut empty = None;
+        drop_stale_retained_audio(&mut empty);
+        assert!(empty.is_none());
+    }
+    
+    #[test]
+    fn find_text_overlap_finds_where_the_next_chunk_picks_up() {
+        let previous = "we should meet at the station tomorrow";
+        let next = "the station tomorrow morning then";
+        assert_eq!(find_text_overlap(previous, next), previous.find("the station"));
+        assert_eq!(
+            merge_overlapping_text(previous, next, ' '),
+            "we should meet at the station tomorrow morning then",
+        );
+    }
+    
+    #[test]
+    fn find_text_overlap_needs_more_than_one_shared_word() {
+        assert_eq!(find_text_overlap("hello there", "general kenobi"), None);
+        assert_eq!(find_text_overlap("I went home", "home is where"), None);
+        assert_eq!(merge_overlapping_text("hello there", "general kenobi", ' '), "hello there general kenobi");
+        assert_eq!(merge_overlapping_text("hello there", "general kenobi", '\n'), "hello there\ngeneral kenobi");
+    }
+    
+    #[test]
+    fn find_text_overlap_ignores_punctuation_and_case() {
+        let previous = "Then we left. The end";
+        let next = "the END, really";
+        assert_eq!(find_text_overlap(previous, next), previous.find("The end"));
+        assert_eq!(merge_overlapping_text(previous, next, ' '), "Then we left. the END, really");
+    }
+    
+    #[test]
+    fn find_text_overlap_cuts_before_a_non_ascii_word() {
+        let previous = "we ate at the café together";
+        let next = "Café together was nice";
+        assert_eq!(find_text_overlap(previous, next), previous.find("café"));
+        assert_eq!(merge_overlapping_text(previous, next, ' '), "we ate at the Café together was nice");
+    }
+    
+    #[test]
+    fn merge_overlapping_text_handles_empty_text() {
+        assert_eq!(find_text_overlap("", "hello world"), None);
+        assert_eq!(find_text_overlap("hello world", ""), None);
+        assert_eq!(merge_overlapping_text("", "hello world", ' '), "hello world");
+        assert_eq!(merge_overlapping_text("hello world", "", ' '), "hello world");
+        assert_eq!(merge_overlapping_text("", "", ' '), "");
+    }
+    
+    /// Silence and constant-level bursts, each given as (frames, level)
+    fn vad_frames(parts: &[(usize, f32)]) -> Vec<f32> {
+        let frame_len = 16_000 * VAD_FRAME_MS / 1000;
+        parts.iter().flat_map(|&(frames, level)| vec![level; frames * frame_len]).collect()
+    }
+    
+    #[test]
+    fn detect_speech_regions_pads_and_merges_bursts() {
+        // Aggressiveness 1 pads by 6 frames and merges gaps under 16 frames
+        let samples = vad_frames(&[(33, 0.0), (10, 0.5), (10, 0.0), (5, 0.5), (42, 0.0), (5, 0.5), (35, 0.0)]);
+        let frame_len = 16_000 * VAD_FRAME_MS / 1000;
+        assert_eq!(
+            detect_speech_regions(&samples, 16_000, &VadSettings::default()),
+            vec![27 * frame_len..64 * frame_len, 94 * frame_len..111 * frame_len],
+        );
+    }
+    
+    #[test]
+    fn detect_speech_regions_clamps_padding_to_the_buffer() {
+        let samples = vad_frames(&[(2, 0.5), (20, 0.0), (2, 0.5)]);
+        assert_eq!(
+            detect_speech_regions(&samples, 16_000, &VadSettings::default()),
+            vec![0..samples.len()],
+        );
+    }
+    
+    #[test]
+    fn detect_speech_regions_finds_nothing_in_silence() {
+        let settings = VadSettings::default();
+        assert!(detect_speech_regions(&vad_frames(&[(50, 0.0)]), 16_000, &settings).is_empty());
+        assert!(detect_speech_regions(&vad_frames(&[(50, 0.001)]), 16_000, &settings).is_empty());
+        assert!(detect_speech_regions(&[], 16_000, &settings).is_empty());
+    }
+}
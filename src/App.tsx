@@ -13,7 +13,7 @@ import {
   SelectTrigger,
   SelectValue,
 } from "@/components/ui/select";
-import { Layers, Settings, Circle, Download, Check, Loader2, Power, Mic } from "lucide-react";
+import { Layers, Settings, Circle, Download, Check, Loader2, Power, Mic, AlertTriangle } from "lucide-react";
 import "@/index.css";
 
 type Tab = "model" | "settings";
@@ -25,6 +25,13 @@ interface ModelInfo {
   size: string;
   downloaded: boolean;
   active: boolean;
+  english_only: boolean;
+}
+
+interface HotkeyListenerStatus {
+  running: boolean;
+  reason: string | null;
+  remediation: string;
 }
 
 interface AudioDeviceInfo {
@@ -47,6 +54,9 @@ function App() {
   const [audioDevices, setAudioDevices] = useState<AudioDeviceInfo[]>([]);
   const [selectedMicrophone, setSelectedMicrophone] = useState<string | null>(null);
 
+  // Hotkey listener failure (e.g. missing Accessibility permission)
+  const [hotkeyFailure, setHotkeyFailure] = useState<{ reason: string; remediation: string } | null>(null);
+
   // Load models list
   const refreshModels = async () => {
     try {
@@ -79,6 +89,14 @@ function App() {
     invoke<string | null>("get_selected_microphone")
       .then(setSelectedMicrophone)
       .catch(console.error);
+    // Check whether the hotkey listener started
+    invoke<HotkeyListenerStatus>("get_hotkey_listener_status")
+      .then((status) => {
+        if (!status.running && status.reason) {
+          setHotkeyFailure({ reason: status.reason, remediation: status.remediation });
+        }
+      })
+      .catch(console.error);
   }, []);
 
   // Listen for Tauri events
@@ -101,6 +119,15 @@ function App() {
       refreshModels();
     }).then((un) => unlisteners.push(un));
 
+    // Hotkey listener events
+    listen<{ reason: string; remediation: string }>("hotkey_listener_failed", (event) => {
+      setHotkeyFailure({ reason: event.payload.reason, remediation: event.payload.remediation });
+    }).then((un) => unlisteners.push(un));
+
+    listen("hotkey_listener_recovered", () => {
+      setHotkeyFailure(null);
+    }).then((un) => unlisteners.push(un));
+
     return () => {
       unlisteners.forEach((un) => un());
     };
@@ -194,6 +221,20 @@ function App() {
       <main className="flex-1 overflow-hidden">
         <ScrollArea className="h-full group">
           <div className="p-8 max-w-2xl">
+            {/* Hotkey listener failure */}
+            {hotkeyFailure && (
+              <Card className="border-amber-500/40 bg-amber-500/5 mb-6">
+                <CardContent className="flex items-start gap-3 p-4">
+                  <AlertTriangle className="h-4 w-4 text-amber-500 shrink-0 mt-0.5" />
+                  <div className="min-w-0">
+                    <p className="text-sm font-medium text-foreground">Hotkey unavailable</p>
+                    <p className="text-xs text-muted-foreground mt-0.5">{hotkeyFailure.remediation}</p>
+                    <p className="text-[10px] text-muted-foreground/60 font-mono mt-1.5 truncate">{hotkeyFailure.reason}</p>
+                  </div>
+                </CardContent>
+              </Card>
+            )}
+
             {/* Model Tab */}
             {activeTab === "model" && (
               <div className="space-y-6">
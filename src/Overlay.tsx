@@ -1,14 +1,23 @@
 import { useEffect, useState, useRef } from "react";
+import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { getCurrentWindow } from "@tauri-apps/api/window";
-import { AudioWaveform, Loader2, AlertTriangle, XCircle } from "lucide-react";
+import { AudioWaveform, Loader2, AlertTriangle, XCircle, Pause } from "lucide-react";
 
-type OverlayState = "recording" | "transcribing" | "error" | "no_model" | "idle";
+type OverlayState = "recording" | "paused" | "transcribing" | "error" | "no_model" | "idle";
+
+// Number of recent audio levels drawn in the waveform
+const WAVEFORM_BARS = 24;
 
 function Overlay() {
   // Default to "recording" since that's the most common reason to show the overlay
   const [state, setState] = useState<OverlayState>("recording");
   const [errorMessage, setErrorMessage] = useState("");
+  // Fade state driven by the backend, which delays the real hide until the fade completes
+  const [visible, setVisible] = useState(true);
+  const [animationMs, setAnimationMs] = useState(150);
+  const [opacity, setOpacity] = useState(1);
+  const [levels, setLevels] = useState<number[]>([]);
   const listenersReady = useRef(false);
 
   // Log state changes
@@ -19,21 +28,58 @@ function Overlay() {
   useEffect(() => {
     const unlisteners: (() => void)[] = [];
 
+    // Catch up on audio levels emitted while the overlay wasn't listening yet
+    const syncLevels = () => {
+      invoke<number[]>("overlay_ready")
+        .then((history) => setLevels(history.slice(-WAVEFORM_BARS)))
+        .catch(console.error);
+    };
+
     // Set up all listeners - use both global and window-specific listeners
     const setupListeners = async () => {
       const window = getCurrentWindow();
 
       // Global listeners (for events broadcast via app.emit)
+      unlisteners.push(await listen<number>("overlay_will_show", (event) => {
+        setAnimationMs(event.payload);
+        setVisible(true);
+        syncLevels();
+      }));
+
+      unlisteners.push(await listen<number>("audio_level", (event) => {
+        setLevels((prev) => [...prev, event.payload].slice(-WAVEFORM_BARS));
+      }));
+
+      unlisteners.push(await listen<{ opacity: number }>("overlay_appearance", (event) => {
+        setOpacity(event.payload.opacity);
+      }));
+
+      unlisteners.push(await listen<number>("overlay_will_hide", (event) => {
+        setAnimationMs(event.payload);
+        setVisible(false);
+      }));
+
       unlisteners.push(await listen("recording_started", () => {
         console.log("[Overlay] Received recording_started (global)");
         setState("recording");
         setErrorMessage("");
+        syncLevels();
       }));
 
       unlisteners.push(await listen("recording_stopped", () => {
         console.log("[Overlay] Received recording_stopped (global)");
       }));
 
+      unlisteners.push(await listen("recording_paused", () => {
+        console.log("[Overlay] Received recording_paused (global)");
+        setState("paused");
+      }));
+
+      unlisteners.push(await listen("recording_resumed", () => {
+        console.log("[Overlay] Received recording_resumed (global)");
+        setState("recording");
+      }));
+
       unlisteners.push(await listen("transcription_started", () => {
         console.log("[Overlay] Received transcription_started (global)");
         setState("transcribing");
@@ -75,6 +121,8 @@ function Overlay() {
       // Mark listeners as ready
       listenersReady.current = true;
       console.log("[Overlay] All listeners ready (global + window-specific)");
+
+      syncLevels();
     };
 
     setupListeners();
@@ -85,7 +133,10 @@ function Overlay() {
   }, []);
 
   return (
-    <div className="w-screen h-screen flex items-center justify-center bg-gradient-to-br from-zinc-900/95 to-zinc-950/95 backdrop-blur-xl rounded-2xl select-none relative">
+    <div
+      className="w-screen h-screen flex items-center justify-center bg-gradient-to-br from-zinc-900/95 to-zinc-950/95 backdrop-blur-xl rounded-2xl select-none relative transition-opacity ease-in-out"
+      style={{ opacity: visible ? opacity : 0, transitionDuration: `${animationMs}ms` }}
+    >
       {/* Glow effect background */}
       <div className="absolute inset-0 flex items-center justify-center -z-10 blur-3xl opacity-30">
         {state === "recording" && (
@@ -111,6 +162,22 @@ function Overlay() {
               <div className="absolute inset-0 h-7 w-7 rounded-full bg-cyan-400/20 animate-ping" />
             </div>
             <span className="text-base font-semibold text-white tracking-wide drop-shadow-lg">Listening...</span>
+            <div className="flex items-center gap-0.5 h-7">
+              {levels.map((level, i) => (
+                <div
+                  key={i}
+                  className="w-1 rounded-full bg-cyan-400/80"
+                  style={{ height: `${Math.max(8, level * 100)}%` }}
+                />
+              ))}
+            </div>
+          </div>
+        )}
+
+        {state === "paused" && (
+          <div className="flex items-center gap-4">
+            <Pause className="h-6 w-6 text-zinc-300 drop-shadow-[0_0_8px_rgba(212,212,216,0.4)]" />
+            <span className="text-base font-semibold text-white tracking-wide drop-shadow-lg">Paused</span>
           </div>
         )}
 
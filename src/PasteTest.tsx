@@ -0,0 +1,38 @@
+import { useEffect, useRef } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+// Scratch field for the backend's paste test: reports its contents on every change
+function PasteTest() {
+  const fieldRef = useRef<HTMLTextAreaElement>(null);
+
+  useEffect(() => {
+    fieldRef.current?.focus();
+
+    // Cleared between round-trips of the reliability test
+    const unlisten = listen("paste_test_reset", () => {
+      if (fieldRef.current) {
+        fieldRef.current.value = "";
+        fieldRef.current.focus();
+      }
+    });
+    return () => {
+      unlisten.then((un) => un());
+    };
+  }, []);
+
+  return (
+    <div className="h-screen flex flex-col gap-2 p-4 bg-background">
+      <p className="text-xs text-muted-foreground">Testing paste - this window closes automatically.</p>
+      <textarea
+        ref={fieldRef}
+        className="flex-1 resize-none rounded-md border border-border/40 bg-background/50 p-2 text-sm text-foreground font-mono"
+        onChange={(e) => {
+          invoke("report_paste_test", { text: e.target.value }).catch(console.error);
+        }}
+      />
+    </div>
+  );
+}
+
+export default PasteTest;
@@ -3,6 +3,7 @@ import ReactDOM from "react-dom/client";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import App from "./App";
 import Overlay from "./Overlay";
+import PasteTest from "./PasteTest";
 import "./index.css";
 
 // Determine which component to render based on window label
@@ -31,6 +32,13 @@ async function init() {
         <Overlay />
       </React.StrictMode>
     );
+  } else if (label === "paste_test") {
+    // Scratch window the backend pastes into to verify paste simulation
+    root.render(
+      <React.StrictMode>
+        <PasteTest />
+      </React.StrictMode>
+    );
   } else {
     // Main window - full settings UI
     root.render(